@@ -1,194 +1,228 @@
 use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
 use std::sync::Arc;
 use lazy_static::lazy_static;
-use log::{error, info};
+use log::{error, info, warn};
 use tokio::sync::RwLock;
 use client::manager::cache::CacheManager;
-use client::manager::http::Http;
+use client::manager::http::{ApiResult, Http};
 use client::models::components::Emoji;
 use client::models::guild::GuildId;
-use client::models::interaction::{ApplicationCommand, ApplicationCommandOption, ApplicationCommandOptionType, ApplicationCommandType};
+use client::models::interaction::{ApplicationCommand, ApplicationCommandOption};
 use client::models::user::Application;
 use error::RuntimeError;
+use translation::{Language, TranslationNode};
 use crate::constants::ADMIN_GUILD;
+use crate::scripts::registry;
 
-#[allow(dead_code)]
+/// The path of the localizations of the commands in the lang files
+const LOCALIZATIONS_PATH: &str = "slash_commands";
 
-/// This function will send a post request for all scripts
+/// What a synchronization changed, or would change with a dry run, in a scope
+#[derive(Debug, Default)]
+pub(crate) struct SyncReport {
+    /// The guild of the commands, None for the global commands
+    pub guild: Option<GuildId>,
+    pub created: Vec<String>,
+    pub updated: Vec<String>,
+    pub deleted: Vec<String>,
+    pub unchanged: Vec<String>
+}
+
+impl SyncReport {
+    pub(crate) fn has_changes(&self) -> bool {
+        !(self.created.is_empty() && self.updated.is_empty() && self.deleted.is_empty())
+    }
+}
+
+impl Display for SyncReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.guild {
+            Some(guild) => writeln!(f, "**Guild {guild}**")?,
+            None => writeln!(f, "**Global**")?
+        }
+
+        for (label, names) in [("Created", &self.created), ("Updated", &self.updated), ("Deleted", &self.deleted)] {
+            if !names.is_empty() {
+                writeln!(f, "- {label}: {}", names.iter().map(|n| format!("`{n}`")).collect::<Vec<String>>().join(", "))?;
+            }
+        }
+        write!(f, "- Unchanged: {}", self.unchanged.len())
+    }
+}
+
+/// Synchronize the commands of Discord with the registry (`scripts::registry`)
 ///
-///  Don't call too much or you will get rate-limited very badly
-pub(crate) async fn instance_trigger(
-    http: Arc<Http>,
-    cache: Arc<RwLock<CacheManager>>
-) -> (Option<Vec<String>>, Option<Vec<RuntimeError>>)
+/// The current commands are fetched and compared to the registry, then each scope (the global commands and each guild)
+/// with a difference is replaced with a single bulk overwrite: the unchanged commands keep their id and never disappear.
+/// With `dry_run`, nothing is sent and the reports tell what would be done.
+pub(crate) async fn synchronize(
+    http: &Http,
+    cache: &RwLock<CacheManager>,
+    dry_run: bool
+) -> Result<Vec<SyncReport>, RuntimeError>
 {
-    let application = {
-        let app = {
-            let cache = cache.read().await;
+    let application = get_application(http, cache).await?;
+
+    let mut scopes: HashMap<Option<GuildId>, Vec<ApplicationCommand>> = HashMap::new();
+    for container in COMMANDS.read().await.values() {
+        scopes.entry(container.guild.clone())
+            .or_default()
+            .extend(container.commands.values().map(|command| localize(command.clone())));
+    }
 
-            cache.get_application().cloned()
+    let mut reports = Vec::new();
+    let mut registered = Vec::new();
+
+    for (guild, mut commands) in scopes {
+        // the same payload on every synchronization
+        commands.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let current = match &guild {
+            Some(g) => http.get_guild_commands(&application.id, g).await,
+            None => http.get_global_commands(&application.id).await
         };
+        let current = api_result(current, "Failed to fetch the commands", "fetching_commands")?;
 
-        if app.is_none() {
-            match http.fetch_application().await {
-                Ok(Ok(app)) => {
-                    let mut cache = cache.write().await;
-                    cache.update_application(&app);
-
-                    app
-                }
-                Ok(Err(e)) => return (
-                    None,
-                    Some(
-                        vec![
-                            RuntimeError::new(e.to_string())
-                                .with_context("Failed to fetch application")
-                                .with_target("fetching_application")
-                        ]
-                    )
-                ),
-                Err(e) => return (
-                    None,
-                    Some(
-                        vec![
-                            RuntimeError::new(e.to_string())
-                                .with_context("Failed to fetch application")
-                                .with_target("fetching_application")
-                        ]
-                    )
-                )
-            }
+        let report = diff(guild, &commands, &current);
+        info!(target: "CommandsManager", "Commands synchronization{}: {report}", if dry_run { " (dry run)" } else { "" });
+
+        if dry_run || !report.has_changes() {
+            registered.extend(current);
         } else {
-            app.unwrap()
+            let overwritten = match &report.guild {
+                Some(g) => http.bulk_overwrite_guild_commands(&application.id, g, &commands).await,
+                None => http.bulk_overwrite_global_commands(&application.id, &commands).await
+            };
+            registered.extend(api_result(overwritten, "Failed to overwrite the commands", "overwriting_commands")?);
         }
-    };
 
-    // delete all commands
-    if let Err(e) = delete_all_global_commands(http.as_ref(), &application).await {
-        error!(target: "GlobalCommandSuppressor", "Cannot delete all global commands: {e:#?}");
-        return (
-            None,
-            Some(vec![e])
-        )
-    };
-    info!(target: "CommandsManager", "All global commands have been deleted");
-    if let Err(e) = delete_guild_commands(http.as_ref(), &application, ADMIN_GUILD).await {
-        error!(target: "GlobalCommandSuppressor", "Cannot delete all commands in the admin's guild: {e:#?}");
-        return (
-            None,
-            Some(vec![e])
-        )
-    };
-    info!(target: "CommandsManager", "All guild commands have been deleted");
-    {
+        reports.push(report);
+    }
+
+    if !dry_run {
         let mut cache = cache.write().await;
         cache.clear_application_commands();
+        registered.iter().for_each(|command| cache.update_application_command(command));
     }
-    info!(target: "CommandsManager", "All application commands are gone");
 
-    let commands = COMMANDS.read().await;
-    let mut successful_operations: Vec<String> = Vec::new();
-    let mut errors_occurred: Vec<RuntimeError> = Vec::new();
+    // the global commands first
+    reports.sort_by_key(|r| r.guild.as_ref().map(|g| g.to_string()));
+    Ok(reports)
+}
 
-    for (category, commands) in commands.iter() {
-        info!(target: "GlobalCommandCreator", "Creating all slash commands for {category:?}");
+/// Compare the commands of a scope with the current ones of Discord
+fn diff(guild: Option<GuildId>, commands: &[ApplicationCommand], current: &[ApplicationCommand]) -> SyncReport {
+    let mut report = SyncReport { guild, ..Default::default() };
 
-        for (name, command) in commands.commands.iter() {
-            let res = match commands.guild.as_ref() {
-                Some(g) => http.create_guild_application_command(&application.id, g, command).await,
-                None => http.create_global_application_command(&application.id, command).await
-            };
-
-            match res {
-                Ok(Ok(command)) => {
-                    {
-                        let mut cache = cache.write().await;
-                        cache.update_application_command(&command);
-                    }
-                    successful_operations.push(format!("Slash command {name:?} successfully created"));
-                },
-                Ok(Err(e)) => {
-                    error!(target: "GlobalCommandCreator", "An error occured while creating the command {name:?}: {e:#?}");
-                    errors_occurred.push(
-                        RuntimeError::new(e.message)
-                            .with_target(e.code)
-                    );
-                }
-                Err(e) => {
-                    error!(target: "GlobalCommandCreator", "An error occured while trying to create the command {name:?}: {e:#?}");
-                    errors_occurred.push(
-                        RuntimeError::new(e.to_string())
-                    );
-                }
-            };
+    for command in commands {
+        match current.iter().find(|c| c.name == command.name) {
+            None => report.created.push(command.name.clone()),
+            Some(c) if c.same_definition(command) => report.unchanged.push(command.name.clone()),
+            Some(_) => report.updated.push(command.name.clone())
         }
+    }
+    report.deleted = current.iter()
+        .filter(|c| !commands.iter().any(|command| command.name == c.name))
+        .map(|c| c.name.clone())
+        .collect();
+
+    report
+}
+
+fn api_result<T>(result: error::Result<ApiResult<T>>, context: &str, target: &str) -> Result<T, RuntimeError> {
+    match result {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(e)) => {
+            error!(target: "CommandsManager", "{context}: {e:#?}");
+            Err(RuntimeError::new(e.message).with_context(context).with_target(e.code))
+        },
+        Err(e) => {
+            error!(target: "CommandsManager", "{context}: {e:#?}");
+            Err(RuntimeError::new(e.to_string()).with_context(context).with_target(target))
+        }
+    }
+}
 
-
-        info!(target: "GlobalCommandCreator", "All slash commands in the {category:?} have been created");
+async fn get_application(http: &Http, cache: &RwLock<CacheManager>) -> Result<Application, RuntimeError> {
+    if let Some(app) = cache.read().await.get_application().cloned() {
+        return Ok(app);
     }
 
-    info!(target: "CommandsManager", "All commands had been updated");
+    let app = api_result(http.fetch_application().await, "Failed to fetch application", "fetching_application")?;
+    cache.write().await.update_application(&app);
 
-    (
-        Some(successful_operations),
-        Some(errors_occurred)
-    )
+    Ok(app)
 }
 
-pub(crate) async fn delete_all_global_commands(
-    http: &Http,
-    application: &Application
-) -> Result<(), RuntimeError>
-{
-    let all_commands = http.get_global_commands(&application.id).await;
-
-    match all_commands {
-        Ok(Ok(commands)) => {
-            for command in commands {
-                if let Err(e) = http.delete_global_command(&application.id, &command.id).await {
-                    error!(target: "GlobalCommandSuppressor", "Cannot delete a global command: {e:#?}");
-                };
-            }
+/// Add the names and descriptions written in the lang files (`slash_commands::<command>`) to a command and its options
+///
+/// Each language is used for its Discord locales, so adding a language localizes every command
+pub(crate) fn localize(mut command: ApplicationCommand) -> ApplicationCommand {
+    let translations = translation::store::snapshot();
+
+    for lang in translations.languages() {
+        let Some(node) = translation::fmt::get_raw(lang, &format!("{LOCALIZATIONS_PATH}::{}", command.name)) else { continue };
 
-            Ok(())
+        for locale in discord_locales(lang) {
+            if let Some(name) = localized(&node, "name").filter(|n| is_valid_name(n)) {
+                command.name_localizations.get_or_insert_with(HashMap::new).insert(locale.clone(), name);
+            }
+            if let Some(description) = localized(&node, "description") {
+                command.description_localizations.get_or_insert_with(HashMap::new).insert(locale.clone(), description);
+            }
+            if let (Some(options), Some(nodes)) = (command.options.as_mut(), node.get_children("options")) {
+                localize_options(options, nodes, &locale);
+            }
         }
-        Ok(Err(e)) => Err(RuntimeError::new(e.to_string())
-            .with_context("Failed to fetch application")
-            .with_target("fetching_application")),
-        Err(e) => Err(RuntimeError::new(e.to_string())
-            .with_context("Failed to send request for fetching application")
-            .with_target("fetching_application"))
     }
+
+    command
 }
 
-pub(crate) async fn delete_guild_commands(
-    http: &Http,
-    application: &Application,
-    guild_id: impl Into<GuildId>
-) -> Result<(), RuntimeError>
-{
-    let guild_id: GuildId = guild_id.into();
-    let all_commands = http.get_guild_commands(&application.id, &guild_id).await;
-
-    match all_commands {
-        Ok(Ok(commands)) => {
-            for command in commands {
-                if let Err(e) = http.delete_global_command(&application.id, &command.id).await {
-                    error!(target: "GlobalCommandSuppressor", "Cannot delete a command in the guild {}: {e:#?}", guild_id.to_string());
-                };
-            }
+fn localize_options(options: &mut [ApplicationCommandOption], nodes: &TranslationNode, locale: &String) {
+    for option in options {
+        let Some(node) = nodes.get_children(option.name.as_str()) else { continue };
 
-            Ok(())
+        if let Some(name) = localized(node, "name").filter(|n| is_valid_name(n)) {
+            option.name_localizations.get_or_insert_with(HashMap::new).insert(locale.clone(), name);
+        }
+        if let Some(description) = localized(node, "description") {
+            option.description_localizations.get_or_insert_with(HashMap::new).insert(locale.clone(), description);
+        }
+        if let (Some(options), Some(nodes)) = (option.options.as_mut(), node.get_children("options")) {
+            localize_options(options, nodes, locale);
         }
-        Ok(Err(e)) => Err(RuntimeError::new(e.to_string())
-            .with_context("Failed to fetch application")
-            .with_target("fetching_application")),
-        Err(e) => Err(RuntimeError::new(e.to_string())
-            .with_context("Failed to send request for fetching application")
-            .with_target("fetching_application"))
     }
 }
 
+fn localized(node: &TranslationNode, key: &str) -> Option<String> {
+    node.get_children(key)?.value()?.as_str().map(str::to_string)
+}
+
+/// The Discord locales of a language, declared with `discord_locales` in its declaration file
+fn discord_locales(lang: &Language) -> Vec<String> {
+    let declared = translation::fmt::get_raw(lang, "__discord_locales")
+        .and_then(|node| node.value().and_then(|v| v.as_array()).cloned());
+
+    match declared {
+        Some(locales) => locales.iter().filter_map(|l| l.as_str().map(str::to_string)).collect(),
+        None => vec![lang.0.clone()]
+    }
+}
+
+/// Discord refuses the names with uppercase letters, spaces, or more than 32 characters
+fn is_valid_name(name: &str) -> bool {
+    let valid = !name.is_empty()
+        && name.chars().count() <= 32
+        && name.chars().all(|c| c == '-' || c == '_' || (c.is_alphanumeric() && !c.is_uppercase()));
+
+    if !valid {
+        warn!(target: "CommandsManager", "The localized command name {name:?} is invalid and is ignored");
+    }
+    valid
+}
+
 #[derive()]
 pub(crate) struct CommandsContainer {
     pub visible: bool,
@@ -207,19 +241,6 @@ pub(crate) fn get_command_type(storage: &CommandsStorage, command_name: &str) ->
     None
 }
 
-fn commands_vec_to_hashmap(commands: Vec<ApplicationCommand>) -> HashMap<String, ApplicationCommand> {
-    let mut hashmap = HashMap::default();
-
-    for command in commands {
-        hashmap.insert(
-            command.name.clone(),
-            command
-        );
-    }
-
-    hashmap
-}
-
 pub(crate) type CommandsStorage = HashMap<CommandType, CommandsContainer>;
 
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone)]
@@ -314,387 +335,37 @@ impl ToString for CommandType {
 }
 
 
-lazy_static! {
-    pub(crate) static ref COMMANDS: Arc<RwLock<CommandsStorage>> = {
-        let mut commands_storage: CommandsStorage = CommandsStorage::default();
-
-        // insert the dev commands
-        commands_storage.insert(
-            CommandType::Dev,
-            CommandsContainer {
-                visible: false,
-                emoji: "💾".into(),
-                guild: Some(ADMIN_GUILD.into()),
-                commands: commands_vec_to_hashmap(vec![
-                    admin_memory_report_slash(),
-                    admin_reload_langs_slash(),
-                    admin_reload_requests_slash(),
-                    admin_reload_commands_slash()
-                ])
-            }
-        );
-
-        // insert funny commands
-        commands_storage.insert(
-            CommandType::Fun,
-            CommandsContainer {
-                visible: true,
-                guild: None,
-                emoji: "🧨".into(),
-                commands: commands_vec_to_hashmap(vec![
-                    eight_ball_slash(),
-                    welcome_slash(),
-                    unacceptable_slash(),
-                    rateit_slash()
-                ])
-            }
-        );
-
-        // insert useful commands
-        commands_storage.insert(
-            CommandType::Tools,
-            CommandsContainer {
-                visible: true,
-                guild: None,
-                emoji: "🔧".into(),
-                commands: commands_vec_to_hashmap(vec![
-                    citation_slash(),
-                    top_slash(),
-                    cookies_slash(),
-                    guild_rank_slash()
-                ])
-            }
-        );
-
-        // insert common commands
-        commands_storage.insert(
-            CommandType::Common,
-            CommandsContainer {
-                visible: true,
-                guild: None,
-                emoji: "🏷️".into(),
-                commands: commands_vec_to_hashmap(vec![
-                    ping_slash(),
-                    avatar_slash(),
-                    banner_slash(),
-                    user_info(),
-                    help_slash(),
-                    kady_slash()
-                ])
-            }
-        );
-
-        Arc::new(RwLock::new(commands_storage))
+/// The container of the commands of a category
+fn container(category: CommandType) -> CommandsContainer {
+    let (visible, emoji, guild) = match category {
+        CommandType::Dev => (false, "💾", Some(ADMIN_GUILD.into())),
+        CommandType::Fun => (true, "🧨", None),
+        CommandType::Tools => (true, "🔧", None),
+        CommandType::Common | CommandType::All => (true, "🏷️", None)
     };
-}
-
-
-//
-//
-// commands builders
-//
-//
-
-
-
-fn ping_slash() -> ApplicationCommand {
-    ApplicationCommand::new_global(
-        "ping",
-        "🏓 Get Kady's latency",
-        ApplicationCommandType::ChatInput
-    ).add_localization("fr", "ping", "🏓 Obtenez la latence de Kady")
-}
-
-fn guild_rank_slash() -> ApplicationCommand {
-    ApplicationCommand::new_global(
-        "guild_rank",
-        "🏆 Get the guild rank of a player",
-        ApplicationCommandType::ChatInput
-    ).add_localization("fr", "serveur_xp", "🏆 Obtenez le rang du joueur sur le serveur")
-        .set_dm_permission(false)
-        .add_option(
-            ApplicationCommandOption::new(ApplicationCommandOptionType::User, "user", "The user you want to get the rank of", false)
-                .add_description_localization("fr", "L'utilisateur dont vous voulez obtenir le rang")
-        )
-}
-
-fn top_slash() -> ApplicationCommand {
-    ApplicationCommand::new_global(
-        "top",
-        "🏆 Get the rankings for the server... or for the whole world!",
-        ApplicationCommandType::ChatInput
-    ).add_localization("fr", "top", "🏆 Obtenez les classements du serveur... ou du monde entier !")
-        .set_dm_permission(true)
-        .add_option(
-            ApplicationCommandOption::new(
-                ApplicationCommandOptionType::SubCommand,
-                "xp",
-                "⭐ The xp rankings of the server",
-                false
-            ).add_description_localization("fr", "⭐ Le classement de l'expérience du serveur")
-        )
-        .add_option(
-            ApplicationCommandOption::new(
-                ApplicationCommandOptionType::SubCommand,
-                "cookies", // reputation
-                "🍪 Who have the most cookies ??",
-                false
-            ).add_description_localization("fr", "🍪 Qui as le plus de cookies ??")
-        )
-}
-
-
-
-fn admin_reload_commands_slash() -> ApplicationCommand {
-    ApplicationCommand::new_local(
-        "admin_reload_commands",
-        "🔄 Update all slash commands",
-        ApplicationCommandType::ChatInput,
-        crate::constants::ADMIN_GUILD.into(),
-    ).add_localization("fr", "admin_reload_commands", "🔄 Mettre à jour toutes les commandes slash")
-}
-
-fn admin_reload_requests_slash() -> ApplicationCommand {
-    ApplicationCommand::new_local(
-        "admin_reload_requests",
-        "🔄 Update the request list for the database",
-        ApplicationCommandType::ChatInput,
-        crate::constants::ADMIN_GUILD.into(),
-    ).add_localization("fr", "admin_reload_requests", "🔄 Mettre à jour la liste des requêtes pour la base de données")
-}
 
-fn admin_reload_langs_slash() -> ApplicationCommand {
-    ApplicationCommand::new_local(
-        "admin_reload_langs",
-        "🔄 Update the translations",
-        ApplicationCommandType::ChatInput,
-        crate::constants::ADMIN_GUILD.into(),
-    ).add_localization("fr", "admin_reload_langs", "🔄 Mettre à jour les traductions")
-}
-
-fn admin_memory_report_slash() -> ApplicationCommand {
-    ApplicationCommand::new_local(
-        "admin_memory_report",
-        "📄 Get the memory usage",
-        ApplicationCommandType::ChatInput,
-        crate::constants::ADMIN_GUILD.into(),
-    ).add_localization("fr", "admin_memory_report", "📄 Obtenir l'utilisation de la mémoire")
-}
-
-
-
-fn citation_slash() -> ApplicationCommand {
-    ApplicationCommand::new_global(
-        "citation",
-        "✉️ Send a beautiful citation to the whole server",
-        ApplicationCommandType::ChatInput
-    ).add_localization("fr", "citation", "✉️ Envoyez une magnifique citation à tout le serveur")
-        .set_dm_permission(false)
-        .add_option(
-            ApplicationCommandOption::new(ApplicationCommandOptionType::String, "citation", "Your citation here", true)
-                .add_description_localization("fr", "Votre citation")
-        )
-}
-
-fn avatar_slash() -> ApplicationCommand {
-    ApplicationCommand::new_global(
-        "avatar",
-        "👤 Get the beautiful avatar of yourself or your friends",
-        ApplicationCommandType::ChatInput
-    ).add_localization("fr", "avatar", "👤 Obtenez le magnifique avatar de vous-même ou de vos amis")
-        .set_dm_permission(true)
-        .add_option(
-            ApplicationCommandOption::new(ApplicationCommandOptionType::User, "user", "The user (optional)", false)
-                .add_name_localization("fr", "utilisateur")
-                .add_description_localization("fr", "L'utilisateur (optionnel)")
-        )
-}
-
-fn banner_slash() -> ApplicationCommand {
-    ApplicationCommand::new_global(
-        "banner",
-        "👤 Get the beautiful banner of yourself or your friends",
-        ApplicationCommandType::ChatInput
-    ).add_localization("fr", "avatar", "👤 Obtenez la magnifique bannière de vous-même ou de vos amis")
-        .set_dm_permission(true)
-        .add_option(
-            ApplicationCommandOption::new(ApplicationCommandOptionType::User, "user", "The user (optional)", false)
-                .add_name_localization("fr", "utilisateur")
-                .add_description_localization("fr", "L'utilisateur (optionnel)")
-        )
-}
-
-fn cookies_slash() -> ApplicationCommand {
-    ApplicationCommand::new_global(
-        "cookies",
-        "🍪 A cookie ?",
-        ApplicationCommandType::ChatInput
-    ).add_localization("fr", "cookies", "🍪 Un cookie ?")
-        .set_dm_permission(true)
-        .add_option(
-            ApplicationCommandOption::new(
-                ApplicationCommandOptionType::SubCommand,
-                "daily",
-                "🍪 Get your daily cookie by solving an enigma",
-                false
-            )
-                .add_name_localization("fr", "journalier")
-                .add_description_localization("fr", "🍪 Obtient ton cookie quotidien en résolvant une énigme")
-        )
-        .add_option(
-        ApplicationCommandOption::new(
-            ApplicationCommandOptionType::SubCommand,
-            "donate",
-            "🍪 Give one or more cookies to your friends !",
-            false
-        )
-            .add_name_localization("fr", "donner")
-            .add_description_localization("fr", "🍪 Donne un ou plusieurs cookies à tes amis !")
-            .add_option(
-                ApplicationCommandOption::new(
-                    ApplicationCommandOptionType::User,
-                    "user",
-                    "👤 The lucky person who will receive your cookie(s)",
-                    true
-                )
-                    .add_name_localization("fr", "utilisateur")
-                    .add_description_localization("fr", "👤 Le chanceux qui va recevoir votre/vos cookie(s)")
-            )
-            .add_option(
-                ApplicationCommandOption::new(
-                    ApplicationCommandOptionType::Number,
-                    "number",
-                    "🍪 The number of cookies you want to donate",
-                    true
-                )
-                    .add_name_localization("fr", "nombre")
-                    .add_description_localization("fr", "🍪 Le nombre de cookies que vous souhaiter donné(e)")
-            )
-    )
-}
-
-
-
-fn rateit_slash() -> ApplicationCommand {
-    ApplicationCommand::new_global(
-        "rateit",
-        "📒 Will you have the best note ?",
-        ApplicationCommandType::ChatInput
-    ).add_localization("fr", "note", "📒 Allez-vous avoir la meilleure note ?")
-}
-
-
-
-fn eight_ball_slash() -> ApplicationCommand {
-    ApplicationCommand::new_global(
-        "8ball",
-        "🎱 Will the chance be with you ?",
-        ApplicationCommandType::ChatInput
-    ).add_localization("fr", "8ball", "🎱 La chance sera-elle de ton coté ?")
-        .add_option(
-            ApplicationCommandOption::new(
-                ApplicationCommandOptionType::String,
-                "question",
-                "😁 Your question",
-                true
-            )
-                .add_description_localization("fr", "😁 Ta question")
-        )
+    CommandsContainer {
+        visible,
+        emoji: emoji.into(),
+        guild,
+        commands: HashMap::new()
+    }
 }
 
-fn unacceptable_slash() -> ApplicationCommand {
-    ApplicationCommand::new_global(
-        "unacceptable",
-        "💥 This is definitely unacceptable!",
-        ApplicationCommandType::ChatInput
-    ).add_localization("fr", "inacceptable", "💥 C'est définitivement inacceptable !")
-}
 
-fn welcome_slash() -> ApplicationCommand {
-    ApplicationCommand::new_global(
-        "welcome",
-        "👋 Welcome the new members",
-        ApplicationCommandType::ChatInput
-    ).add_localization("fr", "bienvenue", "👋 Souhaite la bienvenue")
-}
+lazy_static! {
+    /// The commands of the registry (`scripts::registry`) by category
+    pub(crate) static ref COMMANDS: Arc<RwLock<CommandsStorage>> = {
+        let mut commands_storage: CommandsStorage = CommandsStorage::default();
 
-fn user_info() -> ApplicationCommand {
-    ApplicationCommand::new_global("userinfo", "👤 Get informations about someone", ApplicationCommandType::ChatInput)
-        .add_localization("fr", "user_info", "👤 Obtenir des informations sur quelqu'un")
-        .add_option(
-            ApplicationCommandOption::new(
-                ApplicationCommandOptionType::User,
-                "user",
-                "👋 The user you want informations about (leave blank to get your infos)",
-                false
-            )
-                .add_name_localization("fr", "utilisateur")
-                .add_description_localization("fr", "👋 L'utilisateur dont tu souhaite voir les informations (laisser vide pour avoir vos infos)")
-        )
-}
+        for command in registry::COMMANDS {
+            let definition = (command.definition)();
+            commands_storage.entry(command.category)
+                .or_insert_with(|| container(command.category))
+                .commands
+                .insert(definition.name.clone(), definition);
+        }
 
-fn help_slash() -> ApplicationCommand {
-    ApplicationCommand::new_global("help", "🔎 Find every Kady's commands!", ApplicationCommandType::ChatInput)
-        .add_localization("fr", "help", "🔎 Trouvez toutes les commandes de Kady !")
-        .add_option(
-            ApplicationCommandOption::new(ApplicationCommandOptionType::String, "command", "🔎 The command you search for", false)
-                .add_description_localization("fr", "🔎 La commande que vous cherchez")
-                .add_name_localization("fr", "commande")
-        )
+        Arc::new(RwLock::new(commands_storage))
+    };
 }
-
-fn kady_slash() -> ApplicationCommand {
-    ApplicationCommand::new_global("kady", "✨ Hello!", ApplicationCommandType::ChatInput)
-        .add_localization("fr", "kady", "✨ Bonjour!")
-        .add_option(
-            ApplicationCommandOption::new(
-                ApplicationCommandOptionType::SubCommand,
-                "invite",
-                "🔗 Invite Kady in your server!",
-                false
-            ).add_description_localization("fr", "🔗 Ajoutez Kady sur votre serveur!")
-        )
-        .add_option(
-            ApplicationCommandOption::new(
-                ApplicationCommandOptionType::SubCommand,
-                "support",
-                "🔗 Join the support server of Kady",
-                false
-            ).add_description_localization("fr", "🔗 Rejoignez le serveur support de Kady")
-        )
-        .add_option(
-            ApplicationCommandOption::new(
-                ApplicationCommandOptionType::SubCommand,
-                "informations",
-                "🔎 Get informations on Kady",
-                false
-            ).add_description_localization("fr", "🔎 Obtenez des informations sur Kady")
-        )
-        .add_option(
-            ApplicationCommandOption::new(
-                ApplicationCommandOptionType::SubCommand,
-                "suggest",
-                "💡 Give a suggestion to the development team!",
-                false
-            ).add_description_localization("fr", "💡 Donnez une suggestion à l'équipe de développement")
-                .add_name_localization("fr", "suggestion")
-        )
-        .add_option(
-            ApplicationCommandOption::new(
-                ApplicationCommandOptionType::SubCommand,
-                "issue",
-                "💥 Report a bug to the development team",
-                false
-            ).add_description_localization("fr", "💥 Signalez une erreur à l'équipe de développement")
-                .add_name_localization("fr", "erreur")
-        )
-        .add_option(
-            ApplicationCommandOption::new(
-                ApplicationCommandOptionType::SubCommand,
-                "review",
-                "✨ Give you opinion about Kady",
-                false
-            ).add_description_localization("fr", "✨ Donnez votre avis sur Kady")
-                .add_name_localization("fr", "avis")
-        )
-}
\ No newline at end of file
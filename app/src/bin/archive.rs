@@ -0,0 +1,217 @@
+//! Manage the PNDR archive holding the secrets of Kady
+//!
+//! ```sh
+//! archive create config/secrets.pndr
+//! archive create --format toml config/secrets.pndr
+//! archive set config/secrets.pndr discord_token            # the value is read from stdin
+//! archive set config/secrets.pndr database.password "..."
+//! archive set --json config/secrets.pndr database.port 3306
+//! archive inspect config/secrets.pndr
+//! archive delete config/secrets.pndr database.password
+//! archive migrate config/secrets.pndr                     # re-encrypt a legacy archive
+//! archive convert config/secrets.pndr yaml                # write the body in another format
+//! ```
+//!
+//! The key is the passphrase given by `KADY_ARCHIVE_PASSPHRASE` or the file given by
+//! `--key-file` / `KADY_ARCHIVE_KEY_FILE`.
+
+use std::io::Read;
+use std::path::PathBuf;
+use std::process::exit;
+use clap::{Parser, Subcommand};
+use serde_json::Value;
+use archive::{Archive, ArchiveDataFormat, ArchiveKey};
+
+const NEW_PASSPHRASE_ENV: &str = "KADY_ARCHIVE_NEW_PASSPHRASE";
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct ArchiveArgs {
+    /// The file holding the key of the archive, overrides the environment variables
+    #[arg(long, global = true)]
+    key_file: Option<PathBuf>,
+    #[command(subcommand)]
+    command: Command
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Create a new empty archive
+    Create {
+        path: PathBuf,
+        /// The format of the body: toml, json or yaml
+        #[arg(long, default_value = "json")]
+        format: ArchiveDataFormat
+    },
+    /// Show the header of the archive and the keys it contains
+    Inspect {
+        path: PathBuf,
+        /// Also print the values, secrets included
+        #[arg(long)]
+        show: bool
+    },
+    /// Set a value, `.` separates the nested keys (e.g. `database.password`), a JSON pointer is needed for a key containing a `.`
+    Set {
+        path: PathBuf,
+        key: String,
+        /// The value; read from stdin if missing
+        value: Option<String>,
+        /// Parse the value as JSON instead of storing it as a string
+        #[arg(long)]
+        json: bool
+    },
+    /// Replace the value of an existing key, fails if the key doesn't exist yet
+    Rotate {
+        path: PathBuf,
+        key: String,
+        /// The new value; read from stdin if missing
+        value: Option<String>,
+        /// Parse the value as JSON instead of storing it as a string
+        #[arg(long)]
+        json: bool
+    },
+    /// Delete a value, an object is deleted with everything it contains and an array item by its index (e.g. `admins.0`)
+    Delete {
+        path: PathBuf,
+        key: String
+    },
+    /// Re-encrypt the archive with the current key, legacy archives are upgraded to the new format
+    Migrate {
+        path: PathBuf
+    },
+    /// Write the body of the archive in another format: toml, json or yaml
+    Convert {
+        path: PathBuf,
+        format: ArchiveDataFormat
+    },
+    /// Re-encrypt the archive with a new key, read from `--new-key-file` or `KADY_ARCHIVE_NEW_PASSPHRASE`
+    Rekey {
+        path: PathBuf,
+        #[arg(long)]
+        new_key_file: Option<PathBuf>
+    }
+}
+
+fn main() {
+    let args = ArchiveArgs::parse();
+
+    let key = args.key_file.map(ArchiveKey::KeyFile).or_else(ArchiveKey::from_env);
+
+    if let Err(e) = run(args.command, key) {
+        eprintln!("{e}");
+        exit(1);
+    }
+}
+
+fn run(command: Command, archive_key: Option<ArchiveKey>) -> Result<(), String> {
+    match command {
+        Command::Create { path, format } => {
+            if path.exists() {
+                return Err(format!("{path:?} already exists"));
+            }
+
+            let key = archive_key.ok_or("A key is required to create an archive")?;
+            Archive::create_with_key(path.clone(), format, key).map_err(|e| format!("Cannot create {path:?}: {e}"))?;
+
+            println!("Archive {path:?} created");
+        }
+        Command::Inspect { path, show } => {
+            let archive = open(&path, archive_key)?;
+
+            println!("version: {}", archive.version);
+            println!("creation: {}", archive.creation);
+            println!("last modification: {}", archive.last_modification);
+            println!("encrypted: {}", archive.is_encrypted());
+            println!("data type: {:?}", archive.data_type());
+
+            for key in archive.keys() {
+                if show {
+                    println!("{key} = {}", archive.get_raw(&key).cloned().unwrap_or_default());
+                } else {
+                    println!("{key} = <redacted>");
+                }
+            }
+        }
+        Command::Set { path, key, value, json } => {
+            let mut archive = open(&path, archive_key)?;
+            archive.set_raw(&key, parse_value(value, json)?).map_err(|e| format!("Cannot set '{key}': {e}"))?;
+            archive.save().map_err(|e| format!("Cannot save {path:?}: {e}"))?;
+
+            println!("'{key}' was set");
+        }
+        Command::Rotate { path, key, value, json } => {
+            let mut archive = open(&path, archive_key)?;
+
+            if archive.get_raw(&key).is_none() {
+                return Err(format!("'{key}' doesn't exist, use `set` to create it"));
+            }
+
+            archive.set_raw(&key, parse_value(value, json)?).map_err(|e| format!("Cannot set '{key}': {e}"))?;
+            archive.save().map_err(|e| format!("Cannot save {path:?}: {e}"))?;
+
+            println!("'{key}' was rotated");
+        }
+        Command::Delete { path, key } => {
+            let mut archive = open(&path, archive_key)?;
+
+            if archive.remove(&key).is_none() {
+                return Err(format!("'{key}' doesn't exist"));
+            }
+            archive.save().map_err(|e| format!("Cannot save {path:?}: {e}"))?;
+
+            println!("'{key}' was deleted");
+        }
+        Command::Migrate { path } => {
+            let key = archive_key.ok_or("A key is required to migrate an archive")?;
+            let archive = open(&path, Some(key))?;
+            archive.save().map_err(|e| format!("Cannot save {path:?}: {e}"))?;
+
+            println!("Archive {path:?} migrated");
+        }
+        Command::Convert { path, format } => {
+            let mut archive = open(&path, archive_key)?;
+            archive.set_data_type(format).map_err(|e| format!("Cannot convert {path:?}: {e}"))?;
+            archive.save().map_err(|e| format!("Cannot save {path:?}: {e}"))?;
+
+            println!("Archive {path:?} converted to {format:?}");
+        }
+        Command::Rekey { path, new_key_file } => {
+            let new_key = new_key_file.map(ArchiveKey::KeyFile)
+                .or_else(|| std::env::var(NEW_PASSPHRASE_ENV).ok().map(ArchiveKey::Passphrase))
+                .ok_or("A new key is required, use `--new-key-file` or `KADY_ARCHIVE_NEW_PASSPHRASE`")?;
+
+            let mut archive = open(&path, archive_key)?;
+            archive.set_key(new_key);
+            archive.save().map_err(|e| format!("Cannot save {path:?}: {e}"))?;
+
+            println!("Archive {path:?} rekeyed");
+        }
+    }
+
+    Ok(())
+}
+
+fn open(path: &PathBuf, key: Option<ArchiveKey>) -> Result<Archive, String> {
+    match key {
+        Some(key) => Archive::open_with_key(path.clone(), key),
+        None => Archive::open(path.clone())
+    }.map_err(|e| format!("Cannot open {path:?}: {e}"))
+}
+
+/// Read the value from stdin if missing, and parse it as JSON if asked
+fn parse_value(value: Option<String>, json: bool) -> Result<Value, String> {
+    let raw = match value {
+        Some(value) => value,
+        None => {
+            let mut buffer = String::new();
+            std::io::stdin().read_to_string(&mut buffer).map_err(|e| format!("Cannot read the value from stdin: {e}"))?;
+            buffer.trim_end().to_string()
+        }
+    };
+
+    if json {
+        serde_json::from_str(&raw).map_err(|e| format!("The value is not valid JSON: {e}"))
+    } else {
+        Ok(Value::String(raw))
+    }
+}
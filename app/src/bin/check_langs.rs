@@ -0,0 +1,49 @@
+//! Check the coverage of the languages and the keys used in the code
+//!
+//! ```sh
+//! check_langs                                   # langs/ against fr, scans app, modules and client
+//! check_langs --reference en langs app modules
+//! check_langs --strict                          # also fail on missing keys and placeholder mismatches
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::process::exit;
+use clap::Parser;
+use translation::coverage;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct CheckArgs {
+    /// The language every other language is compared to
+    #[arg(long, default_value = "fr")]
+    reference: String,
+    /// Fail on missing keys and placeholder mismatches, not only on the unknown keys
+    #[arg(long)]
+    strict: bool,
+    /// The directory of the languages
+    #[arg(default_value = "langs")]
+    langs: PathBuf,
+    /// The directories in which the `message!` calls are searched
+    #[arg(default_values = ["app", "modules", "client"])]
+    sources: Vec<PathBuf>
+}
+
+fn main() {
+    let args = CheckArgs::parse();
+    let sources: Vec<&Path> = args.sources.iter().map(PathBuf::as_path).collect();
+
+    let report = match coverage::check(&args.langs, &args.reference, &sources) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("Cannot check the languages: {e:?}");
+            exit(2);
+        }
+    };
+
+    println!("{report}");
+
+    let incomplete = report.locales.values().any(|l| !l.missing.is_empty() || !l.placeholder_mismatches.is_empty());
+    if report.has_unknown_keys() || (args.strict && incomplete) {
+        exit(1);
+    }
+}
@@ -0,0 +1,52 @@
+use std::sync::Arc;
+use log::info;
+use tokio::sync::RwLock;
+use tokio::sync::watch::Receiver;
+use client::manager::http::{HttpConfiguration, HttpManager};
+use client::supervisor::{self, RestartPolicy};
+use client::typemap::TypeMap;
+use config::Config;
+use crate::crates::status::ShardStatusManager;
+
+/// Everything that must be updated when the config is reloaded
+pub(crate) struct ConfigSubscribers {
+    /// The config shared with the status manager
+    pub(crate) config: Arc<RwLock<Config>>,
+    /// The client data, in which a copy of the config is stored
+    pub(crate) data: Arc<RwLock<TypeMap>>,
+    pub(crate) http: Arc<HttpManager>,
+    pub(crate) status: ShardStatusManager,
+    /// The files declared to the API
+    pub(crate) public_files: Arc<RwLock<Vec<(String, String, String)>>>
+}
+
+/// Propagate each new config to the subscribers
+pub(crate) fn config_reloader(receiver: Receiver<Arc<Config>>, subscribers: ConfigSubscribers) {
+    let subscribers = Arc::new(subscribers);
+
+    supervisor::spawn_loop("config_reloader", RestartPolicy::default(), move || {
+        let mut receiver = receiver.clone();
+        let subscribers = subscribers.clone();
+        async move {
+            while receiver.changed().await.is_ok() {
+                let config = receiver.borrow_and_update().clone();
+
+                *subscribers.config.write().await = (*config).clone();
+                subscribers.data.write().await.insert::<Config>((*config).clone());
+
+                subscribers.http.set_configuration(HttpConfiguration {
+                    retry_limit: config.api.retry_limit,
+                    connect_timeout: std::time::Duration::from_secs(config.api.close_timeout)
+                }).await;
+
+                subscribers.status.set_config(config.status.clone()).await;
+
+                translation::set_default_language(config.default_lang.as_str());
+
+                *subscribers.public_files.write().await = config.api.declared_files.clone();
+
+                info!(target: "ConfigReloader", "The new config was applied");
+            }
+        }
+    });
+}
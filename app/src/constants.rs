@@ -1,7 +1,6 @@
 use client::models::user::UserId;
 use config::Config;
 
-pub const DEFAULT_LANG: &str = "fr";
 pub const DEFAULT_AVATAR: &str = "https://cdn.discordapp.com/embed/avatars/0.png";
 
 pub(crate) const ADMIN_GUILD: &str = "1135937845635317861";
@@ -9,14 +9,16 @@ pub(crate) mod nuggets {
     use std::ops::Deref;
     use std::sync::Arc;
     use std::time::Duration;
+    use chrono::Utc;
     use log::error;
     use tokio::sync::RwLock;
     use tokio::time::sleep;
     use client::manager::cache::CacheManager;
     use client::manager::http::Http;
     use client::models::user::UserId;
+    use client::supervisor::{self, RestartPolicy};
     use database::Database;
-    use database::model::users::{CookiesNumber, UserNuggets};
+    use database::model::users::{CookieOperation, CookiesNumber, UserNuggets};
     use crate::crates::cookies::notify_cookies_given_from_system;
 
 
@@ -27,103 +29,99 @@ pub(crate) mod nuggets {
         cache: Arc<RwLock<CacheManager>>
     )
     {
-        tokio::spawn(async move {
-            'main: loop {
-                let pool = database.get_pool().await;
-                let pool = pool.deref();
-                let requests = database.get_requests().await;
-
-
-                let client_user = {
-                    let cache = cache.read().await;
-
-                    match cache.get_client_user() {
-                        Some(c) => c.clone(),
-                        _ => match http.fetch_client_user().await {
-                            Ok(Ok(c)) => c,
-                            Ok(Err(e)) => {
-                                error!(target: "NuggetUpdater", "An error occured while fetching the client user: {e:#?}");
-                                continue 'main;
-                            }
-                            Err(e) => {
-                                error!(target: "NuggetUpdater", "Cannot fetch the client user: {e:#?}");
-                                continue 'main;
+        supervisor::spawn_loop("nugget_updater", RestartPolicy::default(), move || {
+            let database = database.clone();
+            let http = http.clone();
+            let cache = cache.clone();
+            async move {
+                'main: loop {
+                    let Ok(pool) = database.get_pool().await else {
+                        sleep(Duration::from_secs(60)).await;
+                        continue 'main;
+                    };
+                    let pool = pool.deref();
+                    let requests = database.get_requests().await;
+
+
+                    let client_user = {
+                        let cache = cache.read().await;
+
+                        match cache.get_client_user() {
+                            Some(c) => c.clone(),
+                            _ => match http.fetch_client_user().await {
+                                Ok(Ok(c)) => c,
+                                Ok(Err(e)) => {
+                                    error!(target: "NuggetUpdater", "An error occured while fetching the client user: {e:#?}");
+                                    continue 'main;
+                                }
+                                Err(e) => {
+                                    error!(target: "NuggetUpdater", "Cannot fetch the client user: {e:#?}");
+                                    continue 'main;
+                                }
                             }
                         }
-                    }
-                };
+                    };
 
 
-                let query = sqlx::query_as::<_, UserNuggets>(requests.users.cookies.get_updatable_nuggets.as_str())
-                    .fetch_all(pool)
-                    .await;
+                    let query = sqlx::query_as::<_, UserNuggets>(requests.users.cookies.get_updatable_nuggets.as_str())
+                        .fetch_all(pool)
+                        .await;
 
-                let updatable_users = match query {
-                    Ok(rows) => rows,
-                    Err(e) => {
-                        error!(target: "NuggetUpdater", "An error occured while fetching updatable user nuggets: {e:#?}");
-                        continue 'main;
-                    }
-                };
+                    let updatable_users = match query {
+                        Ok(rows) => rows,
+                        Err(e) => {
+                            error!(target: "NuggetUpdater", "An error occured while fetching updatable user nuggets: {e:#?}");
+                            continue 'main;
+                        }
+                    };
+
+                    // the key is unique for each run, so a run that crashed can be retried safely
+                    let run = Utc::now().format("%Y-%m-%d-%H");
 
-                if !updatable_users.is_empty() {
                     for row in updatable_users.iter() {
-                        let new_cookies = (row.nuggets - row.nuggets % 6) / 6;
-                        let nuggets_after = row.nuggets - (new_cookies * 6);
-
-                        if new_cookies > 0 && nuggets_after != row.nuggets {
-                            // update the user's cookies && nuggets
-
-                            // give cookies
-                            for _ in 0..new_cookies {
-                                let q = sqlx::query(requests.users.cookies.give_cookie.as_str())
-                                    .bind(client_user.id.to_string())
-                                    .bind(&row.user)
-                                    .execute(pool)
-                                    .await;
-
-                                if let Err(e) = q {
-                                    error!(target: "NuggetUpdater", "Cannot add a cookie to '{}': {e:#?}", row.user)
-                                }
+                        let key = format!("nuggets:{}:{run}", row.user);
+
+                        let conversion = UserNuggets::convert(
+                            pool,
+                            &requests.users.cookies,
+                            key.as_str(),
+                            client_user.id.to_string(),
+                            row.user.clone(),
+                            6
+                        ).await;
+
+                        let new_cookies = match conversion {
+                            Ok(CookieOperation::Applied(n)) if n > 0 => n,
+                            Ok(_) => continue,
+                            Err(e) => {
+                                error!(target: "NuggetUpdater", "Cannot convert the nuggets of '{}' into cookies: {e:#?}", row.user);
+                                continue;
                             }
-
-                            // set the new nugget number
-                            let q = sqlx::query(requests.users.cookies.decrease_nuggets.as_str())
-                                .bind(nuggets_after)
-                                .bind(&row.user)
-                                .execute(pool)
-                                .await;
-
-                            if let Err(e) = q {
-                                error!(target: "NuggetUpdater", "Cannot set the number of nuggets for '{}' to '{nuggets_after}' nuggets: {e:#?}", row.user)
+                        };
+
+                        let q = sqlx::query_as::<_, CookiesNumber>(requests.users.cookies.get_cookies_number.as_str())
+                            .bind(&row.user)
+                            .fetch_one(pool)
+                            .await;
+
+                        match q {
+                            Ok(cookies) => {
+                                notify_cookies_given_from_system(
+                                    &http,
+                                    UserId::from(row.user.clone()),
+                                    new_cookies,
+                                    cookies.count as u64
+                                ).await;
                             }
-
-                            {
-                                let q = sqlx::query_as::<_, CookiesNumber>(requests.users.cookies.get_cookies_number.as_str())
-                                    .bind(&row.user)
-                                    .fetch_one(pool)
-                                    .await;
-
-                                match q {
-                                    Ok(cookies) => {
-                                        notify_cookies_given_from_system(
-                                            &http,
-                                            UserId::from(row.user.clone()),
-                                            new_cookies,
-                                            cookies.count as u64
-                                        ).await;
-                                    }
-                                    Err(e) => {
-                                        error!(target: "NuggetUpdater", "An error occured while fetching all cookies from '{}': {e:#?}", row.user)
-                                    }
-                                }
+                            Err(e) => {
+                                error!(target: "NuggetUpdater", "An error occured while fetching all cookies from '{}': {e:#?}", row.user)
                             }
                         }
                     }
-                }
 
-                // 3600s = 1h
-                sleep(Duration::from_secs(3600)).await;
+                    // 3600s = 1h
+                    sleep(Duration::from_secs(3600)).await;
+                }
             }
         });
     }
@@ -200,13 +198,6 @@ pub mod quiz {
             .await
     }
 
-    pub async fn question_completed(pool: &MySqlPool, requests: &DynamicRequest, user: impl ToString) -> Result<MySqlQueryResult, Error> {
-        sqlx::query(requests.system.quiz.question_completed.as_str())
-            .bind(user.to_string())
-            .execute(pool)
-            .await
-    }
-
     pub async fn get_all_possible_answers(pool: &MySqlPool, requests: &DynamicRequest, id: impl ToString) -> Result<Vec<UserQuizAnswer>, Error> {
         sqlx::query_as::<_, UserQuizAnswer>(requests.system.quiz.get_all_possible_answers.as_str())
             .bind(id.to_string())
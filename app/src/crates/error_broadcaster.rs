@@ -1,12 +1,17 @@
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 use std::io;
-use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use chrono::{DateTime, Duration, Utc};
+use lazy_static::lazy_static;
 use log::error;
 use serde::Serialize;
+use sha2::{Digest, Sha256};
 use tokio::fs;
+use tokio::sync::{Mutex, RwLock};
 use uuid::Uuid;
 use client::manager::http::Http;
+use client::supervisor;
 use client::models::channel::ChannelId;
 use client::models::components::Color;
 use client::models::components::embed::{Embed, Field};
@@ -14,9 +19,78 @@ use client::models::components::message_components::{ActionRow, Button, ButtonSt
 use client::models::guild::GuildId;
 use client::models::message::{AttachmentBuilder, MessageAttachmentBuilder, MessageBuilder};
 use client::models::user::UserId;
+use database::Database;
+use database::model::errors::ErrorRecord;
+use error::{ErrorCode, ErrorKind, ErrorReport};
 
 
 const ERROR_BROADCASTER_CHANNEL: &str = "1154827849048543232";
+/// The minimum delay between two broadcasts of the same error, in seconds
+const BROADCAST_INTERVAL: i64 = 600;
+/// The maximum length of an embed field value
+const FIELD_MAX_LENGTH: usize = 1024;
+
+lazy_static! {
+    /// The database where the errors are recorded, the errors are saved in files without it
+    static ref REGISTRY: RwLock<Option<Database>> = RwLock::new(None);
+    static ref RATE_LIMITER: Mutex<RateLimiter> = Mutex::new(RateLimiter::default());
+}
+
+/// Record the errors in the database instead of files
+pub async fn set_registry(database: Database) {
+    *REGISTRY.write().await = Some(database);
+}
+
+/// Broadcast the panics captured in the supervised tasks, with their backtrace
+pub fn broadcast_panics(http: Arc<Http>) {
+    supervisor::set_panic_handler(move |panic| {
+        let http = http.clone();
+
+        tokio::spawn(async move {
+            let localisation = BroadcastLocalisation::default()
+                .set_code_path(panic.location.as_deref().unwrap_or("unknown"));
+            let details = BroadcastDetails::default()
+                .add("task", &panic.task)
+                .add("panic", &panic.message)
+                .add("backtrace", &panic.backtrace);
+
+            crate::broadcast_error!(localisation: localisation, details: details, http.as_ref());
+        });
+    });
+}
+
+/// Limit the broadcasts of an error to one per `BROADCAST_INTERVAL`
+#[derive(Default)]
+struct RateLimiter {
+    /// The last broadcast of each fingerprint, and the number of broadcasts skipped since
+    last_broadcasts: HashMap<String, (DateTime<Utc>, u32)>
+}
+
+impl RateLimiter {
+    /// Return the number of skipped broadcasts if the error can be broadcast
+    fn check(&mut self, fingerprint: &str, now: DateTime<Utc>) -> Option<u32> {
+        let interval = Duration::seconds(BROADCAST_INTERVAL);
+        // the errors that weren't skipped have nothing to report anymore
+        self.last_broadcasts.retain(|_, (date, skipped)| now - *date < interval || *skipped > 0);
+
+        match self.last_broadcasts.get_mut(fingerprint) {
+            Some((date, skipped)) if now - *date < interval => {
+                *skipped += 1;
+                None
+            }
+            Some((date, skipped)) => {
+                let count = *skipped;
+                *date = now;
+                *skipped = 0;
+                Some(count)
+            }
+            None => {
+                self.last_broadcasts.insert(fingerprint.to_string(), (now, 0));
+                Some(0)
+            }
+        }
+    }
+}
 
 
 /// Store the informations about an interaction for the error broadcaster
@@ -117,6 +191,13 @@ impl BroadcastDetails {
         self.informations.insert(k.to_string(), v.to_string());
         self
     }
+
+    /// Add the context and the sources of a report
+    pub fn from_report(report: &ErrorReport) -> Self {
+        report.chain().into_iter()
+            .enumerate()
+            .fold(Self::default().add("kind", format!("{:?}", report.kind)), |details, (i, part)| details.add(format!("cause {i}"), part))
+    }
 }
 
 
@@ -128,6 +209,10 @@ impl BroadcastDetails {
 #[derive(Default, Debug, Serialize)]
 pub struct BroadcastError {
     pub id: Uuid,
+    /// The code of the error, the reports are grouped by code
+    pub code: Option<ErrorCode>,
+    /// The kind of the error, only known for a report
+    pub kind: Option<ErrorKind>,
     pub date: DateTime<Utc>,
     pub localisation: BroadcastLocalisation,
     pub details: BroadcastDetails,
@@ -139,11 +224,61 @@ impl BroadcastError {
         serde_json::to_string_pretty(&self).unwrap()
     }
 
+    /// Identify the identical errors, computed from the code path, the error code and the error kind
+    ///
+    /// The interaction isn't part of it, so the same failure reached from several interactions is grouped.
+    /// The line and column ending the code path aren't either, so moving the code doesn't create a new error
+    pub fn fingerprint(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(without_position(self.localisation.code_path.as_deref().unwrap_or_default()));
+        hasher.update("\n");
+        hasher.update(self.code.map(|c| c.to_string()).unwrap_or_default());
+        hasher.update("\n");
+        hasher.update(self.kind.map(|k| format!("{k:?}")).unwrap_or_default());
+
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// Record the error in the registry, return false if the registry isn't available
+    async fn record(&self) -> bool {
+        let registry = REGISTRY.read().await;
+        let database = match registry.as_ref() {
+            Some(database) => database,
+            None => return false
+        };
+
+        let Ok(pool) = database.get_pool().await else { return false };
+        let requests = database.get_requests().await;
+
+        let result = ErrorRecord::record(
+            &pool,
+            requests.system.errors.record.as_str(),
+            &self.fingerprint(),
+            self.code.map(|c| c.to_string()),
+            self.localisation.code_path.as_deref().unwrap_or("unknown"),
+            &self.to_json()
+        ).await;
+
+        match result {
+            Ok(_) => true,
+            Err(e) => {
+                error!(target: "BroadcastErrorHandler", "Cannot record the error '{}' in the registry: {e:#?}", self.id);
+                false
+            }
+        }
+    }
 
+
+    /// Save the report in `errors/broadcaster/<code>/`, or `errors/broadcaster/` without code
     pub async fn save(&self) -> io::Result<()> {
-        fs::create_dir_all("errors/broadcaster/").await?;
+        let directory = match &self.code {
+            Some(code) => format!("errors/broadcaster/{code}/"),
+            None => "errors/broadcaster/".to_string()
+        };
+
+        fs::create_dir_all(&directory).await?;
         fs::write(
-            format!("errors/broadcaster/{}", self.id),
+            format!("{directory}{}", self.id),
             self.to_json()
         ).await
     }
@@ -154,9 +289,18 @@ impl BroadcastError {
         self
     }
 
+    /// Record the error then send it to the broadcast channel, at most once per `BROADCAST_INTERVAL`
     pub async fn broadcast(&self, http: &Http) {
-        if let Err(e) = self.save().await {
-            error!(target: "BroadcastErrorHandler", "Cannot save the error '{}' to a file: {e:#?}", self.id)
+        if !self.record().await {
+            if let Err(e) = self.save().await {
+                error!(target: "BroadcastErrorHandler", "Cannot save the error '{}' to a file: {e:#?}", self.id)
+            };
+        }
+
+        let fingerprint = self.fingerprint();
+        let skipped = match RATE_LIMITER.lock().await.check(&fingerprint, self.date) {
+            Some(skipped) => skipped,
+            None => return
         };
 
         let file = AttachmentBuilder {
@@ -182,7 +326,16 @@ impl BroadcastError {
         )
         .add_embed(
             Embed::new()
-                .set_description("> **New report**")
+                .set_description({
+                    let mut description = match &self.code {
+                        Some(code) => format!("> **New report** `{code}`\n> Fingerprint: `{}`", &fingerprint[..12]),
+                        None => format!("> **New report**\n> Fingerprint: `{}`", &fingerprint[..12])
+                    };
+                    if skipped > 0 {
+                        description.push_str(&format!("\n> {skipped} identical reports since the last broadcast"));
+                    }
+                    description
+                })
                 .set_color(Color::from_rgb(255, 51, 51))
         )
         .add_attachment(MessageAttachmentBuilder {
@@ -244,7 +397,7 @@ impl BroadcastError {
                 embed = embed.add_field(
                     Field::new()
                         .set_name(k)
-                        .set_value(format!("```\n{v}```"))
+                        .set_value(format!("```\n{}```", truncate(v, FIELD_MAX_LENGTH - 8)))
                         .set_inline(false)
                 );
             }
@@ -284,6 +437,31 @@ impl BroadcastError {
     }
 }
 
+/// Remove the `:<line>` and `:<column>` ending a code path, e.g. `top.rs:categories::xp:104` gives `top.rs:categories::xp`
+fn without_position(code_path: &str) -> &str {
+    let mut code_path = code_path;
+    while let Some((rest, position)) = code_path.rsplit_once(':') {
+        if position.is_empty() || !position.chars().all(|c| c.is_ascii_digit()) {
+            break;
+        }
+        code_path = rest;
+    }
+    code_path
+}
+
+/// Cut the value if it's too long for an embed, the full value is in the attached report
+fn truncate(value: &str, max: usize) -> &str {
+    if value.len() <= max {
+        return value;
+    }
+
+    let mut end = max;
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
+    &value[..end]
+}
+
 
 // Macro definition to create a new BroadcastError
 
@@ -292,9 +470,33 @@ impl BroadcastError {
 /// The macro cannot called outside of an async runtime (async function or tokio::task)
 #[macro_export]
 macro_rules! broadcast_error {
+    (report: $report:expr, localisation: $localisation:expr, interaction: $interaction:expr, $http:expr) => {
+        ($crate::crates::error_broadcaster::BroadcastError {
+            id: uuid::Uuid::new_v4(),
+            code: Some($report.code),
+            kind: Some($report.kind),
+            date: chrono::Utc::now(),
+            localisation: $localisation,
+            details: $crate::crates::error_broadcaster::BroadcastDetails::from_report(&$report),
+            interaction: $interaction
+        }).broadcast($http).await;
+    };
+    (report: $report:expr, localisation: $localisation:expr, $http:expr) => {
+        ($crate::crates::error_broadcaster::BroadcastError {
+            id: uuid::Uuid::new_v4(),
+            code: Some($report.code),
+            kind: Some($report.kind),
+            date: chrono::Utc::now(),
+            localisation: $localisation,
+            details: $crate::crates::error_broadcaster::BroadcastDetails::from_report(&$report),
+            interaction: $crate::crates::error_broadcaster::BroadcastInteraction::default()
+        }).broadcast($http).await;
+    };
     (localisation: $localisation:expr, interaction: $interaction:expr, details: $details:expr, $http:expr) => {
         ($crate::crates::error_broadcaster::BroadcastError {
             id: uuid::Uuid::new_v4(),
+            code: None,
+            kind: None,
             date: chrono::Utc::now(),
             localisation: $localisation,
             details: $details,
@@ -304,6 +506,8 @@ macro_rules! broadcast_error {
     (localisation: $localisation:expr, details: $details:expr, $http:expr) => {
         ($crate::crates::error_broadcaster::BroadcastError {
             id: uuid::Uuid::new_v4(),
+            code: None,
+            kind: None,
             date: chrono::Utc::now(),
             localisation: $localisation,
             details: $details,
@@ -313,6 +517,8 @@ macro_rules! broadcast_error {
     (localisation: $localisation:expr, interaction: $interaction:expr, $http:expr) => {
         ($crate::crates::error_broadcaster::BroadcastError {
             id: uuid::Uuid::new_v4(),
+            code: None,
+            kind: None,
             date: chrono::Utc::now(),
             localisation: $localisation,
             details: $crate::crates::error_broadcaster::BroadcastDetails::default(),
@@ -322,6 +528,8 @@ macro_rules! broadcast_error {
     (localisation: $localisation:expr, $http:expr) => {
         ($crate::crates::error_broadcaster::BroadcastError {
             id: uuid::Uuid::new_v4(),
+            code: None,
+            kind: None,
             date: chrono::Utc::now(),
             localisation: $localisation,
             details: $crate::crates::error_broadcaster::BroadcastDetails::default(),
@@ -331,6 +539,8 @@ macro_rules! broadcast_error {
     (details: $details:expr, interaction: $interaction:expr, $http:expr) => {
         ($crate::crates::error_broadcaster::BroadcastError {
             id: uuid::Uuid::new_v4(),
+            code: None,
+            kind: None,
             date: chrono::Utc::now(),
             localisation: $crate::crates::error_broadcaster::BroadcastLocalisation::default(),
             details: $details,
@@ -340,6 +550,8 @@ macro_rules! broadcast_error {
     (details: $details:expr, $http:expr) => {
         ($crate::crates::error_broadcaster::BroadcastError {
             id: uuid::Uuid::new_v4(),
+            code: None,
+            kind: None,
             date: chrono::Utc::now(),
             localisation: $crate::crates::error_broadcaster::BroadcastLocalisation::default(),
             details: $details,
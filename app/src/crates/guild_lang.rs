@@ -0,0 +1,61 @@
+//! The languages chosen by the guilds (`guilds.lang`)
+//!
+//! They are cached so that the locale of an interaction is resolved without querying the database,
+//! the cache is refreshed when an interaction is received. A guild that didn't choose a language
+//! is cached too, its interactions use the locale given by Discord.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+use lazy_static::lazy_static;
+use log::warn;
+use client::manager::events::Context;
+use client::models::guild::GuildId;
+use database::Database;
+use database::model::guild::Guild;
+
+/// How long a language is used before being fetched again
+const LIFETIME: Duration = Duration::from_secs(10 * 60);
+
+lazy_static! {
+    static ref LANGS: RwLock<HashMap<GuildId, (Option<String>, Instant)>> = RwLock::new(HashMap::new());
+}
+
+/// Get the cached language of a guild, None if the guild didn't choose one
+pub(crate) fn get(guild: &GuildId) -> Option<String> {
+    LANGS.read().ok()?.get(guild).and_then(|(lang, _)| lang.clone())
+}
+
+/// Fetch the language of a guild if it isn't cached or is outdated
+///
+/// The outdated language is kept if the database cannot be reached
+pub(crate) async fn refresh(ctx: &Context, guild: &GuildId) {
+    let fresh = LANGS.read().ok()
+        .and_then(|langs| langs.get(guild).map(|(_, fetched)| fetched.elapsed() < LIFETIME))
+        .unwrap_or(false);
+    if fresh { return; }
+
+    let database = match ctx.get_data::<Database>().await {
+        Some(db) => db,
+        None => return
+    };
+
+    let Ok(pool) = database.get_pool().await else { return };
+    let requests = database.get_requests().await;
+
+    match Guild::get_lang(&pool, requests.guilds.get_lang.as_str(), guild).await {
+        Ok(lang) => {
+            if let Ok(mut langs) = LANGS.write() {
+                langs.insert(guild.clone(), (lang, Instant::now()));
+            }
+        },
+        Err(e) => warn!(target: "GuildLang", "Cannot fetch the language of the guild '{guild}': {e:#?}")
+    }
+}
+
+/// Remove a guild from the cache, e.g. when the client leaves it
+pub(crate) fn forget(guild: &GuildId) {
+    if let Ok(mut langs) = LANGS.write() {
+        langs.remove(guild);
+    }
+}
@@ -1,5 +1,6 @@
 pub mod status;
 pub mod cookies;
+pub mod guild_lang;
 
 #[macro_use]
 pub mod error_broadcaster;
\ No newline at end of file
@@ -10,6 +10,7 @@ use tokio::time::sleep;
 use client::manager::cache::CacheManager;
 use client::manager::shard::ShardManager;
 use client::models::presence::Activity;
+use client::supervisor::{self, RestartPolicy};
 use client::typemap::Type;
 use config::{Config, StatusConfig};
 
@@ -68,118 +69,129 @@ impl ShardStatusManager {
         }
     }
 
+    /// Replace the status settings, the alternate status pool reads them from the shared config
+    pub async fn set_config(&self, config: StatusConfig) {
+        *self.config.write().await = config;
+    }
+
     fn start_alternate_status_pool(
         shards: Arc<RwLock<HashMap<u64, Shard>>>,
         shard_manager: Arc<RwLock<ShardManager>>,
         config: Arc<RwLock<Config>>,
         cache: Arc<RwLock<CacheManager>>
     ) -> JoinHandle<()> {
-        tokio::spawn(async move {
-            // we wait 5 seconds to let the shards the time to connect
-            sleep(Duration::from_secs(5)).await;
-
-            let mut alternate_index: u64 = 0;
-
-            loop {
-                {
-                    let shard_manager = shard_manager.read().await;
-                    let mut shards = shards.write().await;
-
-                    for (id, shard) in shard_manager.get_shards().iter() {
-                        // if this shard is gone, we skip it
-                        if !*shard.run.lock().await {
-                            continue
-                        }
-
-                        // if the shard isn't registered yet, we register it
-                        if shards.get(id).is_none() {
-                            shards.insert(*id, Shard::new_empty());
-                        }
-                        // now we can get the shard from the hashmap safely
-                        let cache_data = shards.get(id).unwrap();
-
-                        // if a custom status was set, we use it
-                        if let Some(custom_status) = &cache_data.custom {
-                            if let Err(e) = format_status(custom_status.clone(), cache.clone(), config.clone()).await.set_presence(shard).await {
-                                error!(target: "ShardStatusManager", "Failed to set the custom status for shard {}: {:?}", id, e);
-                                continue;
-                            };
-                        }
-
-                        // else, we use the state to choose the best status
-                        match cache_data.state {
-                            ShardState::Normal => {
-                                // we set the alternative status
-
-                                let status = {
-                                    let config = config.read().await;
-                                    config.status.alternate[alternate_index as usize].clone()
-                                };
-
-                                if let Err(e) = format_status(status, cache.clone(), config.clone()).await.set_presence(shard).await {
-                                    error!(target: "ShardStatusManager", "Failed to set the alternate status for shard {}: {:?}", id, e);
-                                    continue;
-                                };
+        supervisor::spawn_loop("status_pool", RestartPolicy::default(), move || {
+            let shards = shards.clone();
+            let shard_manager = shard_manager.clone();
+            let config = config.clone();
+            let cache = cache.clone();
+            async move {
+                // we wait 5 seconds to let the shards the time to connect
+                sleep(Duration::from_secs(5)).await;
+
+                let mut alternate_index: u64 = 0;
+
+                loop {
+                    {
+                        let shard_manager = shard_manager.read().await;
+                        let mut shards = shards.write().await;
+
+                        for (id, shard) in shard_manager.get_shards().iter() {
+                            // if this shard is gone, we skip it
+                            if !*shard.run.lock().await {
+                                continue
                             }
-                            ShardState::Dev => {
-                                // we set the dev status
-                                let status = {
-                                    let config = config.read().await;
-                                    config.status.dev.clone()
-                                };
 
-                                if let Err(e) = format_status(status, cache.clone(), config.clone()).await.set_presence(shard).await {
-                                    error!(target: "ShardStatusManager", "Failed to set the dev status for shard {}: {:?}", id, e);
-                                    continue;
-                                };
+                            // if the shard isn't registered yet, we register it
+                            if shards.get(id).is_none() {
+                                shards.insert(*id, Shard::new_empty());
                             }
-                            ShardState::Maintenance => {
-                                // we set the maintenance status
-                                let status = {
-                                    let config = config.read().await;
-                                    config.status.maintenance.clone()
-                                };
+                            // now we can get the shard from the hashmap safely
+                            let cache_data = shards.get(id).unwrap();
 
-                                if let Err(e) = format_status(status, cache.clone(), config.clone()).await.set_presence(shard).await {
-                                    error!(target: "ShardStatusManager", "Failed to set the maintenance status for shard {}: {:?}", id, e);
+                            // if a custom status was set, we use it
+                            if let Some(custom_status) = &cache_data.custom {
+                                if let Err(e) = format_status(custom_status.clone(), cache.clone(), config.clone()).await.set_presence(shard).await {
+                                    error!(target: "ShardStatusManager", "Failed to set the custom status for shard {}: {:?}", id, e);
                                     continue;
                                 };
-                            },
-                            ShardState::Unavailable => {
-                                // we set the unavailable status
-                                let status = {
-                                    let config = config.read().await;
-                                    config.status.unavailable.clone()
-                                };
+                            }
 
-                                if let Err(e) = format_status(status, cache.clone(), config.clone()).await.set_presence(shard).await {
-                                    error!(target: "ShardStatusManager", "Failed to set the unavailable status for shard {}: {:?}", id, e);
-                                    continue;
-                                };
+                            // else, we use the state to choose the best status
+                            match cache_data.state {
+                                ShardState::Normal => {
+                                    // we set the alternative status
+
+                                    let status = {
+                                        let config = config.read().await;
+                                        config.status.alternate[alternate_index as usize].clone()
+                                    };
+
+                                    if let Err(e) = format_status(status, cache.clone(), config.clone()).await.set_presence(shard).await {
+                                        error!(target: "ShardStatusManager", "Failed to set the alternate status for shard {}: {:?}", id, e);
+                                        continue;
+                                    };
+                                }
+                                ShardState::Dev => {
+                                    // we set the dev status
+                                    let status = {
+                                        let config = config.read().await;
+                                        config.status.dev.clone()
+                                    };
+
+                                    if let Err(e) = format_status(status, cache.clone(), config.clone()).await.set_presence(shard).await {
+                                        error!(target: "ShardStatusManager", "Failed to set the dev status for shard {}: {:?}", id, e);
+                                        continue;
+                                    };
+                                }
+                                ShardState::Maintenance => {
+                                    // we set the maintenance status
+                                    let status = {
+                                        let config = config.read().await;
+                                        config.status.maintenance.clone()
+                                    };
+
+                                    if let Err(e) = format_status(status, cache.clone(), config.clone()).await.set_presence(shard).await {
+                                        error!(target: "ShardStatusManager", "Failed to set the maintenance status for shard {}: {:?}", id, e);
+                                        continue;
+                                    };
+                                },
+                                ShardState::Unavailable => {
+                                    // we set the unavailable status
+                                    let status = {
+                                        let config = config.read().await;
+                                        config.status.unavailable.clone()
+                                    };
+
+                                    if let Err(e) = format_status(status, cache.clone(), config.clone()).await.set_presence(shard).await {
+                                        error!(target: "ShardStatusManager", "Failed to set the unavailable status for shard {}: {:?}", id, e);
+                                        continue;
+                                    };
+                                }
                             }
                         }
                     }
-                }
 
-                // we increment the index
-                {
-                    alternate_index += 1;
+                    // we increment the index
+                    {
+                        alternate_index += 1;
 
-                    let config = config.read().await;
+                        let config = config.read().await;
 
-                    // if the index is out of bounds, we reset it
-                    if alternate_index >= config.status.alternate.len() as u64 {
-                        alternate_index %= config.status.alternate.len() as u64;
+                        // if the index is out of bounds, we reset it
+                        if alternate_index >= config.status.alternate.len() as u64 {
+                            alternate_index %= config.status.alternate.len() as u64;
+                        }
                     }
-                }
 
-                let interval = {
-                    let config = config.read().await;
-                    config.status.interval
-                };
+                    let interval = {
+                        let config = config.read().await;
+                        config.status.interval
+                    };
 
-                // we wait 5 minutes before updating the status again :)
-                sleep(Duration::from_secs(interval)).await;
+                    // we wait 5 minutes before updating the status again :)
+                    sleep(Duration::from_secs(interval)).await;
+                }
             }
         })
     }
@@ -1,22 +1,54 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
-use log::error;
+use lazy_static::lazy_static;
+use log::{error, info, warn};
 use sqlx::MySqlPool;
 use tokio::time::Instant;
+use client::manager::events::Context;
+use client::models::guild::GuildId;
+use client::models::user::UserId;
+use client::supervisor::{self, RestartPolicy};
+use config::CleanerConfig;
 use database::Database;
 use database::dynamic_requests::DynamicRequest;
+use database::model::guild::Guild;
+use database::model::users::{User, UserCookie};
 
-pub(crate) fn database_cleaner(database: Database) {
-    tokio::spawn(async move {
-        let database = Arc::new(database);
-        loop {
-            let start_time = Instant::now();
-            let pool = database.get_pool().await;
-            let requests = database.get_requests().await;
+/// The minimum delay between two updates of the last seen timestamp of a user or a guild
+const LAST_SEEN_REFRESH: Duration = Duration::from_secs(60 * 60);
 
-            clear_cookies_quiz(&pool, &requests).await;
+lazy_static! {
+    /// The last time the last seen timestamp of each user (`u<id>`) and guild (`g<id>`) was written
+    static ref LAST_SEEN_WRITES: std::sync::Mutex<HashMap<String, Instant>> = std::sync::Mutex::new(HashMap::new());
+}
+
+pub(crate) fn database_cleaner(database: Database, config: CleanerConfig) {
+    supervisor::spawn_loop("database_cleaner", RestartPolicy::default(), move || {
+        let database = database.clone();
+        let config = config.clone();
+        async move {
+            let database = Arc::new(database);
+            loop {
+                let start_time = Instant::now();
+                let Ok(pool) = database.get_pool().await else {
+                    warn!(target: "DatabaseCleaner", "The database is unavailable, skipping this run");
+                    tokio::time::sleep(Duration::from_secs(5 * 60)).await;
+                    continue;
+                };
+                let requests = database.get_requests().await;
+
+                clear_cookies_quiz(&pool, &requests).await;
+                prune_cookie_operations(&pool, &requests).await;
+                prune_last_seen_writes();
+                purge_expired_users(&pool, &requests, &config).await;
+                purge_expired_guilds(&pool, &requests, &config).await;
 
-            tokio::time::sleep(Duration::from_secs(5 * 60) - (Instant::now() - start_time)).await;
+                drop(pool);
+                drop(requests);
+
+                tokio::time::sleep(Duration::from_secs(5 * 60).saturating_sub(Instant::now() - start_time)).await;
+            }
         }
     });
 }
@@ -25,4 +57,152 @@ async fn clear_cookies_quiz(pool: &MySqlPool, requests: &DynamicRequest) {
     if let Err(e) = sqlx::query(requests.system.quiz.clear_users.as_str()).execute(pool).await {
         error!(target: "Runtime", "An error occured while cleaning the users from the cookies quiz table: {e:#?}");
     }
-}
\ No newline at end of file
+}
+
+/// Delete the old idempotency keys of the cookie operations
+///
+/// They hold no personal data beyond the user ID, so they are pruned even in dry run
+async fn prune_cookie_operations(pool: &MySqlPool, requests: &DynamicRequest) {
+    match UserCookie::prune_operations(pool, requests.system.cleaner.prune_cookie_operations.as_str()).await {
+        Ok(0) => {},
+        Ok(count) => info!(target: "DatabaseCleaner", "{count} old cookie operations were pruned"),
+        Err(e) => error!(target: "DatabaseCleaner", "Cannot prune the cookie operations: {e:#?}")
+    }
+}
+
+/// Delete the users that weren't seen since `config.user_lifetime` and that aren't a known member
+/// of any guild anymore, with all their data (RGPD)
+async fn purge_expired_users(pool: &MySqlPool, requests: &DynamicRequest, config: &CleanerConfig) {
+    if config.dry_run {
+        match User::count_expired(pool, requests.system.cleaner.count_expired_users.as_str(), config.user_lifetime).await {
+            Ok(0) => {},
+            Ok(count) => info!(target: "DatabaseCleaner", "[dry run] {count} expired users would be deleted"),
+            Err(e) => error!(target: "DatabaseCleaner", "Cannot count the expired users: {e:#?}")
+        }
+        return;
+    }
+
+    let mut deleted = 0usize;
+    loop {
+        let ids = match User::get_expired(pool, requests.system.cleaner.get_expired_users.as_str(), config.user_lifetime, config.batch_size).await {
+            Ok(ids) => ids,
+            Err(e) => {
+                error!(target: "DatabaseCleaner", "Cannot fetch the expired users: {e:#?}");
+                break;
+            }
+        };
+
+        let batch_len = ids.len();
+        for id in ids {
+            match User::delete(pool, &requests.system.cleaner.delete_user, &id).await {
+                Ok(_) => deleted += 1,
+                Err(e) => {
+                    // stop there, otherwise the same user would be fetched again in the next batch
+                    warn!(target: "DatabaseCleaner", "Cannot delete the expired user '{id}': {e:#?}");
+                    return log_deleted("users", deleted);
+                }
+            }
+        }
+
+        if (batch_len as u64) < config.batch_size { break; }
+    }
+
+    log_deleted("users", deleted)
+}
+
+/// Delete the guilds that weren't seen since `config.guild_lifetime`, with all their data (RGPD)
+async fn purge_expired_guilds(pool: &MySqlPool, requests: &DynamicRequest, config: &CleanerConfig) {
+    if config.dry_run {
+        match Guild::count_expired(pool, requests.system.cleaner.count_expired_guilds.as_str(), config.guild_lifetime).await {
+            Ok(0) => {},
+            Ok(count) => info!(target: "DatabaseCleaner", "[dry run] {count} expired guilds would be deleted"),
+            Err(e) => error!(target: "DatabaseCleaner", "Cannot count the expired guilds: {e:#?}")
+        }
+        return;
+    }
+
+    let mut deleted = 0usize;
+    loop {
+        let ids = match Guild::get_expired(pool, requests.system.cleaner.get_expired_guilds.as_str(), config.guild_lifetime, config.batch_size).await {
+            Ok(ids) => ids,
+            Err(e) => {
+                error!(target: "DatabaseCleaner", "Cannot fetch the expired guilds: {e:#?}");
+                break;
+            }
+        };
+
+        let batch_len = ids.len();
+        for id in ids {
+            match Guild::delete(pool, &requests.system.cleaner.delete_guild, &id).await {
+                Ok(_) => deleted += 1,
+                Err(e) => {
+                    // stop there, otherwise the same guild would be fetched again in the next batch
+                    warn!(target: "DatabaseCleaner", "Cannot delete the expired guild '{id}': {e:#?}");
+                    return log_deleted("guilds", deleted);
+                }
+            }
+        }
+
+        if (batch_len as u64) < config.batch_size { break; }
+    }
+
+    log_deleted("guilds", deleted)
+}
+
+fn log_deleted(kind: &str, deleted: usize) {
+    if deleted > 0 {
+        info!(target: "DatabaseCleaner", "{deleted} expired {kind} were deleted with their data");
+    }
+}
+
+/// Refresh the last time a user and a guild were seen
+///
+/// Must be called on each activity, otherwise the cleaner will consider the data as expired.
+/// The timestamps are written at most once per `LAST_SEEN_REFRESH` for each user and guild.
+pub(crate) async fn refresh_last_seen(ctx: &Context, user: Option<&UserId>, guild: Option<&GuildId>) {
+    let user = user.filter(|u| should_refresh(format!("u{u}")));
+    let guild = guild.filter(|g| should_refresh(format!("g{g}")));
+
+    if user.is_none() && guild.is_none() { return; }
+
+    let database = match ctx.get_data::<Database>().await {
+        Some(db) => db,
+        None => return
+    };
+
+    let Ok(pool) = database.get_pool().await else { return };
+    let requests = database.get_requests().await;
+
+    if let Some(user) = user {
+        if let Err(e) = User::update_last_seen(&pool, requests.users.update_last_seen.as_str(), user.to_string()).await {
+            warn!(target: "DatabaseCleaner", "Cannot update the last seen timestamp of the user '{user}': {e:#?}");
+        }
+    }
+
+    if let Some(guild) = guild {
+        if let Err(e) = Guild::update_last_seen(&pool, requests.guilds.update_last_seen.as_str(), guild.to_string()).await {
+            warn!(target: "DatabaseCleaner", "Cannot update the last seen timestamp of the guild '{guild}': {e:#?}");
+        }
+    }
+}
+
+/// Return false if the last seen timestamp of the subject was written less than `LAST_SEEN_REFRESH` ago
+fn should_refresh(subject: String) -> bool {
+    let mut writes = LAST_SEEN_WRITES.lock().unwrap_or_else(|e| e.into_inner());
+    let now = Instant::now();
+    match writes.get(&subject) {
+        Some(last) if now - *last < LAST_SEEN_REFRESH => false,
+        _ => {
+            writes.insert(subject, now);
+            true
+        }
+    }
+}
+
+/// Forget the subjects whose last seen timestamp can be written again, so the map doesn't grow forever
+fn prune_last_seen_writes() {
+    let now = Instant::now();
+    LAST_SEEN_WRITES.lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .retain(|_, last| now - *last < LAST_SEEN_REFRESH);
+}
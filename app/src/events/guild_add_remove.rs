@@ -12,6 +12,7 @@ use client::models::message::MessageBuilder;
 use config::Config;
 use database::Database;
 use crate::constants::DEFAULT_AVATAR;
+use crate::crates::guild_lang;
 use crate::scripts::{get_client_user, get_user};
 
 pub async fn guild_create(ctx: &Context, payload: GuildCreate) {
@@ -20,33 +21,34 @@ pub async fn guild_create(ctx: &Context, payload: GuildCreate) {
 
     // delete the guild from the database
     if let Some(db) = ctx.get_data::<Database>().await {
-        let pool = db.get_pool().await;
-        let requests = db.get_requests().await;
-
-        let g_data = database::model::guild::Guild::get_optional(
-            &pool,
-            requests.guilds.get.as_str(),
-            &guild.id
-        ).await;
-
-        // if Ok & None, the guild wasn't registered, so we can say that this is a new guild
-        if let Ok(guild_data) = g_data {
-            if guild_data.is_none() {
-                // register the database
-                let _ = sqlx::query(requests.guilds.create.as_str())
-                    .bind(guild.id.to_string())
-                    .execute(pool.deref())
-                    .await;
-
-                // send the message in the guild
-                if let Some(config) = ctx.get_data::<Config>().await {
-                    send_new_guild_message(
-                        ctx,
-                        &guild,
-                        &config
-                    ).await;
-                } else {
-                    warn!(target: "GuildCreate", "No config in the context data")
+        if let Ok(pool) = db.get_pool().await {
+            let requests = db.get_requests().await;
+
+            let g_data = database::model::guild::Guild::get_optional(
+                &pool,
+                requests.guilds.get.as_str(),
+                &guild.id
+            ).await;
+
+            // if Ok & None, the guild wasn't registered, so we can say that this is a new guild
+            if let Ok(guild_data) = g_data {
+                if guild_data.is_none() {
+                    // register the database
+                    let _ = sqlx::query(requests.guilds.create.as_str())
+                        .bind(guild.id.to_string())
+                        .execute(pool.deref())
+                        .await;
+
+                    // send the message in the guild
+                    if let Some(config) = ctx.get_data::<Config>().await {
+                        send_new_guild_message(
+                            ctx,
+                            &guild,
+                            &config
+                        ).await;
+                    } else {
+                        warn!(target: "GuildCreate", "No config in the context data")
+                    }
                 }
             }
         }
@@ -58,18 +60,21 @@ pub async fn guild_create(ctx: &Context, payload: GuildCreate) {
 pub async fn guild_remove(ctx: &Context, payload: GuildDelete, guild: Option<Guild>) {
     if payload.unavailable { return };
 
+    guild_lang::forget(&payload.id);
+
     // delete the guild from the database
     if let Some(db) = ctx.get_data::<Database>().await {
-        let pool = db.get_pool().await;
-        let requests = db.get_requests().await;
+        if let Ok(pool) = db.get_pool().await {
+            let requests = db.get_requests().await;
 
-        let res = sqlx::query(requests.guilds.delete.as_str())
-            .bind(payload.id.to_string())
-            .execute(pool.deref())
-            .await;
+            let res = sqlx::query(requests.guilds.delete.as_str())
+                .bind(payload.id.to_string())
+                .execute(pool.deref())
+                .await;
 
-        if let Err(e) = res {
-            error!(target: "Runtime", "An error occured while deleting a Guild data's (bot removed): {e:#?}");
+            if let Err(e) = res {
+                error!(target: "Runtime", "An error occured while deleting a Guild data's (bot removed): {e:#?}");
+            }
         }
     }
 
@@ -8,7 +8,7 @@ pub(crate) async fn triggered(ctx: &Context, payload: GuildMemberAdd) {
     let database = ctx.get_data::<Database>().await.expect("No database found");
 
     let guild_data = {
-        let pool = database.get_pool().await;
+        let Ok(pool) = database.get_pool().await else { return };
         match model::guild::Guild::from_pool(&pool, database.get_requests().await.guilds.get.as_str(), &payload.guild_id).await {
             Ok(guild_data) => guild_data,
             Err(e) => {
@@ -6,8 +6,11 @@ use client::models::message::MessageBuilder;
 use database::Database;
 use database::model::guild::Guild;
 use database::model::users::User;
+use error::{ErrorCode, ErrorReport};
 use translation::fmt::formatter::Formatter;
 use translation::message;
+use crate::broadcast_error;
+use crate::crates::error_broadcaster::BroadcastLocalisation;
 
 pub(crate) async fn triggered(ctx: Context, payload: MessageCreate) {
     // add the user to the cache
@@ -19,54 +22,69 @@ pub(crate) async fn triggered(ctx: Context, payload: MessageCreate) {
 
 
     let db = ctx.get_data::<Database>().await.expect("Cannot acquire the Database structure, wtf?");
-    let pool = db.get_pool().await;
 
-    let requests = db.get_requests().await;
+    // the xp and the user registration are skipped while the database is unavailable
+    if let Ok(pool) = db.get_pool().await {
+        let requests = db.get_requests().await;
 
 
-    // register the use in the database
-    {
-        match User::ensure(&pool, requests.users.ensure.as_str(), payload.message.author.id.to_string()).await {
-            Ok(()) => (),
-            Err(e) => {
-                error!(target: "Runtime", "An error occured while ensuring the presence of the author in the database from the message_create event: {e:#?}")
+        // register the use in the database
+        {
+            match User::ensure(&pool, requests.users.ensure.as_str(), payload.message.author.id.to_string()).await {
+                Ok(()) => (),
+                Err(e) => {
+                    error!(target: "Runtime", "An error occured while ensuring the presence of the author in the database from the message_create event: {e:#?}")
+                }
             }
         }
-    }
 
-    if let Some(guild_id) = &payload.guild_id {
-        match Guild::from_pool(&pool, requests.guilds.get.as_str(), guild_id).await {
-            Ok(d) => {
-                let xp_result = features::xp::trigger(
-                    &ctx,
-                    &d,
-                    &pool,
-                    &requests,
-                    &payload.message.author,
-                    &payload.message.channel_id
-                ).await;
+        if let Some(guild_id) = &payload.guild_id {
+            match Guild::from_pool(&pool, requests.guilds.get.as_str(), guild_id).await {
+                Ok(d) => {
+                    let xp_result = features::xp::trigger(
+                        &ctx,
+                        &d,
+                        &pool,
+                        &requests,
+                        &payload.message.author,
+                        &payload.message.channel_id
+                    ).await;
+
+                    match xp_result {
+                        Ok(_) => {}
+                        Err(code) => {
+                            let report = ErrorReport::new(ErrorCode::new(1, code as u16))
+                                .with_context("Cannot trigger the xp system");
 
-                match xp_result {
-                    Ok(_) => {}
-                    Err(code) => {
-                        let _ = payload.message.channel_id.send_message(
-                            &ctx.skynet,
-                            MessageBuilder::new()
-                                .set_content(
-                                    message!(
-                                        d.lang,
-                                        "errors::internal_error",
-                                        Formatter::new().add("code", format!("01{code:03}"))
+                            let _ = payload.message.channel_id.send_message(
+                                &ctx.skynet,
+                                MessageBuilder::new()
+                                    .set_content(
+                                        message!(
+                                            d.lang_or(&translation::default_language().0),
+                                            report.translation_key(),
+                                            Formatter::new().add("code", report.code)
+                                        )
                                     )
-                                )
-                        ).await;
+                            ).await;
+
+                            broadcast_error!(
+                                report: report,
+                                localisation: BroadcastLocalisation::default()
+                                    .set_guild(payload.guild_id.clone())
+                                    .set_channel(Some(payload.message.channel_id.clone()))
+                                    .set_user(Some(payload.message.author.id.clone()))
+                                    .set_code_path("app/src/events/message_create.rs:xp"),
+                                ctx.skynet.as_ref()
+                            );
+                        }
                     }
+                },
+                Err(e) => {
+                    error!(target: "Runtime", "cannot acquire the guild informations in the MessageCreate event: {e:#?}");
                 }
-            },
-            Err(e) => {
-                error!(target: "Runtime", "cannot acquire the guild informations in the MessageCreate event: {e:#?}");
-            }
-        };
+            };
+        }
     }
 
     // blep
@@ -5,8 +5,7 @@ use client::models::components::embed::{Author, Embed, Footer};
 use client::models::events::MessageDelete;
 use client::models::message::{Message, MessageBuilder};
 use database::{Database, model};
-use translation::message;
-use translation::fmt::formatter::Formatter;
+use translation::checked_message;
 
 pub(crate) async fn triggered(ctx: &Context, payload: MessageDelete) {
     let message = {
@@ -37,7 +36,7 @@ pub(crate) async fn triggered(ctx: &Context, payload: MessageDelete) {
 
     // Fetch guild data
     let guild_data = {
-        let pool = database.get_pool().await;
+        let Ok(pool) = database.get_pool().await else { return };
         match model::guild::Guild::from_pool(&pool, database.get_requests().await.guilds.get.as_str(), &payload.guild_id.expect("No guild ID found")).await {
             Ok(guild_data) => guild_data,
             Err(e) => {
@@ -71,11 +70,11 @@ async fn ghostping(ctx: &Context, message: &Message, guild_data: &model::guild::
         let mut embed = Embed::new()
             .set_author(
                 Author::new()
-                    .set_name(message!("fr", "feature::ghostping::author"))
+                    .set_name(checked_message!("fr", features::ghostping::author))
                     .set_icon_url(client_user.avatar_url(1024, false, "png"))
             )
-            .set_footer(Footer::new().set_text(message!("fr", "const::copyright")))
-            .set_color(Color::from_hex(message!("fr", "const::palette::main").to_string()));
+            .set_footer(Footer::new().set_text(checked_message!("fr", r#const::copyright)))
+            .set_color(Color::from_hex(checked_message!("fr", r#const::palette::main).to_string()));
 
         // define the embed description
         embed = if ghostping.mentions.len() == 1 {
@@ -83,36 +82,26 @@ async fn ghostping(ctx: &Context, message: &Message, guild_data: &model::guild::
             let mention = &ghostping.mentions[0];
             if mention.count > 1 {
                 embed.set_description(
-                    message!(
-                        "fr",
-                        "feature::ghostping::multiple",
-                        Formatter::new()
-                            .add("mention", mention.user.to_string())
-                            .add("count", mention.count.to_string())
-                            .add("author", message.author.id.clone())
-                    )
+                    checked_message!("fr", features::ghostping::multiple {
+                        mention: &mention.user,
+                        count: mention.count,
+                        author: &message.author.id
+                    })
                 )
             } else {
                 embed.set_description(
-                    message!(
-                        "fr",
-                        "feature::ghostping::single",
-                        Formatter::new()
-                            .add("mention", mention.user.to_string())
-                            .add("author", message.author.id.clone())
-                    )
+                    checked_message!("fr", features::ghostping::single {
+                        mention: &mention.user,
+                        author: &message.author.id
+                    })
                 )
             }
         } else {
             embed.set_description(
-                message!(
-                    "fr",
-                    "feature::ghostping::a_lot",
-                    Formatter::new()
-                        .add("count", ghostping.mentions.len().to_string())
-                        .add("author", message.author.id.clone())
-                        .add("mentions", ghostping.mentions.iter().map(|m| format!("<@{}>", m.user)).collect::<Vec<_>>().join(", "))
-                )
+                checked_message!("fr", features::ghostping::a_lot {
+                    author: &message.author.id,
+                    mentions: ghostping.mentions.iter().map(|m| format!("<@{}>", m.user)).collect::<Vec<_>>().join(", ")
+                })
             )
         };
 
@@ -6,6 +6,7 @@ mod application_commands_manager;
 mod scripts;
 mod assets;
 mod database_cleaner;
+mod config_reloader;
 
 use std::ops::Deref;
 use std::path::PathBuf;
@@ -21,10 +22,11 @@ use client::models::components::message_components::ComponentType;
 use client::models::events::{GuildCreate, GuildDelete, GuildMemberUpdate, InteractionCreate, MessageCreate, Ready};
 use client::models::interaction::InteractionType;
 use client::models::message::MessageBuilder;
-use config::Config;
+use config::{Config, ConfigService};
 use database::Database;
 use translation::message;
 use clap::Parser;
+use sha2::Digest;
 use client::typemap::Type;
 use crate::constants::ADMIN_GUILD;
 use crate::scripts::{get_application, get_guild};
@@ -72,6 +74,10 @@ impl EventHandler for Handler {
             }
         }
 
+        if let Some(guild) = payload.guild.as_ref() {
+            database_cleaner::refresh_last_seen(&ctx, None, Some(&guild.id)).await;
+        }
+
         events::guild_add_remove::guild_create(&ctx, payload).await;
     }
 
@@ -116,40 +122,45 @@ impl EventHandler for Handler {
         // ensure the guild exists in the database
         if let Some(guild_id) = payload.guild_id.clone()         {
             if let Some(db) = ctx.data.read().await.get::<Database>() {
-                let pool = db.get_pool().await;
-                let requests = db.get_requests().await;
+                if let Ok(pool) = db.get_pool().await {
+                    let requests = db.get_requests().await;
 
-                let id = guild_id.to_string();
+                    let id = guild_id.to_string();
 
-                let has_res = sqlx::query(requests.guilds.has.as_str())
-                    .bind(&id)
-                    .fetch_one(pool.deref())
-                    .await;
-
-                // if 'has_res' is an Err(_), well, the data don't exist :)
-                if has_res.is_err() {
-                    let _ = sqlx::query(requests.guilds.ensure.as_str())
+                    let has_res = sqlx::query(requests.guilds.has.as_str())
                         .bind(&id)
-                        .execute(pool.deref())
+                        .fetch_one(pool.deref())
                         .await;
 
-                    let guild = get_guild(&ctx, &guild_id).await;
-
-                    if let Some(g) = guild {
-                        if let Some(config) = ctx.get_data().await {
-                            events::guild_add_remove::send_new_guild_message(
-                                &ctx,
-                                &g,
-                                &config
-                            ).await;
-                        } else {
-                            warn!(target: "Runtime", "The config object was not found in the context data");
+                    // if 'has_res' is an Err(_), well, the data don't exist :)
+                    if has_res.is_err() {
+                        let _ = sqlx::query(requests.guilds.ensure.as_str())
+                            .bind(&id)
+                            .execute(pool.deref())
+                            .await;
+
+                        let guild = get_guild(&ctx, &guild_id).await;
+
+                        if let Some(g) = guild {
+                            if let Some(config) = ctx.get_data().await {
+                                events::guild_add_remove::send_new_guild_message(
+                                    &ctx,
+                                    &g,
+                                    &config
+                                ).await;
+                            } else {
+                                warn!(target: "Runtime", "The config object was not found in the context data");
+                            }
                         }
                     }
                 }
             }
         }
 
+        if !payload.message.author.bot.unwrap_or(false) {
+            database_cleaner::refresh_last_seen(&ctx, Some(&payload.message.author.id), payload.guild_id.as_ref()).await;
+        }
+
         events::message_create::triggered(ctx, payload).await;
     }
 
@@ -159,34 +170,35 @@ impl EventHandler for Handler {
         // ensure the guild exists in the database
         if let Some(guild_id) = payload.guild_id.clone()         {
             if let Some(db) = ctx.data.read().await.get::<Database>() {
-                let pool = db.get_pool().await;
-                let requests = db.get_requests().await;
+                if let Ok(pool) = db.get_pool().await {
+                    let requests = db.get_requests().await;
 
-                let id = guild_id.to_string();
+                    let id = guild_id.to_string();
 
-                let has_res = sqlx::query(requests.guilds.has.as_str())
-                    .bind(&id)
-                    .fetch_one(pool.deref())
-                    .await;
-
-                // if 'has_res' is an Err(_), well, the data don't exist :)
-                if has_res.is_err() {
-                    let _ = sqlx::query(requests.guilds.ensure.as_str())
+                    let has_res = sqlx::query(requests.guilds.has.as_str())
                         .bind(&id)
-                        .execute(pool.deref())
+                        .fetch_one(pool.deref())
                         .await;
 
-                    let guild = get_guild(&ctx, &guild_id).await;
-
-                    if let Some(g) = guild {
-                        if let Some(config) = ctx.get_data().await {
-                            events::guild_add_remove::send_new_guild_message(
-                                &ctx,
-                                &g,
-                                &config
-                            ).await;
-                        } else {
-                            warn!(target: "Runtime", "The config object was not found in the context data");
+                    // if 'has_res' is an Err(_), well, the data don't exist :)
+                    if has_res.is_err() {
+                        let _ = sqlx::query(requests.guilds.ensure.as_str())
+                            .bind(&id)
+                            .execute(pool.deref())
+                            .await;
+
+                        let guild = get_guild(&ctx, &guild_id).await;
+
+                        if let Some(g) = guild {
+                            if let Some(config) = ctx.get_data().await {
+                                events::guild_add_remove::send_new_guild_message(
+                                    &ctx,
+                                    &g,
+                                    &config
+                                ).await;
+                            } else {
+                                warn!(target: "Runtime", "The config object was not found in the context data");
+                            }
                         }
                     }
                 }
@@ -217,34 +229,35 @@ impl EventHandler for Handler {
         // ensure the guild exists in the database
         {
             if let Some(db) = ctx.data.read().await.get::<Database>() {
-                let pool = db.get_pool().await;
-                let requests = db.get_requests().await;
-
-                let id = payload.guild_id.to_string();
+                if let Ok(pool) = db.get_pool().await {
+                    let requests = db.get_requests().await;
 
-                let has_res = sqlx::query(requests.guilds.has.as_str())
-                    .bind(&id)
-                    .fetch_one(pool.deref())
-                    .await;
+                    let id = payload.guild_id.to_string();
 
-                // if 'has_res' is an Err(_), well, the data don't exist :)
-                if has_res.is_err() {
-                    let _ = sqlx::query(requests.guilds.ensure.as_str())
+                    let has_res = sqlx::query(requests.guilds.has.as_str())
                         .bind(&id)
-                        .execute(pool.deref())
+                        .fetch_one(pool.deref())
                         .await;
 
-                    let guild = get_guild(&ctx, &payload.guild_id).await;
-
-                    if let Some(g) = guild {
-                        if let Some(config) = ctx.get_data().await {
-                            events::guild_add_remove::send_new_guild_message(
-                                &ctx,
-                                &g,
-                                &config
-                            ).await;
-                        } else {
-                            warn!(target: "Runtime", "The config object was not found in the context data");
+                    // if 'has_res' is an Err(_), well, the data don't exist :)
+                    if has_res.is_err() {
+                        let _ = sqlx::query(requests.guilds.ensure.as_str())
+                            .bind(&id)
+                            .execute(pool.deref())
+                            .await;
+
+                        let guild = get_guild(&ctx, &payload.guild_id).await;
+
+                        if let Some(g) = guild {
+                            if let Some(config) = ctx.get_data().await {
+                                events::guild_add_remove::send_new_guild_message(
+                                    &ctx,
+                                    &g,
+                                    &config
+                                ).await;
+                            } else {
+                                warn!(target: "Runtime", "The config object was not found in the context data");
+                            }
                         }
                     }
                 }
@@ -363,34 +376,35 @@ impl EventHandler for Handler {
         // ensure the guild exists in the database
         if let Some(guild_id) = &payload.interaction.guild_id {
             if let Some(db) = ctx.data.read().await.get::<Database>() {
-                let pool = db.get_pool().await;
-                let requests = db.get_requests().await;
-
-                let id = guild_id.to_string();
+                if let Ok(pool) = db.get_pool().await {
+                    let requests = db.get_requests().await;
 
-                let has_res = sqlx::query(requests.guilds.has.as_str())
-                    .bind(&id)
-                    .fetch_one(pool.deref())
-                    .await;
+                    let id = guild_id.to_string();
 
-                // if 'has_res' is an Err(_), well, the data don't exist :)
-                if has_res.is_err() {
-                    let _ = sqlx::query(requests.guilds.ensure.as_str())
+                    let has_res = sqlx::query(requests.guilds.has.as_str())
                         .bind(&id)
-                        .execute(pool.deref())
+                        .fetch_one(pool.deref())
                         .await;
 
-                    let guild = get_guild(&ctx, guild_id).await;
-
-                    if let Some(g) = guild {
-                        if let Some(config) = ctx.get_data().await {
-                            events::guild_add_remove::send_new_guild_message(
-                                &ctx,
-                                &g,
-                                &config
-                            ).await;
-                        } else {
-                            warn!(target: "Runtime", "The config object was not found in the context data");
+                    // if 'has_res' is an Err(_), well, the data don't exist :)
+                    if has_res.is_err() {
+                        let _ = sqlx::query(requests.guilds.ensure.as_str())
+                            .bind(&id)
+                            .execute(pool.deref())
+                            .await;
+
+                        let guild = get_guild(&ctx, guild_id).await;
+
+                        if let Some(g) = guild {
+                            if let Some(config) = ctx.get_data().await {
+                                events::guild_add_remove::send_new_guild_message(
+                                    &ctx,
+                                    &g,
+                                    &config
+                                ).await;
+                            } else {
+                                warn!(target: "Runtime", "The config object was not found in the context data");
+                            }
                         }
                     }
                 }
@@ -404,6 +418,17 @@ impl EventHandler for Handler {
             }
         }
 
+        database_cleaner::refresh_last_seen(
+            &ctx,
+            scripts::get_user_id(&payload.interaction.user, &payload.interaction.member).as_ref(),
+            payload.interaction.guild_id.as_ref()
+        ).await;
+
+        // the language chosen by the guild is used by the scripts, see `scripts::get_guild_locale`
+        if let Some(guild_id) = &payload.interaction.guild_id {
+            crates::guild_lang::refresh(&ctx, guild_id).await;
+        }
+
         match payload.interaction.interaction_type {
             InteractionType::Ping => {
                 println!("Interaction ping, tf ?");
@@ -430,7 +455,7 @@ impl EventHandler for Handler {
                     Some(_) => {
                         // we notify that we don't understand what the fuck is this interaction
                         let msg = MessageBuilder::new().set_content(
-                            message!(scripts::get_guild_locale(&payload.interaction.guild_locale), "errors::unknown_component")
+                            message!(scripts::get_guild_locale(&payload.interaction), "errors::unknown_component")
                         ).set_ephemeral(true);
 
                         let _ = payload.interaction.reply(&ctx.skynet, msg).await;
@@ -470,9 +495,16 @@ impl EventHandler for Handler {
 #[allow(unused_mut)]
 async fn start(input: CliArgs) {
     // load config
-    let config: Config = config::load_from(input.config).unwrap();
+    let config_service = match ConfigService::load(&input.config) {
+        Ok(service) => service,
+        Err(e) => {
+            report_config_error(&e);
+            std::process::exit(1);
+        }
+    };
+    let config: Config = (*config_service.get()).clone();
 
-    log4rs::init_file(input.log_config, Default::default()).unwrap();
+    log4rs::init_file(input.log_config.expect("The log config is required"), Default::default()).unwrap();
 
     // write the PID in a file
     {
@@ -480,12 +512,14 @@ async fn start(input: CliArgs) {
         std::fs::write(config.pid.clone(), pid.to_string()).expect("Cannot write PID in a file, some real shit is happening");
     }
 
-    translation::init!(config.langs.clone().as_str());
+    translation::init!(config.langs.clone().as_str(), config.default_lang.as_str());
 
     // load database
     let database: Database = match Database::connect( &config).await {
         Ok(d) => {
-            database_cleaner::database_cleaner(d.clone());
+            database::health::spawn_health_monitor(d.clone());
+            database_cleaner::database_cleaner(d.clone(), config.database.cleaner.clone());
+            crates::error_broadcaster::set_registry(d.clone()).await;
             d
         },
         Err(err) => panic!("{:?}", err)
@@ -497,6 +531,13 @@ async fn start(input: CliArgs) {
         HttpConfiguration { retry_limit: config.api.retry_limit, connect_timeout: std::time::Duration::from_secs(config.api.close_timeout) }
     ).await;
 
+    crates::error_broadcaster::broadcast_panics(client.http_manager.client.clone());
+
+    // the signed custom_ids are bound to the token: they are refused once it is regenerated
+    client::models::components::custom_id::set_signing_key(
+        sha2::Sha256::digest(format!("custom_id:{}", config.security.discord_token)).to_vec()
+    );
+
     // IMPORTANT
     // This is a function that will manage to stop the bot when the SIGINT or SIGTERM signals are received
     #[cfg(unix)]
@@ -516,14 +557,17 @@ async fn start(input: CliArgs) {
         let mut data = client.data.write().await;
         // add the config
         data.insert::<Config>(config.read().await.clone());
+        data.insert::<ConfigService>(config_service.clone());
         // add the database
         data.insert::<Database>(database.clone());
         // add the status manager
-        data.insert::<crates::status::ShardStatusManager>(status_manager);
+        data.insert::<crates::status::ShardStatusManager>(status_manager.clone());
         // add the captcha container
         data.insert::<features::captcha::CaptchaContainer>(features::captcha::CaptchaContainer::new());
         // add the xp cooldown container
         data.insert::<features::xp::XpCooldownContainer>(features::xp::XpCooldownContainer::new());
+        // add the cooldowns of the interactions
+        data.insert::<features::cooldown::CooldownContainer>(features::cooldown::CooldownContainer::new());
         // add the font container
         let font_container = features::xp::image_gen::FontContainer::new()
             .expect("Cannot load the fonts for the FontContainer, What the fuck?");
@@ -546,6 +590,9 @@ async fn start(input: CliArgs) {
     let http_manager_clone = client.http_manager.clone();
 
     // Starting the API
+    let public_files = Arc::new(RwLock::new(
+        config.read().await.api.declared_files.clone()
+    ));
     {
         let mut informations = Api::new(
             Arc::new(ApiState::new(
@@ -554,17 +601,32 @@ async fn start(input: CliArgs) {
                     client.shard_manager.clone(),
                     database
                 ),
-                Arc::new(RwLock::new(
-                    config.read().await.api.declared_files.clone()
-                ))
+                public_files.clone()
             ))
         );
-        api::start(&mut informations, format!("{}:{}", input.domain, input.port).as_str());
+        api::start(&mut informations, format!(
+            "{}:{}",
+            input.domain.expect("The domain is required"),
+            input.port.expect("The port is required")
+        ).as_str());
 
         let mut data = client.data.write().await;
         data.insert::<Api>(informations);
     }
 
+    // apply the new config each time the config file changes
+    config_reloader::config_reloader(
+        config_service.subscribe(),
+        config_reloader::ConfigSubscribers {
+            config: config.clone(),
+            data: client.data.clone(),
+            http: client.http_manager.clone(),
+            status: status_manager,
+            public_files
+        }
+    );
+    config_service.watch(std::time::Duration::from_secs(5));
+
     // start the client with the intents
     // this function will block the thread until the client is stopped
     let intents = config.read().await.intents;
@@ -578,9 +640,55 @@ async fn start(input: CliArgs) {
 async fn main(){
     let args = CliArgs::parse();
 
+    if args.print_config {
+        print_config(&args.config);
+        return;
+    }
+
+    if args.check_config {
+        check_config(&args.config);
+        return;
+    }
+
     start(args).await;
 }
 
+/// Validate the config without starting the bot, the process exits with 1 if the config is invalid
+fn check_config(path: &PathBuf) {
+    match config::load_from(path).and_then(|config| config.validate()) {
+        Ok(_) => println!("The config is valid"),
+        Err(e) => {
+            report_config_error(&e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn report_config_error(error: &error::Error) {
+    match error {
+        error::Error::Config(error::ConfigError::InvalidConfig(problems)) => {
+            eprintln!("The config is invalid ({} problems):", problems.len());
+            for problem in problems {
+                eprintln!("  - {problem}");
+            }
+        }
+        e => eprintln!("Cannot load the config: {e}")
+    }
+}
+
+/// Print the config resolved from all its layers, without its secrets
+fn print_config(path: &PathBuf) {
+    let content = config::load_from(path).and_then(|config| config.redacted().to_toml());
+
+    match content {
+        Ok(content) => println!("{content}"),
+        Err(e) => {
+            eprintln!("Cannot load the config: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default)]
 pub(crate) struct CoreStart(pub(crate) DateTime<Utc>);
 
@@ -592,18 +700,26 @@ impl Type for CoreStart {
 #[command(author, version, about, long_about = None)]
 pub struct CliArgs {
     /// The domain that the API will listen to
-    #[arg(short, long)]
-    domain: String,
+    #[arg(short, long, required_unless_present_any = ["print_config", "check_config"])]
+    domain: Option<String>,
 
     /// The port of which the API will be listening to
-    #[arg(short, long)]
-    port: u16,
+    #[arg(short, long, required_unless_present_any = ["print_config", "check_config"])]
+    port: Option<u16>,
 
     /// The path at which the config file is
     #[arg(short, long)]
     config: PathBuf,
 
     /// The path at which the log config file is :)
-    #[arg(short, long)]
-    log_config: PathBuf
+    #[arg(short, long, required_unless_present_any = ["print_config", "check_config"])]
+    log_config: Option<PathBuf>,
+
+    /// Print the config resolved from all its layers, with the secrets redacted, then exit
+    #[arg(long)]
+    print_config: bool,
+
+    /// Validate the config without starting the bot, then exit
+    #[arg(long)]
+    check_config: bool
 }
\ No newline at end of file
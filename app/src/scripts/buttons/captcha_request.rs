@@ -4,18 +4,26 @@ use log::error;
 use rand::prelude::SliceRandom;
 use rand::thread_rng;
 use client::manager::events::Context;
+use client::models::components::custom_id::CustomId;
 use client::models::components::message_components::{ActionRow, Button, ButtonStyle, Component};
 use client::models::events::InteractionCreate;
 use client::models::message::{AttachmentBuilder, message_flags, MessageAttachmentBuilder, MessageBuilder, MessageFlags};
 use database::{Database, model};
 use features::captcha;
+use features::cooldown::Cooldown;
 use translation::fmt::formatter::Formatter;
 use translation::message;
 use crate::crates::error_broadcaster::*;
 use crate::broadcast_error;
-use crate::scripts::{get_guild_locale, QueryParams};
+use crate::scripts::{database_unavailable, get_guild_locale};
+use crate::scripts::buttons::captcha_try::CaptchaTry;
+use crate::scripts::registry::{handler, ComponentScript};
 
-pub(in crate::scripts) async fn triggered(ctx: &Context, payload: &InteractionCreate, _query: QueryParams) {
+pub(crate) const BUTTON: ComponentScript = ComponentScript::new("CAPTCHA_REQUEST", handler!(triggered))
+    .needs_database()
+    .cooldown(Cooldown::per_user(10));
+
+pub(in crate::scripts) async fn triggered(ctx: &Context, payload: &InteractionCreate) {
     let guild_id = match &payload.interaction.guild_id {
         Some(id) => id.clone(),
         _ => return
@@ -30,7 +38,7 @@ pub(in crate::scripts) async fn triggered(ctx: &Context, payload: &InteractionCr
                     MessageBuilder::new()
                         .set_content(
                             message!(
-                                get_guild_locale(&payload.interaction.guild_locale),
+                                get_guild_locale(&payload.interaction),
                                 "errors::cannot_get_user_id"
                             )
                         )
@@ -60,7 +68,7 @@ pub(in crate::scripts) async fn triggered(ctx: &Context, payload: &InteractionCr
                 MessageBuilder::new()
                     .set_content(
                         message!(
-                            get_guild_locale(&payload.interaction.guild_locale),
+                            get_guild_locale(&payload.interaction),
                             "errors::cannot_get_user_id"
                         )
                     )
@@ -98,7 +106,7 @@ pub(in crate::scripts) async fn triggered(ctx: &Context, payload: &InteractionCr
                     MessageBuilder::new()
                         .set_content(
                             message!(
-                                get_guild_locale(&payload.interaction.guild_locale),
+                                get_guild_locale(&payload.interaction),
                                 "errors::internal_error",
                                 Formatter::new().add("code", "10000")
                             )
@@ -134,7 +142,7 @@ pub(in crate::scripts) async fn triggered(ctx: &Context, payload: &InteractionCr
     let database = ctx.get_data::<Database>().await.expect("No database found");
 
     let guild_data = {
-        let pool = database.get_pool().await;
+        let Ok(pool) = database.get_pool().await else { return database_unavailable(ctx, &payload.interaction).await };
         match model::guild::Guild::from_pool(&pool, database.get_requests().await.guilds.get.as_str(), &guild_id).await {
             Ok(guild_data) => guild_data,
             Err(e) => {
@@ -161,12 +169,14 @@ pub(in crate::scripts) async fn triggered(ctx: &Context, payload: &InteractionCr
         }
     };
 
+    let lang = guild_data.lang_or(&get_guild_locale(&payload.interaction));
+
     if !guild_data.captcha_enabled.unwrap_or(false) {
         let _ = payload.interaction.reply(
             &ctx.skynet,
             MessageBuilder::new()
                 .set_content(
-                    message!(guild_data.lang, "features::captcha::disabled")
+                    message!(lang.clone(), "features::captcha::disabled")
                 )
                 .set_ephemeral(true)
         ).await;
@@ -259,7 +269,7 @@ pub(in crate::scripts) async fn triggered(ctx: &Context, payload: &InteractionCr
                     MessageBuilder::new()
                         .set_content(
                             message!(
-                                guild_data.lang,
+                                lang.clone(),
                                 "errors::internal_error",
                                 Formatter::new().add("code", "10001")
                             )
@@ -303,7 +313,7 @@ pub(in crate::scripts) async fn triggered(ctx: &Context, payload: &InteractionCr
 
     // build the message
     let mut msg = MessageBuilder::new()
-        .set_content(message!(get_guild_locale(&payload.interaction.guild_locale), "features::captcha::request"))
+        .set_content(message!(get_guild_locale(&payload.interaction), "features::captcha::request"))
         .add_attachment(MessageAttachmentBuilder {
             name: "captcha.png".into(),
             description: None,
@@ -316,9 +326,17 @@ pub(in crate::scripts) async fn triggered(ctx: &Context, payload: &InteractionCr
         let mut action_row = ActionRow::new();
 
         for code in codes.iter() {
+            let custom_id = match (CaptchaTry { code: code.clone(), instance: instance_code }).encode() {
+                Ok(id) => id,
+                Err(e) => {
+                    error!(target: "CaptchaRequest", "Cannot encode the custom_id of a captcha button: {e:?}");
+                    continue;
+                }
+            };
+
             action_row = action_row.add_component(
                 Component::Button(
-                    Button::new(format!("CAPTCHA_TRY&code={code}&instance={}", instance_code))
+                    Button::new(custom_id)
                         .set_label(code)
                         .set_style(ButtonStyle::Secondary)
                 )
@@ -344,7 +362,7 @@ pub(in crate::scripts) async fn triggered(ctx: &Context, payload: &InteractionCr
                     MessageBuilder::new()
                         .set_content(
                             message!(
-                        guild_data.lang,
+                        lang.clone(),
                         "errors::internal_error",
                         Formatter::new().add("code", "10002")
                     )
@@ -359,7 +377,7 @@ pub(in crate::scripts) async fn triggered(ctx: &Context, payload: &InteractionCr
                 MessageBuilder::new()
                     .set_content(
                         message!(
-                            guild_data.lang,
+                            lang.clone(),
                             "errors::internal_error",
                             Formatter::new().add("code", "10003")
                         )
@@ -389,7 +407,7 @@ pub(in crate::scripts) async fn triggered(ctx: &Context, payload: &InteractionCr
 
 
 async fn cannot_get_level(ctx: &Context, payload: &InteractionCreate) {
-    let guild_locale = get_guild_locale(&payload.interaction.guild_locale);
+    let guild_locale = get_guild_locale(&payload.interaction);
     let _ = payload.interaction.reply(
         &ctx.skynet,
         MessageBuilder::new()
@@ -399,7 +417,7 @@ async fn cannot_get_level(ctx: &Context, payload: &InteractionCreate) {
 }
 
 async fn cannot_get_model(ctx: &Context, payload: &InteractionCreate) {
-    let guild_locale = get_guild_locale(&payload.interaction.guild_locale);
+    let guild_locale = get_guild_locale(&payload.interaction);
     let _ = payload.interaction.reply(
         &ctx.skynet,
         MessageBuilder::new()
@@ -409,7 +427,7 @@ async fn cannot_get_model(ctx: &Context, payload: &InteractionCreate) {
 }
 
 async fn cannot_generate(ctx: &Context, payload: &InteractionCreate) {
-    let guild_locale = get_guild_locale(&payload.interaction.guild_locale);
+    let guild_locale = get_guild_locale(&payload.interaction);
     let _ = payload.interaction.update(
         &ctx.skynet,
         MessageBuilder::new()
@@ -419,7 +437,7 @@ async fn cannot_generate(ctx: &Context, payload: &InteractionCreate) {
 }
 
 async fn cannot_get_guild_data(ctx: &Context, payload: &InteractionCreate) {
-    let guild_locale = get_guild_locale(&payload.interaction.guild_locale);
+    let guild_locale = get_guild_locale(&payload.interaction);
     let _ = payload.interaction.reply(
         &ctx.skynet,
         MessageBuilder::new()
@@ -1,100 +1,63 @@
 //! Unique error ID for this file: 11xxx
 
-use std::str::FromStr;
 use log::error;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use client::manager::events::Context;
 use client::models::channel::Channel;
+use client::models::components::custom_id::CustomId;
 use client::models::events::InteractionCreate;
 use client::models::message::MessageBuilder;
 use client::models::Snowflake;
 use database::Database;
 use features::captcha;
+use features::cooldown::Cooldown;
 use translation::{ message, fmt::formatter::Formatter };
 use crate::broadcast_error;
 use crate::crates::error_broadcaster::*;
-use crate::scripts::{get_guild_locale, QueryParams};
+use crate::scripts::{database_unavailable, decode, get_guild_locale};
+use crate::scripts::registry::{handler, ComponentScript};
+
+/// The answer given by a button of a captcha
+#[derive(Serialize, Deserialize)]
+pub(crate) struct CaptchaTry {
+    #[serde(rename = "c")]
+    pub code: String,
+    #[serde(rename = "i")]
+    pub instance: Uuid
+}
 
-pub(in crate::scripts) async fn triggered(ctx: &Context, payload: &InteractionCreate, query: QueryParams) {
-    let guild_id = match &payload.interaction.guild_id {
-        Some(id) => id.clone(),
-        _ => return
-    };
+impl CustomId for CaptchaTry {
+    const NAME: &'static str = "CAPTCHA_TRY";
+    // the code of the button would be enough to pass the captcha
+    const SIGNED: bool = true;
+}
 
+pub(crate) const BUTTON: ComponentScript = ComponentScript::new(CaptchaTry::NAME, handler!(decode_and_trigger))
+    .needs_database()
+    .cooldown(Cooldown::per_user(2));
 
-    // verify the integrity of the query
-    // we are getting 'code' and 'instance'
-    let query_code = match query.get("code") {
-        Some(c) => c,
-        None => {
-            let _ = payload.interaction.reply(
-                &ctx.skynet,
-                MessageBuilder::new()
-                    .set_content(
-                        message!(
-                            get_guild_locale(&payload.interaction.guild_locale),
-                            "errors::internal_error",
-                            Formatter::new().add("code", "11001")
-                        )
-                    )
-                    .set_ephemeral(true)
-            ).await;
+async fn decode_and_trigger(ctx: &Context, payload: &InteractionCreate) {
+    let custom_id = payload.interaction.data.as_ref()
+        .and_then(|d| d.custom_id.clone())
+        .unwrap_or_default();
 
-            broadcast_error!(
-                localisation: BroadcastLocalisation::default()
-                    .set_guild(Some(guild_id))
-                    .set_channel(payload.interaction.channel_id.clone())
-                    .set_code_path("app/src/scripts/buttons/captcha_try.rs:triggered:47"),
-                interaction: BroadcastInteraction::default()
-                    .set_name("captcha_try")
-                    .set_type(BroadcastInteractionType::Button),
-                details: BroadcastDetails::default()
-                    .add("code", "11001")
-                    .add("error", "Cannot get the 'code' query parameter"),
-                ctx.skynet.as_ref()
-            );
+    if let Some(answer) = decode::<CaptchaTry>(ctx, payload, &custom_id).await {
+        triggered(ctx, payload, answer).await
+    }
+}
 
-            return;
-        }
+async fn triggered(ctx: &Context, payload: &InteractionCreate, answer: CaptchaTry) {
+    let guild_id = match &payload.interaction.guild_id {
+        Some(id) => id.clone(),
+        _ => return
     };
-    let query_instance = match query.get("instance") {
-        Some(c) => c,
-        None => {
-            let _ = payload.interaction.reply(
-                &ctx.skynet,
-                MessageBuilder::new()
-                    .set_content(
-                        message!(
-                            get_guild_locale(&payload.interaction.guild_locale),
-                            "errors::internal_error",
-                            Formatter::new().add("code", "11002")
-                        )
-                    )
-                    .set_ephemeral(true)
-            ).await;
 
-            broadcast_error!(
-                localisation: BroadcastLocalisation::default()
-                    .set_guild(Some(guild_id))
-                    .set_channel(payload.interaction.channel_id.clone())
-                    .set_code_path("app/src/scripts/buttons/captcha_try.rs:triggered:80"),
-                interaction: BroadcastInteraction::default()
-                    .set_name("captcha_try")
-                    .set_type(BroadcastInteractionType::Button),
-                details: BroadcastDetails::default()
-                    .add("code", "11002")
-                    .add("error", "Cannot get the 'instance' query parameter"),
-                ctx.skynet.as_ref()
-            );
-
-            return;
-        }
-    };
 
     // we get the guild data
     let database = ctx.get_data::<Database>().await.expect("No database found");
     let guild_data = {
-        let pool = database.get_pool().await;
+        let Ok(pool) = database.get_pool().await else { return database_unavailable(ctx, &payload.interaction).await };
         match database::model::guild::Guild::from_pool(&pool, database.get_requests().await.guilds.get.as_str(), &guild_id).await {
             Ok(guild_data) => guild_data,
             Err(e) => {
@@ -119,6 +82,8 @@ pub(in crate::scripts) async fn triggered(ctx: &Context, payload: &InteractionCr
         }
     };
 
+    let lang = guild_data.lang_or(&get_guild_locale(&payload.interaction));
+
     // we verify if the configuration is okay
     if guild_data.captcha_role.is_none() || guild_data.captcha_channel.is_none() {
         let _ = payload.interaction.reply(
@@ -126,7 +91,7 @@ pub(in crate::scripts) async fn triggered(ctx: &Context, payload: &InteractionCr
             MessageBuilder::new()
                 .set_content(
                     message!(
-                            guild_data.lang,
+                            lang.clone(),
                             "features::captcha::invalid_config",
                             Formatter::new().add("code", "11003")
                         )
@@ -146,7 +111,7 @@ pub(in crate::scripts) async fn triggered(ctx: &Context, payload: &InteractionCr
                 MessageBuilder::new()
                     .set_content(
                         message!(
-                            guild_data.lang,
+                            lang.clone(),
                             "errors::internal_error",
                             Formatter::new().add("code", "11003")
                         )
@@ -172,10 +137,10 @@ pub(in crate::scripts) async fn triggered(ctx: &Context, payload: &InteractionCr
         }
     };
 
-    match captcha_container.get(Uuid::from_str(query_instance.as_str()).unwrap_or(Default::default())).await {
+    match captcha_container.get(answer.instance).await {
         Some(instance) => {
             let good_code = instance.code.clone().iter().collect::<String>();
-            if &good_code == query_code {
+            if good_code == answer.code {
                 // OKAY
                 let guild_member = match &payload.interaction.member {
                     Some(m) => m,
@@ -186,7 +151,7 @@ pub(in crate::scripts) async fn triggered(ctx: &Context, payload: &InteractionCr
                 let _ = payload.interaction.reply(
                     &ctx.skynet,
                     MessageBuilder::new()
-                        .set_content(message!(guild_data.lang.clone(), "features::captcha::ok"))
+                        .set_content(message!(lang.clone(), "features::captcha::ok"))
                         .set_ephemeral(true)
                 ).await;
 
@@ -209,7 +174,7 @@ pub(in crate::scripts) async fn triggered(ctx: &Context, payload: &InteractionCr
                         let msg = MessageBuilder::new()
                             .set_content(
                                 message!(
-                                    guild_data.lang.clone(),
+                                    lang.clone(),
                                     "errors::cannot_remove_role",
                                     Formatter::new().add("id", guild_member.user.as_ref().unwrap().id.to_string())
                                 )
@@ -231,7 +196,7 @@ pub(in crate::scripts) async fn triggered(ctx: &Context, payload: &InteractionCr
                     MessageBuilder::new()
                         .set_content(
                             message!(
-                                guild_data.lang,
+                                lang.clone(),
                                 "features::captcha::bad_code"
                             )
                         )
@@ -246,7 +211,7 @@ pub(in crate::scripts) async fn triggered(ctx: &Context, payload: &InteractionCr
                 MessageBuilder::new()
                     .set_content(
                         message!(
-                            guild_data.lang,
+                            lang.clone(),
                             "features::captcha::invalid_instance"
                         )
                     )
@@ -257,7 +222,7 @@ pub(in crate::scripts) async fn triggered(ctx: &Context, payload: &InteractionCr
 }
 
 async fn cannot_get_guild_data(ctx: &Context, payload: &InteractionCreate) {
-    let guild_locale = get_guild_locale(&payload.interaction.guild_locale);
+    let guild_locale = get_guild_locale(&payload.interaction);
     let _ = payload.interaction.reply(
         &ctx.skynet,
         MessageBuilder::new()
@@ -267,7 +232,7 @@ async fn cannot_get_guild_data(ctx: &Context, payload: &InteractionCreate) {
 }
 
 async fn cannot_get_guild_member(ctx: &Context, payload: &InteractionCreate) {
-    let guild_locale = get_guild_locale(&payload.interaction.guild_locale);
+    let guild_locale = get_guild_locale(&payload.interaction);
     let _ = payload.interaction.reply(
         &ctx.skynet,
         MessageBuilder::new()
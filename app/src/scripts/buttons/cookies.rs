@@ -3,10 +3,15 @@ use client::models::components::message_components::{ActionRow, Component, Compo
 use client::models::events::InteractionCreate;
 use client::models::message::MessageBuilder;
 use translation::message;
+use features::cooldown::Cooldown;
 use crate::scripts::get_guild_locale;
+use crate::scripts::registry::{handler, ComponentScript};
+
+pub(crate) const BUTTON: ComponentScript = ComponentScript::new("ANSWER_COOKIES_QUIZ", handler!(triggered))
+    .cooldown(Cooldown::per_user(5));
 
 pub(in crate::scripts) async fn triggered(ctx: &Context, payload: &InteractionCreate) {
-    let local = get_guild_locale(&payload.interaction.guild_locale);
+    let local = get_guild_locale(&payload.interaction);
 
     let _ = payload.interaction.reply_with_modal(
         &ctx.skynet,
@@ -0,0 +1,231 @@
+//! Error code: 34xxx
+//!
+//! Confirmation buttons of the `/data` command
+
+use log::error;
+use client::manager::events::Context;
+use client::models::components::Emoji;
+use client::models::components::message_components::{ActionRow, Button, ButtonStyle, Component};
+use client::models::events::InteractionCreate;
+use client::models::message::{AttachmentBuilder, MessageAttachmentBuilder, MessageBuilder};
+use database::Database;
+use database::model::users::UserData;
+use translation::message;
+use crate::scripts::{database_unavailable, get_guild_locale, get_user_id};
+use crate::scripts::slashs::internal_error_deferred;
+use crate::crates::error_broadcaster::*;
+use crate::broadcast_error;
+use crate::scripts::registry::{handler, ComponentScript};
+
+pub(crate) const EXPORT_BUTTON: ComponentScript = ComponentScript::new("DATA_EXPORT", handler!(export)).needs_database();
+pub(crate) const ERASE_BUTTON: ComponentScript = ComponentScript::new("DATA_ERASE", handler!(erase)).needs_database();
+pub(crate) const CANCEL_BUTTON: ComponentScript = ComponentScript::new("DATA_CANCEL", handler!(cancel));
+
+/// Send every data stored about the user in his DMs, as a JSON file
+pub(in crate::scripts) async fn export(ctx: &Context, payload: &InteractionCreate) {
+    let local = get_guild_locale(&payload.interaction);
+
+    let Some(db) = ctx.get_data::<Database>().await else { return database_unavailable(ctx, &payload.interaction).await };
+    let Ok(pool) = db.get_pool().await else { return database_unavailable(ctx, &payload.interaction).await };
+    let requests = db.get_requests().await;
+
+    if !disable_buttons(ctx, payload, &local, "DATA_EXPORT", ButtonStyle::Primary).await { return; }
+
+    let user_id = match get_user_id(&payload.interaction.user, &payload.interaction.member) {
+        Some(id) => id,
+        None => {
+            internal_error_deferred(ctx, &payload.interaction, local, "34001").await;
+            return;
+        }
+    };
+
+    let data = match UserData::collect(&pool, &requests, user_id.to_string()).await {
+        Ok(data) => data,
+        Err(e) => {
+            internal_error_deferred(ctx, &payload.interaction, local, "34002").await;
+
+            broadcast_error!(
+                localisation: BroadcastLocalisation::default()
+                    .set_guild(payload.interaction.guild_id.clone())
+                    .set_channel(payload.interaction.channel_id.clone())
+                    .set_user(payload.interaction.user.clone())
+                    .set_code_path("app/src/scripts/buttons/data.rs:export:42"),
+                interaction: BroadcastInteraction::default()
+                    .set_name("DATA_EXPORT")
+                    .set_type(BroadcastInteractionType::Button),
+                details: BroadcastDetails::default()
+                    .add("code", "34002")
+                    .add("reason", "Cannot collect the user data")
+                    .add("error", format!("{e:#?}")),
+                ctx.skynet.as_ref()
+            );
+
+            return;
+        }
+    };
+
+    let json = match data.to_json() {
+        Ok(json) => json,
+        Err(e) => {
+            error!(target: "Runtime", "Cannot serialize the data of '{user_id}': {e:#?}");
+            internal_error_deferred(ctx, &payload.interaction, local, "34003").await;
+            return;
+        }
+    };
+
+    let dm_channel = match ctx.skynet.create_dm_channel(&user_id).await {
+        Ok(Ok(c)) => c,
+        _ => {
+            let _ = payload.interaction.update(
+                &ctx.skynet,
+                MessageBuilder::new()
+                    .set_content(message!(&local, "slashs::data::export::dm_closed"))
+            ).await;
+            return;
+        }
+    };
+
+    let file = AttachmentBuilder {
+        bytes: json.into_bytes(),
+        content_type: "application/json".into(),
+        description: None,
+        filename: "data.json".into(),
+        id: 0
+    };
+
+    let sent = ctx.skynet.send_message(
+        &dm_channel.id,
+        MessageBuilder::new()
+            .set_content(message!(&local, "slashs::data::export::dm"))
+            .add_attachment(MessageAttachmentBuilder {
+                description: None,
+                name: "data.json".to_string(),
+                content_type: "application/json".to_string(),
+                id: 0,
+            }),
+        Some(vec![file])
+    ).await;
+
+    let content = match sent {
+        Ok(Ok(_)) => message!(&local, "slashs::data::export::success"),
+        _ => message!(&local, "slashs::data::export::dm_closed")
+    };
+
+    let _ = payload.interaction.update(&ctx.skynet, MessageBuilder::new().set_content(content)).await;
+}
+
+/// Erase every data stored about the user
+pub(in crate::scripts) async fn erase(ctx: &Context, payload: &InteractionCreate) {
+    let local = get_guild_locale(&payload.interaction);
+
+    let Some(db) = ctx.get_data::<Database>().await else { return database_unavailable(ctx, &payload.interaction).await };
+    let Ok(pool) = db.get_pool().await else { return database_unavailable(ctx, &payload.interaction).await };
+    let requests = db.get_requests().await;
+
+    if !disable_buttons(ctx, payload, &local, "DATA_ERASE", ButtonStyle::Danger).await { return; }
+
+    let user_id = match get_user_id(&payload.interaction.user, &payload.interaction.member) {
+        Some(id) => id,
+        None => {
+            internal_error_deferred(ctx, &payload.interaction, local, "34004").await;
+            return;
+        }
+    };
+
+    if let Err(e) = UserData::erase(&pool, &requests, user_id.to_string()).await {
+        internal_error_deferred(ctx, &payload.interaction, local, "34005").await;
+
+        broadcast_error!(
+            localisation: BroadcastLocalisation::default()
+                .set_guild(payload.interaction.guild_id.clone())
+                .set_channel(payload.interaction.channel_id.clone())
+                .set_user(payload.interaction.user.clone())
+                .set_code_path("app/src/scripts/buttons/data.rs:erase:135"),
+            interaction: BroadcastInteraction::default()
+                .set_name("DATA_ERASE")
+                .set_type(BroadcastInteractionType::Button),
+            details: BroadcastDetails::default()
+                .add("code", "34005")
+                .add("reason", "Cannot erase the user data")
+                .add("error", format!("{e:#?}")),
+            ctx.skynet.as_ref()
+        );
+
+        return;
+    }
+
+    let _ = payload.interaction.update(
+        &ctx.skynet,
+        MessageBuilder::new()
+            .set_content(message!(&local, "slashs::data::erase::success"))
+    ).await;
+}
+
+/// Disable the confirmation buttons without doing anything
+pub(in crate::scripts) async fn cancel(ctx: &Context, payload: &InteractionCreate) {
+    let local = get_guild_locale(&payload.interaction);
+
+    let _ = payload.interaction.edit_original_message(
+        &ctx.skynet,
+        MessageBuilder::new()
+            .set_content(message!(&local, "slashs::data::cancelled"))
+            .add_component(
+                Component::ActionRow(
+                    ActionRow::new()
+                        .add_component(
+                            Component::Button(
+                                Button::new("DATA_CANCEL")
+                                    .set_label(message!(&local, "slashs::data::cancel_btn"))
+                                    .set_style(ButtonStyle::Secondary)
+                                    .set_disabled(true)
+                            )
+                        )
+                )
+            )
+    ).await;
+}
+
+/// Disable the confirmation buttons, so the action cannot be run twice
+///
+/// It acknowledges the interaction, the result is then written in the confirmation message
+async fn disable_buttons(ctx: &Context, payload: &InteractionCreate, local: &str, custom_id: &str, style: ButtonStyle) -> bool {
+    let result = payload.interaction.edit_original_message(
+        &ctx.skynet,
+        MessageBuilder::new()
+            .add_component(
+                Component::ActionRow(
+                    ActionRow::new()
+                        .add_component(
+                            Component::Button(
+                                Button::new(custom_id)
+                                    .set_label(message!(local, "slashs::data::confirm_btn"))
+                                    .set_emoji(Emoji::new(None, "✔️"))
+                                    .set_style(style)
+                                    .set_disabled(true)
+                            )
+                        )
+                        .add_component(
+                            Component::Button(
+                                Button::new("DATA_CANCEL")
+                                    .set_label(message!(local, "slashs::data::cancel_btn"))
+                                    .set_style(ButtonStyle::Secondary)
+                                    .set_disabled(true)
+                            )
+                        )
+                )
+            )
+    ).await;
+
+    match result {
+        Some(Ok(Ok(_))) => true,
+        Some(Ok(Err(e))) => {
+            error!(target: "Runtime", "Cannot disable the confirmation buttons: {e:#?}");
+            false
+        }
+        Some(Err(e)) => {
+            error!(target: "Runtime", "Cannot disable the confirmation buttons: {e:#?}");
+            false
+        }
+        None => false
+    }
+}
@@ -1,6 +1,9 @@
 pub(crate) mod suggestion {
     use client::manager::events::Context;
     use client::models::events::InteractionCreate;
+    use crate::scripts::registry::{handler, ComponentScript};
+
+    pub(crate) const BUTTON: ComponentScript = ComponentScript::new("SUGGESTION", handler!(triggered));
 
     pub(in crate::scripts) async fn triggered(ctx: &Context, payload: &InteractionCreate) {
         let _ = payload.interaction.defer(&ctx.skynet, None).await;
@@ -1,4 +1,5 @@
 pub(crate) mod captcha_request;
 pub(crate) mod captcha_try;
+pub(crate) mod data;
 pub(crate) mod cookies;
 pub(crate) mod kady;
\ No newline at end of file
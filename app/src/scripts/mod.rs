@@ -10,41 +10,39 @@
 //! - Context Menus
 //! - Modals
 
-use std::collections::HashMap;
 use log::{error, warn};
 use client::manager::events::Context;
+use client::models::components::custom_id::{self, CustomId};
 use client::models::events::InteractionCreate;
 use client::models::guild::{Guild, GuildId, GuildMember};
+use client::models::interaction::Interaction;
 use client::models::message::MessageBuilder;
 use client::models::user::{Application, ClientUser, User, UserId};
-use translation::{Language, message};
-use error::{Error, EventError, Result};
-use crate::constants::DEFAULT_LANG;
-
+use database::Database;
+use features::cooldown::{Cooldown, CooldownContainer, Subject};
+use translation::fmt::formatter::Formatter;
+use translation::message;
+use crate::crates::guild_lang;
+use crate::scripts::registry::{ComponentScript, Permission};
+
+pub(crate) mod registry;
 mod slashs;
 mod buttons;
 mod modal;
 mod select_menu;
 
-/// Get the guild locale, or the default one if the guild locale is not supported.
-pub(crate) fn get_guild_locale(guild_locale: &Option<String>) -> String {
-    if guild_locale.is_none() {
-        return DEFAULT_LANG.to_string();
-    }
-
-    let translations = if let Ok(t) = translation::TRANSLATIONS.read() {
-        t
-    } else {
-        return DEFAULT_LANG.to_string();
-    };
-
-    let lang = Language(guild_locale.clone().unwrap());
+/// Get the language of an interaction: the language chosen by the guild if it chose one, then the guild locale, then the default language
+///
+/// The unsupported locales are resolved by the fallback chain of the translations (`en-US` to `en`, then the default language)
+pub(crate) fn get_guild_locale(interaction: &Interaction) -> String {
+    let chosen = interaction.guild_id.as_ref()
+        .and_then(guild_lang::get)
+        .filter(|lang| translation::is_available(lang.as_str()));
 
-    if translations.contains_key(&lang) {
-        return guild_locale.as_ref().unwrap().to_string();
+    match chosen.or_else(|| interaction.guild_locale.clone()) {
+        Some(lang) => lang,
+        None => translation::default_language().0
     }
-
-    DEFAULT_LANG.to_string()
 }
 
 /// Handle the slash commands.
@@ -58,141 +56,80 @@ pub(crate) async fn slash_command_received(ctx: &Context, payload: &InteractionC
         }
     };
 
-    let name = command.name.clone().unwrap_or(String::new());
-    // DON'T USE "" AS A COMMAND NAME!!
-    match name.as_str() {
-        "ping" => slashs::common::ping::triggered(ctx, payload).await,
-        "citation" => slashs::citation::triggered(ctx, payload).await,
-        "admin_reload_commands" | "admin_update_commands" => slashs::admin::admin_reload_slashs::triggered(ctx, payload).await,
-        "admin_reload_requests" => slashs::admin::admin_reload_requests::triggered(ctx, payload).await,
-        "admin_reload_langs" => slashs::admin::admin_reload_langs::triggered(ctx, payload).await,
-        "admin_memory_report" => slashs::admin::admin_memory_report::triggered(ctx, payload).await,
-        "guild_rank" => slashs::xp::guild_rank::triggered(ctx, payload).await,
-        "top" => slashs::top::triggered(ctx, payload).await,
-        "cookies" => slashs::cookies::triggered(ctx, payload).await,
-        "avatar" => slashs::common::avatar_slash::triggered(ctx, payload).await,
-        "banner" => slashs::common::banner_slash::triggered(ctx, payload).await,
-        "welcome" => slashs::common::welcome::triggered(ctx, payload).await,
-        "rateit" | "note" => slashs::fun::rateit::triggered(ctx, payload).await,
-        "unacceptable" => slashs::fun::unacceptable::triggered(ctx, payload).await,
-        "8ball" => slashs::fun::eight_ball::triggered(ctx, payload).await,
-        "userinfo" => slashs::common::user_info::triggered(ctx, payload).await,
-        "help" => slashs::common::help::triggered(ctx, payload).await,
-        "kady" => slashs::common::kady::triggered(ctx, payload).await,
-        _ => {
-            let local = get_guild_locale(&payload.interaction.guild_locale);
+    let name = command.name.clone().unwrap_or_default();
+    let command = match registry::find(name.as_str()) {
+        Some(c) => c,
+        None => {
+            let local = get_guild_locale(&payload.interaction);
             if let Err(e) = payload.interaction.reply(&ctx.skynet, unknown_command(local)).await {
                 warn!(target: "EventHandler", "Failed to reply to slash command: {:?}", e);
             };
+            return;
         }
-    }
-}
+    };
 
-pub(crate) async fn button_received(ctx: &Context, payload: &InteractionCreate){
-    if payload.interaction.data.is_none() {
-        error!(target: "ButtonReceived", "No button's data was provided (wtf ?)")
+    if command.permission == Permission::Admin && !slashs::admin::authorize(ctx, payload, name.as_str()).await { return; }
+    if command.needs_database && !database_available(ctx, payload).await { return; }
+    if let Some(cooldown) = command.cooldown {
+        // the aliases share the cooldown of the command
+        if !cooldown_elapsed(ctx, payload, (command.definition)().name.as_str(), cooldown).await { return; }
     }
 
-    let button = payload.interaction.data.as_ref().unwrap();
-
-    let (custom_id, query) = {
-        let custom_id = button.custom_id.clone().unwrap_or(String::new());
-        let mut split = custom_id.splitn(2, '&');
+    (command.handler)(ctx, payload).await
+}
 
-        (
-            split.next().unwrap_or(Default::default()).to_string(),
-            split.next().unwrap_or(Default::default()).to_string(),
-        )
+pub(crate) async fn button_received(ctx: &Context, payload: &InteractionCreate){
+    let Some(button) = payload.interaction.data.as_ref() else {
+        error!(target: "ButtonReceived", "No button's data was provided (wtf ?)");
+        return;
     };
 
-    let query_string = match querify(query) {
-        Ok(q) => q,
-        Err(e) => {
-            error!(target: "InteractionHandler", "The runtime received an error while querying the button custom_id: {e:#?}");
-            return;
-        }
-    };
+    let custom_id = button.custom_id.clone().unwrap_or_default();
+    let name = custom_id::name(&custom_id);
 
-    match custom_id.as_str() {
-        "CAPTCHA_REQUEST" => buttons::captcha_request::triggered(ctx, payload, query_string).await,
-        "CAPTCHA_TRY" => buttons::captcha_try::triggered(ctx, payload, query_string).await,
-        "ANSWER_COOKIES_QUIZ" => buttons::cookies::triggered(ctx, payload).await,
-        "SUGGESTION" => buttons::kady::suggestion::triggered(ctx, payload).await,
-        _ => {
+    match registry::find_component(registry::BUTTONS, name) {
+        Some(button) => component_triggered(ctx, payload, button).await,
+        None => {
             if payload.interaction.channel_id.is_none() { return; }
             let _ = payload.interaction.reply(
                 &ctx.skynet,
-                unknown_button(get_guild_locale(&payload.interaction.guild_locale))
+                unknown_button(get_guild_locale(&payload.interaction))
             ).await;
         }
     }
 }
 
 pub(crate) async fn select_menu_received(ctx: &Context, payload: &InteractionCreate){
-    let button = payload.interaction.data.as_ref().unwrap();
+    let Some(menu) = payload.interaction.data.as_ref() else { return };
 
-    let (custom_id, query) = {
-        let custom_id = button.custom_id.clone().unwrap_or(String::new());
-        let mut split = custom_id.splitn(2, '&');
+    let custom_id = menu.custom_id.clone().unwrap_or_default();
+    let name = custom_id::name(&custom_id);
 
-        (
-            split.next().unwrap_or_default().to_string(),
-            split.next().unwrap_or_default().to_string(),
-        )
-    };
-
-    let query_string = match querify(query) {
-        Ok(q) => q,
-        Err(e) => {
-            error!(target: "InteractionHandler", "The runtime received an error while querying the select menu custom_id: {e:#?}");
-            return;
-        }
-    };
-
-    match custom_id.as_str() {
-        "SELECT_HELP_CATEGORY" => select_menu::select_help_category::triggered(ctx, payload, query_string).await,
-        _ => {
+    match registry::find_component(registry::SELECT_MENUS, name) {
+        Some(menu) => component_triggered(ctx, payload, menu).await,
+        None => {
             if payload.interaction.channel_id.is_none() { return; }
             let _ = payload.interaction.reply(
                 &ctx.skynet,
-                unknown_select_menu(get_guild_locale(&payload.interaction.guild_locale))
+                unknown_select_menu(get_guild_locale(&payload.interaction))
             ).await;
         }
     }
 }
 
 pub(crate) async fn modal_received(ctx: &Context, payload: &InteractionCreate){
-    let modal = payload.interaction.data.as_ref().unwrap();
+    let Some(modal) = payload.interaction.data.as_ref() else { return };
 
-    let (custom_id, query) = {
-        let custom_id = modal.custom_id.clone().unwrap_or(String::new());
-        let mut split = custom_id.splitn(2, '&');
+    let custom_id = modal.custom_id.clone().unwrap_or_default();
+    let name = custom_id::name(&custom_id);
 
-        (
-            split.next().unwrap_or_default().to_string(),
-            split.next().unwrap_or_default().to_string(),
-        )
-    };
-
-    #[allow(unused_variables)]
-    let query_string = match querify(query) {
-        Ok(q) => q,
-        Err(e) => {
-            error!(target: "InteractionHandler", "The runtime received an error while querying the modal custom_id: {e:#?}");
-            return;
-        }
-    };
-
-    match custom_id.as_str() {
-        "COOKIE_USER_QUIZ_ANSWER" => modal::cookie_quiz_answer::triggered(ctx, payload).await,
-        "KADY_SUGGEST_MODAL" => modal::kady::suggest::triggered(ctx, payload).await,
-        "KADY_ISSUE_MODAL" => modal::kady::issue::triggered(ctx, payload).await,
-        "KADY_REVIEW_MODAL" => modal::kady::review::triggered(ctx, payload).await,
-        _ => {
+    match registry::find_component(registry::MODALS, name) {
+        Some(modal) => component_triggered(ctx, payload, modal).await,
+        None => {
             if payload.interaction.channel_id.is_none() { return; }
             let _ = payload.interaction.reply(
                 &ctx.skynet,
-                unknown_modal(get_guild_locale(&payload.interaction.guild_locale))
+                unknown_modal(get_guild_locale(&payload.interaction))
             ).await;
         }
     }
@@ -218,6 +155,83 @@ fn unknown_modal(local: String) -> MessageBuilder {
     MessageBuilder::new().set_content(message!(local, "errors::unknown_modal")).set_ephemeral(true)
 }
 
+/// Decode the data of a component, an invalid custom_id is replied with an error
+pub(in crate::scripts) async fn decode<T: CustomId>(ctx: &Context, payload: &InteractionCreate, custom_id: &str) -> Option<T> {
+    match T::decode(custom_id) {
+        Ok(data) => Some(data),
+        Err(e) => {
+            warn!(target: "InteractionHandler", "Cannot decode the custom_id {custom_id:?}: {e:?}");
+            let _ = payload.interaction.reply(
+                &ctx.skynet,
+                MessageBuilder::new()
+                    .set_content(message!(get_guild_locale(&payload.interaction), "errors::invalid_custom_id"))
+                    .set_ephemeral(true)
+            ).await;
+            None
+        }
+    }
+}
+
+/// Reply with an error and return false if the database is unavailable
+async fn database_available(ctx: &Context, payload: &InteractionCreate) -> bool {
+    let available = match ctx.get_data::<Database>().await {
+        Some(db) => db.is_available(),
+        None => false
+    };
+
+    if !available {
+        database_unavailable(ctx, &payload.interaction).await;
+    }
+
+    available
+}
+
+/// Reply that the database is unavailable
+pub(in crate::scripts) async fn database_unavailable(ctx: &Context, interaction: &Interaction) {
+    let _ = interaction.reply(
+        &ctx.skynet,
+        MessageBuilder::new()
+            .set_content(message!(get_guild_locale(interaction), "errors::database_unavailable"))
+            .set_ephemeral(true)
+    ).await;
+}
+
+/// Check the requirements of a component, then run its handler
+async fn component_triggered(ctx: &Context, payload: &InteractionCreate, component: &ComponentScript) {
+    if component.needs_database && !database_available(ctx, payload).await { return; }
+    if let Some(cooldown) = component.cooldown {
+        if !cooldown_elapsed(ctx, payload, component.name, cooldown).await { return; }
+    }
+
+    (component.handler)(ctx, payload).await
+}
+
+/// Reply with the time left and return false if the cooldown of the interaction is still running
+///
+/// The administrators and the users that cannot be identified aren't throttled
+async fn cooldown_elapsed(ctx: &Context, payload: &InteractionCreate, name: &str, cooldown: Cooldown) -> bool {
+    match get_user_id(&payload.interaction.user, &payload.interaction.member) {
+        Some(user) if !slashs::admin::is_admin(&user) => (),
+        _ => return true
+    }
+    let Some(subject) = Subject::of(cooldown.scope, &payload.interaction) else { return true };
+    let Some(cooldowns) = ctx.get_data::<CooldownContainer>().await else { return true };
+
+    match cooldowns.hit(name, subject, cooldown.duration).await {
+        Ok(()) => true,
+        Err(left) => {
+            // rounded up, "0 seconds" would be confusing
+            let seconds = left.as_secs() + u64::from(left.subsec_nanos() > 0);
+            let _ = payload.interaction.reply(
+                &ctx.skynet,
+                MessageBuilder::new()
+                    .set_content(message!(get_guild_locale(&payload.interaction), "errors::cooldown", Formatter::new().add("seconds", seconds)))
+                    .set_ephemeral(true)
+            ).await;
+            false
+        }
+    }
+}
 
 // UTILITY FUNCTIONS
 /// Retrieves a user from user ID.
@@ -462,49 +476,3 @@ pub async fn get_client_user(ctx: &Context) -> Option<ClientUser> {
         _ => None
     }
 }
-
-
-// QUERY STRING SYSTEM
-
-/// Type alias for a `HashMap` where the key and value are both `String`.
-/// This type alias is typically used to represent query parameters, where the keys are parameter names
-/// and the values are their associated values.
-///
-pub type QueryParams = HashMap<String, String>;
-
-/// Parses a given query string into a `HashMap` of key-value pairs.
-///
-/// This function checks if the string contains an '&' symbol. If no '&' is found,
-/// an empty `HashMap` is returned. If '&' is found, it splits the string on each '&' symbol to get
-/// key-value pairs.
-///
-/// Each pair is further split on the '=' symbol to get the key and the value. If for some reason the pair
-/// does not contain a single '=', an error is returned. Otherwise, the key and value are inserted into
-/// the `HashMap`.
-///
-/// # Arguments
-///
-/// * `string` - The query string to be parsed into a `HashMap`.
-///
-/// # Returns
-///
-/// * `Result<QueryParams>` - If the parsing is successful, `Ok` variant containing the `HashMap` is returned,
-/// otherwise an `Error` variant indicating the reason for failure is returned.
-pub fn querify(string: String) -> Result<QueryParams> {
-    if !string.contains('&') {
-        return Ok(HashMap::new())
-    }
-
-    let mut map = HashMap::new();
-    for pair in string.split('&') {
-        let mut it = pair.split('=');
-
-        let kv = match (it.next(), it.next()) {
-            (Some(k), Some(v)) => (k.to_string(), v.to_string()),
-            (a, b) =>
-                return Err(Error::Event(EventError::Runtime(format!("Invalid querystring was received: ({a:?}, {b:?})")))),
-        };
-        map.insert(kv.0, kv.1);
-    }
-    Ok(map)
-}
\ No newline at end of file
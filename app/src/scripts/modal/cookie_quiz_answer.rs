@@ -1,21 +1,29 @@
 use std::ops::Deref;
+use chrono::Utc;
 use log::error;
 use client::manager::events::Context;
 use client::models::components::message_components::Component;
 use client::models::events::InteractionCreate;
 use client::models::message::MessageBuilder;
 use database::Database;
+use database::model::users::{QuizReward, UserQuizProgress};
+use features::cooldown::Cooldown;
 use translation::message;
 use crate::crates::cookies;
-use crate::scripts::{get_guild_locale, get_user_id};
+use crate::scripts::{database_unavailable, get_guild_locale, get_user_id};
 use crate::scripts::slashs::internal_error;
 use crate::crates::error_broadcaster::*;
 use crate::broadcast_error;
+use crate::scripts::registry::{handler, ComponentScript};
 
 const TOLERANCE: usize = 1;
 
+pub(crate) const MODAL: ComponentScript = ComponentScript::new("COOKIE_USER_QUIZ_ANSWER", handler!(triggered))
+    .needs_database()
+    .cooldown(Cooldown::per_user(5));
+
 pub(crate) async fn triggered(ctx: &Context, payload: &InteractionCreate) {
-    let local = get_guild_locale(&payload.interaction.guild_locale);
+    let local = get_guild_locale(&payload.interaction);
 
     let db = match ctx.get_data::<Database>().await {
         Some(d) => d,
@@ -40,7 +48,7 @@ pub(crate) async fn triggered(ctx: &Context, payload: &InteractionCreate) {
         }
     };
 
-    let pool = db.get_pool().await;
+    let Ok(pool) = db.get_pool().await else { return database_unavailable(ctx, &payload.interaction).await };
     let requests = db.get_requests().await;
 
     let user_id = match get_user_id(&payload.interaction.user, &payload.interaction.member) {
@@ -308,41 +316,18 @@ pub(crate) async fn triggered(ctx: &Context, payload: &InteractionCreate) {
         TOLERANCE
     );
 
+    let date = Utc::now().format("%Y-%m-%d");
+
     if is_valid {
-        // declare the question as completed :)
-        let question_completed_result = cookies::quiz::question_completed(
-            &pool,
+        // declare the question as completed and give the cookie :)
+        let cookie_given = UserQuizProgress::complete(
+            pool.deref(),
             &requests,
-            &user_id,
+            format!("quiz:cookie:{user_id}:{date}").as_str(),
+            user_id.to_string(),
+            QuizReward::Cookies(1)
         ).await;
 
-        if let Err(e) = question_completed_result {
-            error!(target: "Runtime", "Cannot declare the user cookie question as completed: {e:#?}");
-            internal_error(ctx, &payload.interaction, local, "30008").await;
-
-            broadcast_error!(
-                localisation: BroadcastLocalisation::default()
-                    .set_guild(payload.interaction.guild_id.clone())
-                    .set_channel(payload.interaction.channel_id.clone())
-                    .set_code_path("app/src/scripts/modal/cookie_quiz_answer.rs:327"),
-                interaction: BroadcastInteraction::default()
-                    .set_name("cookie_quiz_answer")
-                    .set_type(BroadcastInteractionType::Modal),
-                details: BroadcastDetails::default()
-                    .add("code", "30008")
-                    .add("error", "Cannot declare the user cookie question as completed"),
-                ctx.skynet.as_ref()
-            );
-
-            return;
-        }
-
-        // give the cookie
-        let cookie_given = sqlx::query(requests.users.cookies.give_cookie_from_system.as_str())
-            .bind(&user_id.to_string())
-            .execute(pool.deref())
-            .await;
-
         if let Err(e) = cookie_given {
             error!(target: "Runtime", "Cannot give a cookie to the user: {e:#?}");
             internal_error(ctx, &payload.interaction, local, "30009").await;
@@ -351,7 +336,7 @@ pub(crate) async fn triggered(ctx: &Context, payload: &InteractionCreate) {
                 localisation: BroadcastLocalisation::default()
                     .set_guild(payload.interaction.guild_id.clone())
                     .set_channel(payload.interaction.channel_id.clone())
-                    .set_code_path("app/src/scripts/modal/cookie_quiz_answer.rs:350"),
+                    .set_code_path("app/src/scripts/modal/cookie_quiz_answer.rs:325"),
                 interaction: BroadcastInteraction::default()
                     .set_name("cookie_quiz_answer")
                     .set_type(BroadcastInteractionType::Modal),
@@ -373,40 +358,14 @@ pub(crate) async fn triggered(ctx: &Context, payload: &InteractionCreate) {
         ).await;
     } else {
         // naaa, give him 3 nuggets
-        let question_completed_result = cookies::quiz::question_completed(
-            &pool,
+        let nuggets_given = UserQuizProgress::complete(
+            pool.deref(),
             &requests,
-            &user_id,
+            format!("quiz:nuggets:{user_id}:{date}").as_str(),
+            user_id.to_string(),
+            QuizReward::Nuggets(3)
         ).await;
 
-        if let Err(e) = question_completed_result {
-            error!(target: "Runtime", "Cannot declare the user cookie question as completed: {e:#?}");
-            internal_error(ctx, &payload.interaction, local, "30008").await;
-
-            broadcast_error!(
-                localisation: BroadcastLocalisation::default()
-                    .set_guild(payload.interaction.guild_id.clone())
-                    .set_channel(payload.interaction.channel_id.clone())
-                    .set_code_path("app/src/scripts/modal/cookie_quiz_answer.rs:386"),
-                interaction: BroadcastInteraction::default()
-                    .set_name("cookie_quiz_answer")
-                    .set_type(BroadcastInteractionType::Modal),
-                details: BroadcastDetails::default()
-                    .add("code", "30008")
-                    .add("error", "Cannot declare the user cookie question as completed"),
-                ctx.skynet.as_ref()
-            );
-
-            return;
-        }
-
-        // give the nuggets
-        let nuggets_given = sqlx::query(requests.users.cookies.increase_nuggets.as_str())
-            .bind(3)
-            .bind(&user_id.to_string())
-            .execute(pool.deref())
-            .await;
-
         if let Err(e) = nuggets_given {
             error!(target: "Runtime", "Cannot give 3 nuggets to the user: {e:#?}");
             internal_error(ctx, &payload.interaction, local, "30010").await;
@@ -415,7 +374,7 @@ pub(crate) async fn triggered(ctx: &Context, payload: &InteractionCreate) {
                 localisation: BroadcastLocalisation::default()
                     .set_guild(payload.interaction.guild_id.clone())
                     .set_channel(payload.interaction.channel_id.clone())
-                    .set_code_path("app/src/scripts/modal/cookie_quiz_answer.rs:414"),
+                    .set_code_path("app/src/scripts/modal/cookie_quiz_answer.rs:363"),
                 interaction: BroadcastInteraction::default()
                     .set_name("cookie_quiz_answer")
                     .set_type(BroadcastInteractionType::Modal),
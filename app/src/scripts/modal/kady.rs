@@ -1,4 +1,5 @@
 pub(crate) mod suggest {
+    use crate::scripts::registry::{handler, ComponentScript};
     use log::error;
     use client::manager::events::Context;
     use client::models::channel::ChannelId;
@@ -13,8 +14,10 @@ pub(crate) mod suggest {
     use crate::scripts::modal::get_modal_textinput;
     use crate::scripts::slashs::internal_error;
 
+    pub(crate) const MODAL: ComponentScript = ComponentScript::new("KADY_SUGGEST_MODAL", handler!(triggered));
+
     pub(crate) async fn triggered(ctx: &Context, payload: &InteractionCreate) {
-        let local = get_guild_locale(&payload.interaction.guild_locale);
+        let local = get_guild_locale(&payload.interaction);
         let interaction_data = payload.interaction.data.as_ref().unwrap();
 
         let fields = get_modal_textinput(&interaction_data.components.as_ref().unwrap_or(&Vec::new()));
@@ -93,6 +96,7 @@ pub(crate) mod suggest {
 }
 
 pub(crate) mod issue {
+    use crate::scripts::registry::{handler, ComponentScript};
     use log::error;
     use client::manager::events::Context;
     use client::models::channel::ChannelId;
@@ -108,8 +112,10 @@ pub(crate) mod issue {
     use crate::scripts::modal::get_modal_textinput;
     use crate::scripts::slashs::internal_error;
 
+    pub(crate) const MODAL: ComponentScript = ComponentScript::new("KADY_ISSUE_MODAL", handler!(triggered));
+
     pub(crate) async fn triggered(ctx: &Context, payload: &InteractionCreate) {
-        let local = get_guild_locale(&payload.interaction.guild_locale);
+        let local = get_guild_locale(&payload.interaction);
         let interaction_data = payload.interaction.data.as_ref().unwrap();
 
         let fields = get_modal_textinput(&interaction_data.components.as_ref().unwrap_or(&Vec::new()));
@@ -212,6 +218,7 @@ pub(crate) mod issue {
 }
 
 pub(crate) mod review {
+    use crate::scripts::registry::{handler, ComponentScript};
     use std::str::FromStr;
     use chrono::Utc;
     use log::error;
@@ -247,8 +254,10 @@ pub(crate) mod review {
         }
     }
 
+    pub(crate) const MODAL: ComponentScript = ComponentScript::new("KADY_REVIEW_MODAL", handler!(triggered));
+
     pub(crate) async fn triggered(ctx: &Context, payload: &InteractionCreate) {
-        let local = get_guild_locale(&payload.interaction.guild_locale);
+        let local = get_guild_locale(&payload.interaction);
         let interaction_data = payload.interaction.data.as_ref().unwrap();
 
         let fields = get_modal_textinput(&interaction_data.components.as_ref().unwrap_or(&Vec::new()));
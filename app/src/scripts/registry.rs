@@ -0,0 +1,570 @@
+//! The slash commands and the components of Kady
+//!
+//! Each command declares here its definition, its category, its permission, its cooldown and its handler:
+//! the registration (`application_commands_manager::COMMANDS`) and the dispatch (`scripts::slash_command_received`)
+//! are both derived from [`COMMANDS`].
+//!
+//! Each component declares its requirements next to its handler, and is listed in [`BUTTONS`],
+//! [`SELECT_MENUS`] or [`MODALS`] for the dispatch.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use lazy_static::lazy_static;
+use client::manager::events::Context;
+use client::models::events::InteractionCreate;
+use client::models::interaction::{ApplicationCommand, ApplicationCommandOption, ApplicationCommandOptionType, ApplicationCommandType};
+use features::cooldown::Cooldown;
+use crate::application_commands_manager::CommandType;
+use crate::constants::ADMIN_GUILD;
+use crate::scripts::{buttons, modal, select_menu, slashs};
+
+pub(crate) type HandlerFuture<'a> = Pin<Box<dyn Future<Output = ()> + Send + 'a>>;
+/// The function called when a command is used
+pub(crate) type Handler = for<'a> fn(&'a Context, &'a InteractionCreate) -> HandlerFuture<'a>;
+
+/// Turn an `async fn(&Context, &InteractionCreate)` into a [`Handler`]
+macro_rules! handler {
+    ($path:path) => {{
+        fn handle<'a>(
+            ctx: &'a ::client::manager::events::Context,
+            payload: &'a ::client::models::events::InteractionCreate
+        ) -> $crate::scripts::registry::HandlerFuture<'a> {
+            Box::pin($path(ctx, payload))
+        }
+        handle as $crate::scripts::registry::Handler
+    }};
+}
+pub(crate) use handler;
+
+/// Who can use a command
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub(crate) enum Permission {
+    Everyone,
+    /// The administrators of Kady, the attempts of the others are reported
+    Admin
+}
+
+pub(crate) struct Command {
+    /// Build the command sent to Discord
+    pub definition: fn() -> ApplicationCommand,
+    pub category: CommandType,
+    /// The old names of the command, still dispatched until Discord forgets them
+    pub aliases: &'static [&'static str],
+    pub permission: Permission,
+    /// The command is refused while the database is unavailable
+    pub needs_database: bool,
+    /// The delay between two uses, the administrators aren't throttled
+    pub cooldown: Option<Cooldown>,
+    pub handler: Handler
+}
+
+impl Command {
+    const fn new(definition: fn() -> ApplicationCommand, category: CommandType, handler: Handler) -> Self {
+        Self {
+            definition,
+            category,
+            aliases: &[],
+            permission: Permission::Everyone,
+            needs_database: false,
+            cooldown: None,
+            handler
+        }
+    }
+
+    /// An admin command, registered in the admin guild only
+    const fn admin(definition: fn() -> ApplicationCommand, handler: Handler) -> Self {
+        let mut command = Self::new(definition, CommandType::Dev, handler);
+        command.permission = Permission::Admin;
+        command
+    }
+
+    const fn aliases(mut self, aliases: &'static [&'static str]) -> Self {
+        self.aliases = aliases;
+        self
+    }
+
+    const fn needs_database(mut self) -> Self {
+        self.needs_database = true;
+        self
+    }
+
+    const fn cooldown(mut self, cooldown: Cooldown) -> Self {
+        self.cooldown = Some(cooldown);
+        self
+    }
+}
+
+/// Every slash command of Kady
+pub(crate) static COMMANDS: &[Command] = &[
+    // dev
+    Command::admin(admin_memory_report_slash, handler!(slashs::admin::admin_memory_report::triggered)),
+    Command::admin(admin_reload_langs_slash, handler!(slashs::admin::admin_reload_langs::triggered)),
+    Command::admin(admin_reload_config_slash, handler!(slashs::admin::admin_reload_config::triggered)),
+    Command::admin(admin_errors_slash, handler!(slashs::admin::admin_errors::triggered)).needs_database(),
+    Command::admin(admin_tasks_slash, handler!(slashs::admin::admin_tasks::triggered)),
+    Command::admin(admin_reload_requests_slash, handler!(slashs::admin::admin_reload_requests::triggered)),
+    Command::admin(admin_reload_commands_slash, handler!(slashs::admin::admin_reload_slashs::triggered))
+        .aliases(&["admin_update_commands"]),
+    // fun
+    Command::new(eight_ball_slash, CommandType::Fun, handler!(slashs::fun::eight_ball::triggered)),
+    Command::new(welcome_slash, CommandType::Fun, handler!(slashs::common::welcome::triggered)),
+    Command::new(unacceptable_slash, CommandType::Fun, handler!(slashs::fun::unacceptable::triggered)),
+    Command::new(rateit_slash, CommandType::Fun, handler!(slashs::fun::rateit::triggered)).aliases(&["note"]),
+    // tools
+    Command::new(citation_slash, CommandType::Tools, handler!(slashs::citation::triggered))
+        .needs_database()
+        .cooldown(Cooldown::per_user(60)),
+    Command::new(top_slash, CommandType::Tools, handler!(slashs::top::triggered))
+        .needs_database()
+        .cooldown(Cooldown::per_channel(10)),
+    Command::new(cookies_slash, CommandType::Tools, handler!(slashs::cookies::triggered)).needs_database(),
+    // the rank card is an image generated on each use
+    Command::new(guild_rank_slash, CommandType::Tools, handler!(slashs::xp::guild_rank::triggered))
+        .needs_database()
+        .cooldown(Cooldown::per_user(15)),
+    // common
+    Command::new(ping_slash, CommandType::Common, handler!(slashs::common::ping::triggered)),
+    Command::new(avatar_slash, CommandType::Common, handler!(slashs::common::avatar_slash::triggered)),
+    Command::new(banner_slash, CommandType::Common, handler!(slashs::common::banner_slash::triggered)),
+    Command::new(user_info, CommandType::Common, handler!(slashs::common::user_info::triggered)),
+    Command::new(help_slash, CommandType::Common, handler!(slashs::common::help::triggered)),
+    Command::new(kady_slash, CommandType::Common, handler!(slashs::common::kady::triggered)),
+    Command::new(data_slash, CommandType::Common, handler!(slashs::data::triggered))
+        .needs_database()
+        .cooldown(Cooldown::per_user(60))
+];
+
+/// A button, a select menu or a modal, declared next to its handler
+pub(crate) struct ComponentScript {
+    /// The name at the start of the custom_id
+    pub name: &'static str,
+    /// The component is refused while the database is unavailable
+    pub needs_database: bool,
+    /// The delay between two uses, the administrators aren't throttled
+    pub cooldown: Option<Cooldown>,
+    pub handler: Handler
+}
+
+impl ComponentScript {
+    pub(crate) const fn new(name: &'static str, handler: Handler) -> Self {
+        Self {
+            name,
+            needs_database: false,
+            cooldown: None,
+            handler
+        }
+    }
+
+    pub(crate) const fn needs_database(mut self) -> Self {
+        self.needs_database = true;
+        self
+    }
+
+    pub(crate) const fn cooldown(mut self, cooldown: Cooldown) -> Self {
+        self.cooldown = Some(cooldown);
+        self
+    }
+}
+
+/// Every button of Kady
+pub(crate) static BUTTONS: &[ComponentScript] = &[
+    buttons::captcha_request::BUTTON,
+    buttons::captcha_try::BUTTON,
+    buttons::cookies::BUTTON,
+    buttons::kady::suggestion::BUTTON,
+    buttons::data::EXPORT_BUTTON,
+    buttons::data::ERASE_BUTTON,
+    buttons::data::CANCEL_BUTTON
+];
+
+/// Every select menu of Kady
+pub(crate) static SELECT_MENUS: &[ComponentScript] = &[
+    select_menu::select_help_category::SELECT_MENU
+];
+
+/// Every modal of Kady
+pub(crate) static MODALS: &[ComponentScript] = &[
+    modal::cookie_quiz_answer::MODAL,
+    modal::kady::suggest::MODAL,
+    modal::kady::issue::MODAL,
+    modal::kady::review::MODAL
+];
+
+/// Find a component by the name at the start of its custom_id
+pub(crate) fn find_component(components: &'static [ComponentScript], name: &str) -> Option<&'static ComponentScript> {
+    components.iter().find(|c| c.name == name)
+}
+
+lazy_static! {
+    /// The commands by name and by alias
+    static ref BY_NAME: HashMap<String, &'static Command> = {
+        let mut by_name = HashMap::new();
+
+        for command in COMMANDS {
+            by_name.insert((command.definition)().name, command);
+            for alias in command.aliases {
+                by_name.insert(alias.to_string(), command);
+            }
+        }
+
+        by_name
+    };
+}
+
+/// Find a command by its name or one of its aliases
+pub(crate) fn find(name: &str) -> Option<&'static Command> {
+    BY_NAME.get(name).copied()
+}
+
+
+//
+//
+// commands builders
+//
+//
+
+
+
+fn ping_slash() -> ApplicationCommand {
+    ApplicationCommand::new_global(
+        "ping",
+        "🏓 Get Kady's latency",
+        ApplicationCommandType::ChatInput
+    )
+}
+
+fn guild_rank_slash() -> ApplicationCommand {
+    ApplicationCommand::new_global(
+        "guild_rank",
+        "🏆 Get the guild rank of a player",
+        ApplicationCommandType::ChatInput
+    )
+        .set_dm_permission(false)
+        .add_option(
+            ApplicationCommandOption::new(ApplicationCommandOptionType::User, "user", "The user you want to get the rank of", false)
+        )
+}
+
+fn top_slash() -> ApplicationCommand {
+    ApplicationCommand::new_global(
+        "top",
+        "🏆 Get the rankings for the server... or for the whole world!",
+        ApplicationCommandType::ChatInput
+    )
+        .set_dm_permission(true)
+        .add_option(
+            ApplicationCommandOption::new(
+                ApplicationCommandOptionType::SubCommand,
+                "xp",
+                "⭐ The xp rankings of the server",
+                false
+            )
+        )
+        .add_option(
+            ApplicationCommandOption::new(
+                ApplicationCommandOptionType::SubCommand,
+                "cookies", // reputation
+                "🍪 Who have the most cookies ??",
+                false
+            )
+        )
+}
+
+
+
+fn admin_reload_commands_slash() -> ApplicationCommand {
+    ApplicationCommand::new_local(
+        "admin_reload_commands",
+        "🔄 Update all slash commands",
+        ApplicationCommandType::ChatInput,
+        ADMIN_GUILD.into(),
+    )
+        .add_option(
+            ApplicationCommandOption::new(ApplicationCommandOptionType::Boolean, "dry_run", "Only show what would be changed", false)
+        )
+}
+
+fn admin_reload_requests_slash() -> ApplicationCommand {
+    ApplicationCommand::new_local(
+        "admin_reload_requests",
+        "🔄 Update the request list for the database",
+        ApplicationCommandType::ChatInput,
+        ADMIN_GUILD.into(),
+    )
+}
+
+fn admin_reload_langs_slash() -> ApplicationCommand {
+    ApplicationCommand::new_local(
+        "admin_reload_langs",
+        "🔄 Update the translations",
+        ApplicationCommandType::ChatInput,
+        ADMIN_GUILD.into(),
+    )
+}
+
+fn admin_reload_config_slash() -> ApplicationCommand {
+    ApplicationCommand::new_local(
+        "admin_reload_config",
+        "🔄 Reload the config file",
+        ApplicationCommandType::ChatInput,
+        ADMIN_GUILD.into(),
+    )
+}
+
+fn admin_errors_slash() -> ApplicationCommand {
+    ApplicationCommand::new_local(
+        "admin_errors",
+        "🚨 Manage the reported errors",
+        ApplicationCommandType::ChatInput,
+        ADMIN_GUILD.into(),
+    )
+        .add_option(
+            ApplicationCommandOption::new(ApplicationCommandOptionType::SubCommand, "list", "📋 List the unresolved errors", false)
+        )
+        .add_option(
+            ApplicationCommandOption::new(ApplicationCommandOptionType::SubCommand, "inspect", "🔍 Show an error and its last report", false)
+                .add_option(
+                    ApplicationCommandOption::new(ApplicationCommandOptionType::String, "fingerprint", "The fingerprint, or its beginning", true)
+                )
+        )
+        .add_option(
+            ApplicationCommandOption::new(ApplicationCommandOptionType::SubCommand, "resolve", "✅ Mark an error as resolved", false)
+                .add_option(
+                    ApplicationCommandOption::new(ApplicationCommandOptionType::String, "fingerprint", "The fingerprint, or its beginning", true)
+                )
+        )
+}
+
+fn admin_memory_report_slash() -> ApplicationCommand {
+    ApplicationCommand::new_local(
+        "admin_memory_report",
+        "📄 Get the memory usage",
+        ApplicationCommandType::ChatInput,
+        ADMIN_GUILD.into(),
+    )
+}
+
+fn admin_tasks_slash() -> ApplicationCommand {
+    ApplicationCommand::new_local(
+        "admin_tasks",
+        "🩺 Get the health of the background tasks",
+        ApplicationCommandType::ChatInput,
+        ADMIN_GUILD.into(),
+    )
+}
+
+
+
+fn citation_slash() -> ApplicationCommand {
+    ApplicationCommand::new_global(
+        "citation",
+        "✉️ Send a beautiful citation to the whole server",
+        ApplicationCommandType::ChatInput
+    )
+        .set_dm_permission(false)
+        .add_option(
+            ApplicationCommandOption::new(ApplicationCommandOptionType::String, "citation", "Your citation here", true)
+        )
+}
+
+fn avatar_slash() -> ApplicationCommand {
+    ApplicationCommand::new_global(
+        "avatar",
+        "👤 Get the beautiful avatar of yourself or your friends",
+        ApplicationCommandType::ChatInput
+    )
+        .set_dm_permission(true)
+        .add_option(
+            ApplicationCommandOption::new(ApplicationCommandOptionType::User, "user", "The user (optional)", false)
+        )
+}
+
+fn banner_slash() -> ApplicationCommand {
+    ApplicationCommand::new_global(
+        "banner",
+        "👤 Get the beautiful banner of yourself or your friends",
+        ApplicationCommandType::ChatInput
+    )
+        .set_dm_permission(true)
+        .add_option(
+            ApplicationCommandOption::new(ApplicationCommandOptionType::User, "user", "The user (optional)", false)
+        )
+}
+
+fn cookies_slash() -> ApplicationCommand {
+    ApplicationCommand::new_global(
+        "cookies",
+        "🍪 A cookie ?",
+        ApplicationCommandType::ChatInput
+    )
+        .set_dm_permission(true)
+        .add_option(
+            ApplicationCommandOption::new(
+                ApplicationCommandOptionType::SubCommand,
+                "daily",
+                "🍪 Get your daily cookie by solving an enigma",
+                false
+            )
+        )
+        .add_option(
+        ApplicationCommandOption::new(
+            ApplicationCommandOptionType::SubCommand,
+            "donate",
+            "🍪 Give one or more cookies to your friends !",
+            false
+        )
+            .add_option(
+                ApplicationCommandOption::new(
+                    ApplicationCommandOptionType::User,
+                    "user",
+                    "👤 The lucky person who will receive your cookie(s)",
+                    true
+                )
+            )
+            .add_option(
+                ApplicationCommandOption::new(
+                    ApplicationCommandOptionType::Number,
+                    "number",
+                    "🍪 The number of cookies you want to donate",
+                    true
+                )
+            )
+    )
+}
+
+
+
+fn rateit_slash() -> ApplicationCommand {
+    ApplicationCommand::new_global(
+        "rateit",
+        "📒 Will you have the best note ?",
+        ApplicationCommandType::ChatInput
+    )
+}
+
+
+
+fn eight_ball_slash() -> ApplicationCommand {
+    ApplicationCommand::new_global(
+        "8ball",
+        "🎱 Will the chance be with you ?",
+        ApplicationCommandType::ChatInput
+    )
+        .add_option(
+            ApplicationCommandOption::new(
+                ApplicationCommandOptionType::String,
+                "question",
+                "😁 Your question",
+                true
+            )
+        )
+}
+
+fn unacceptable_slash() -> ApplicationCommand {
+    ApplicationCommand::new_global(
+        "unacceptable",
+        "💥 This is definitely unacceptable!",
+        ApplicationCommandType::ChatInput
+    )
+}
+
+fn welcome_slash() -> ApplicationCommand {
+    ApplicationCommand::new_global(
+        "welcome",
+        "👋 Welcome the new members",
+        ApplicationCommandType::ChatInput
+    )
+}
+
+fn user_info() -> ApplicationCommand {
+    ApplicationCommand::new_global("userinfo", "👤 Get informations about someone", ApplicationCommandType::ChatInput)
+        .add_option(
+            ApplicationCommandOption::new(
+                ApplicationCommandOptionType::User,
+                "user",
+                "👋 The user you want informations about (leave blank to get your infos)",
+                false
+            )
+        )
+}
+
+fn help_slash() -> ApplicationCommand {
+    ApplicationCommand::new_global("help", "🔎 Find every Kady's commands!", ApplicationCommandType::ChatInput)
+        .add_option(
+            ApplicationCommandOption::new(ApplicationCommandOptionType::String, "command", "🔎 The command you search for", false)
+        )
+}
+
+fn kady_slash() -> ApplicationCommand {
+    ApplicationCommand::new_global("kady", "✨ Hello!", ApplicationCommandType::ChatInput)
+        .add_option(
+            ApplicationCommandOption::new(
+                ApplicationCommandOptionType::SubCommand,
+                "invite",
+                "🔗 Invite Kady in your server!",
+                false
+            )
+        )
+        .add_option(
+            ApplicationCommandOption::new(
+                ApplicationCommandOptionType::SubCommand,
+                "support",
+                "🔗 Join the support server of Kady",
+                false
+            )
+        )
+        .add_option(
+            ApplicationCommandOption::new(
+                ApplicationCommandOptionType::SubCommand,
+                "informations",
+                "🔎 Get informations on Kady",
+                false
+            )
+        )
+        .add_option(
+            ApplicationCommandOption::new(
+                ApplicationCommandOptionType::SubCommand,
+                "suggest",
+                "💡 Give a suggestion to the development team!",
+                false
+            )
+        )
+        .add_option(
+            ApplicationCommandOption::new(
+                ApplicationCommandOptionType::SubCommand,
+                "issue",
+                "💥 Report a bug to the development team",
+                false
+            )
+        )
+        .add_option(
+            ApplicationCommandOption::new(
+                ApplicationCommandOptionType::SubCommand,
+                "review",
+                "✨ Give you opinion about Kady",
+                false
+            )
+        )
+}
+
+fn data_slash() -> ApplicationCommand {
+    ApplicationCommand::new_global("data", "🔒 Manage the data Kady stores about you", ApplicationCommandType::ChatInput)
+        .add_option(
+            ApplicationCommandOption::new(
+                ApplicationCommandOptionType::SubCommand,
+                "export",
+                "📦 Receive all your data in your DMs",
+                false
+            )
+        )
+        .add_option(
+            ApplicationCommandOption::new(
+                ApplicationCommandOptionType::SubCommand,
+                "erase",
+                "🗑️ Erase all your data",
+                false
+            )
+        )
+}
+
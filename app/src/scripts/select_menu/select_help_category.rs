@@ -4,9 +4,12 @@ use client::models::message::MessageBuilder;
 use translation::message;
 use crate::application_commands_manager::CommandType;
 use crate::assets::help::{generate_all_commands_message, generate_category_message};
-use crate::scripts::{get_guild_locale, QueryParams};
+use crate::scripts::get_guild_locale;
+use crate::scripts::registry::{handler, ComponentScript};
 
-pub async fn triggered(ctx: &Context, payload: &InteractionCreate, _: QueryParams) {
+pub(crate) const SELECT_MENU: ComponentScript = ComponentScript::new("SELECT_HELP_CATEGORY", handler!(triggered));
+
+pub async fn triggered(ctx: &Context, payload: &InteractionCreate) {
     // we can unwrap safely because of a check in the event listener
     let interaction_data = payload.interaction.data.as_ref()
         .unwrap();
@@ -25,7 +28,7 @@ pub async fn triggered(ctx: &Context, payload: &InteractionCreate, _: QueryParam
                 MessageBuilder::new()
                     .set_content(
                         message!(
-                            get_guild_locale(&payload.interaction.guild_locale),
+                            get_guild_locale(&payload.interaction),
                             "slashs::help::invalid_category"
                         )
                     )
@@ -38,7 +41,7 @@ pub async fn triggered(ctx: &Context, payload: &InteractionCreate, _: QueryParam
                         &ctx.skynet,
                         generate_all_commands_message(
                             ctx,
-                            &get_guild_locale(&payload.interaction.guild_locale)
+                            &get_guild_locale(&payload.interaction)
                         ).await
                     ).await;
                 },
@@ -48,7 +51,7 @@ pub async fn triggered(ctx: &Context, payload: &InteractionCreate, _: QueryParam
                         generate_category_message(
                             ctx,
                             ctg,
-                            &get_guild_locale(&payload.interaction.guild_locale)
+                            &get_guild_locale(&payload.interaction)
                         ).await
                     ).await;
                 }
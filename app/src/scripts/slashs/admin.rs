@@ -1,7 +1,12 @@
 use sha2::{Sha256, Digest};
+use client::manager::events::Context;
+use client::models::events::InteractionCreate;
+use client::models::message::MessageBuilder;
+use translation::message;
 use crate::constants::ADMINS;
+use crate::scripts::{get_guild_locale, get_user_id};
 
-fn is_admin(id: impl ToString) -> bool {
+pub(crate) fn is_admin(id: impl ToString) -> bool {
     // generate hash
     let hash = {
         let mut hasher = Sha256::new();
@@ -44,82 +49,92 @@ mod reports {
     }
 }
 
+/// Check that an admin command is triggered by an administrator from a guild
+///
+/// The refusal is replied and reported to the administrators
+pub(crate) async fn authorize(ctx: &Context, payload: &InteractionCreate, command: &str) -> bool {
+    let author = match &payload.interaction.user {
+        Some(u) => format!("{:?} ({})", u.global_name, u.username),
+        None => "unknown".to_string()
+    };
+    let activity = format!("Admin command triggered ({command})");
 
-pub(crate) mod admin_reload_langs {
-    use std::path::PathBuf;
-    use std::str::FromStr;
-    use client::manager::events::Context;
-    use client::models::events::InteractionCreate;
-    use client::models::message::MessageBuilder;
-    use config::Config;
-    use translation::message;
-    use crate::scripts::slashs::admin::{is_admin, reports};
-    use crate::scripts::{get_guild_locale, get_user_id};
+    // if the slash command isn't called from a guild (no GuildMember), we refuse the interaction
+    if payload.interaction.member.is_none() {
+        let _ = payload.interaction.reply(
+            &ctx.skynet,
+            MessageBuilder::new()
+                .set_content(message!(get_guild_locale(&payload.interaction), "errors::not_guild"))
+        ).await;
 
-    pub(crate) async fn triggered(
-        ctx: &Context,
-        payload: &InteractionCreate
-    )
-    {
-        // if the slash command isn't called from a guild (no GuildMember), we refuse the interaction
-        if payload.interaction.member.is_none() {
+        reports::report(
+            &ctx.skynet,
+            author,
+            activity,
+            format!("The command '{command}' was triggered but the guild_member isn't accessible.")
+        ).await;
+
+        return false;
+    }
+
+    let user_id = match get_user_id(&payload.interaction.user, &payload.interaction.member) {
+        Some(id) => id,
+        None => {
             let _ = payload.interaction.reply(
                 &ctx.skynet,
                 MessageBuilder::new()
-                    .set_content(message!(get_guild_locale(&payload.interaction.guild_locale), "errors::not_guild"))
+                    .set_content("INTERNAL ERROR")
             ).await;
 
             reports::report(
                 &ctx.skynet,
-                if let Some(u) = &payload.interaction.user { format!("{:?} ({})", u.global_name, u.username) } else { "unknown".to_string() },
-                "Admin command triggered (admin_reload_langs)",
-                "The command 'admin_update_slashs' was triggered but the guild_member isn't accessible."
+                author,
+                activity,
+                format!("The command '{command}' was triggered but no User ID were found.")
             ).await;
 
-            return;
+            return false;
         }
+    };
 
-        let user_id = match get_user_id(&payload.interaction.user, &payload.interaction.member) {
-            Some(id) => id,
-            None => {
-                let _ = payload.interaction.reply(
-                    &ctx.skynet,
-                    MessageBuilder::new()
-                        .set_content("INTERNAL ERROR")
-                ).await;
+    if !is_admin(&user_id) {
+        let _ = payload.interaction.reply(
+            &ctx.skynet,
+            MessageBuilder::new()
+                .set_content(message!(get_guild_locale(&payload.interaction), "errors::admin_only"))
+        ).await;
 
-                reports::report(
-                    &ctx.skynet,
-                    if let Some(u) = &payload.interaction.user { format!("{:?} ({})", u.global_name, u.username) } else { "unknown".to_string() },
-                    "Admin command triggered (admin_reload_langs)",
-                    "The command 'admin_update_slashs' was triggered but no User ID were found."
-                ).await;
+        reports::report(
+            &ctx.skynet,
+            author,
+            activity,
+            format!(
+                "The command '{command}' was triggered by the user (above) with ID {user_id} in the channel {:?} from the guild {:?}.\n\nUser is not registered as administrator.\n\n> **Access denied successfully.**",
+                payload.interaction.channel_id,
+                payload.interaction.guild_id
+            )
+        ).await;
 
-                return;
-            }
-        };
+        return false;
+    }
 
-        if !is_admin(&user_id) {
-            let _ = payload.interaction.reply(
-                &ctx.skynet,
-                MessageBuilder::new()
-                    .set_content(message!(get_guild_locale(&payload.interaction.guild_locale), "errors::admin_only"))
-            ).await;
+    true
+}
 
-            reports::report(
-                &ctx.skynet,
-                if let Some(u) = &payload.interaction.user { format!("{:?} ({})", u.global_name, u.username) } else { "unknown".to_string() },
-                "Admin command triggered (admin_reload_langs)",
-                format!(
-                    "The command 'admin_update_slashs' was triggered by the user (above) with ID {user_id} in the channel {:?} from the guild {:?}.\n\nUser is not registered as administrator.\n\n> **Access denied successfully.**",
-                    payload.interaction.channel_id,
-                    payload.interaction.guild_id
-                )
-            ).await;
 
-            return;
-        }
+pub(crate) mod admin_reload_langs {
+    use std::path::PathBuf;
+    use std::str::FromStr;
+    use client::manager::events::Context;
+    use client::models::events::InteractionCreate;
+    use client::models::message::MessageBuilder;
+    use config::Config;
 
+    pub(crate) async fn triggered(
+        ctx: &Context,
+        payload: &InteractionCreate
+    )
+    {
         let _ = payload.interaction.defer(&ctx.skynet, None).await;
 
         let config = match ctx.get_data::<Config>().await {
@@ -151,7 +166,14 @@ pub(crate) mod admin_reload_langs {
                 let _ = payload.interaction.update(
                     &ctx.skynet,
                     MessageBuilder::new()
-                        .set_content("> ✅ ** ** **Les fichiers de langues ont bien été mis à jour.**")
+                        .set_content({
+                            let metrics = translation::metrics::get();
+                            format!(
+                                "> ✅ ** ** **Les fichiers de langues ont bien été mis à jour.**\n> Traductions de secours depuis le démarrage: `{}`, clés introuvables: `{}`",
+                                metrics.total_fallbacks(),
+                                metrics.total_missing()
+                            )
+                        })
                 ).await;
             }
             Err(e) => {
@@ -171,74 +193,12 @@ pub(crate) mod admin_reload_requests {
     use client::models::message::MessageBuilder;
     use config::Config;
     use database::Database;
-    use translation::message;
-    use crate::scripts::slashs::admin::{is_admin, reports};
-    use crate::scripts::{get_guild_locale, get_user_id};
 
     pub(crate) async fn triggered(
         ctx: &Context,
         payload: &InteractionCreate
     )
     {
-        // if the slash command isn't called from a guild (no GuildMember), we refuse the interaction
-        if payload.interaction.member.is_none() {
-            let _ = payload.interaction.reply(
-                &ctx.skynet,
-                MessageBuilder::new()
-                    .set_content(message!(get_guild_locale(&payload.interaction.guild_locale), "errors::not_guild"))
-            ).await;
-
-            reports::report(
-                &ctx.skynet,
-                if let Some(u) = &payload.interaction.user { format!("{:?} ({})", u.global_name, u.username) } else { "unknown".to_string() },
-                "Admin command triggered (admin_reload_requests)",
-                "The command 'admin_update_slashs' was triggered but the guild_member isn't accessible."
-            ).await;
-
-            return;
-        }
-
-        let user_id = match get_user_id(&payload.interaction.user, &payload.interaction.member) {
-            Some(id) => id,
-            None => {
-                let _ = payload.interaction.reply(
-                    &ctx.skynet,
-                    MessageBuilder::new()
-                        .set_content("INTERNAL ERROR")
-                ).await;
-
-                reports::report(
-                    &ctx.skynet,
-                    if let Some(u) = &payload.interaction.user { format!("{:?} ({})", u.global_name, u.username) } else { "unknown".to_string() },
-                    "Admin command triggered (admin_reload_requests)",
-                    "The command 'admin_update_slashs' was triggered but no User ID were found."
-                ).await;
-
-                return;
-            }
-        };
-
-        if !is_admin(&user_id) {
-            let _ = payload.interaction.reply(
-                &ctx.skynet,
-                MessageBuilder::new()
-                    .set_content(message!(get_guild_locale(&payload.interaction.guild_locale), "errors::admin_only"))
-            ).await;
-
-            reports::report(
-                &ctx.skynet,
-                if let Some(u) = &payload.interaction.user { format!("{:?} ({})", u.global_name, u.username) } else { "unknown".to_string() },
-                "Admin command triggered (admin_reload_requests)",
-                format!(
-                    "The command 'admin_update_slashs' was triggered by the user (above) with ID {user_id} in the channel {:?} from the guild {:?}.\n\nUser is not registered as administrator.\n\n> **Access denied successfully.**",
-                    payload.interaction.channel_id,
-                    payload.interaction.guild_id
-                )
-            ).await;
-
-            return;
-        }
-
         let _ = payload.interaction.defer(&ctx.skynet, None).await;
 
         let config = match ctx.get_data::<Config>().await {
@@ -286,290 +246,396 @@ pub(crate) mod admin_reload_requests {
     }
 }
 
-pub(crate) mod admin_reload_slashs {
-    use chrono::Utc;
+pub(crate) mod admin_reload_config {
     use client::manager::events::Context;
-    use client::models::components::Color;
-    use client::models::components::embed::Embed;
     use client::models::events::InteractionCreate;
     use client::models::message::MessageBuilder;
-    use translation::message;
-    use crate::scripts::{get_guild_locale, get_user_id};
-    use crate::scripts::slashs::admin::{is_admin, reports};
+    use config::ConfigService;
 
     pub(crate) async fn triggered(
         ctx: &Context,
         payload: &InteractionCreate
     )
     {
-        // if the slash command isn't called from a guild (no GuildMember), we refuse the interaction
-        if payload.interaction.member.is_none() {
-            let _ = payload.interaction.reply(
-                &ctx.skynet,
-                MessageBuilder::new()
-                    .set_content(message!(get_guild_locale(&payload.interaction.guild_locale), "errors::not_guild"))
-            ).await;
-
-            reports::report(
-                &ctx.skynet,
-                if let Some(u) = &payload.interaction.user { format!("{:?} ({})", u.global_name, u.username) } else { "unknown".to_string() },
-                "Admin command triggered (admin_reload_slashs)",
-                "The command 'admin_update_slashs' was triggered but the guild_member isn't accessible."
-            ).await;
-
-            return;
-        }
+        let _ = payload.interaction.defer(&ctx.skynet, None).await;
 
-        let user_id = match get_user_id(&payload.interaction.user, &payload.interaction.member) {
-            Some(id) => id,
+        let service = match ctx.get_data::<ConfigService>().await {
+            Some(s) => s,
             None => {
-                let _ = payload.interaction.reply(
+                let _ = payload.interaction.update(
                     &ctx.skynet,
                     MessageBuilder::new()
-                        .set_content("INTERNAL ERROR")
+                        .set_content("> 💥 ** ** **Une erreur est survenant en tentant d'accéder à la configuration.**")
                 ).await;
+                return;
+            }
+        };
 
-                reports::report(
+        // the subscribers are notified by the service, we only have to report the result
+        match service.reload() {
+            Ok(_) => {
+                let _ = payload.interaction.update(
                     &ctx.skynet,
-                    if let Some(u) = &payload.interaction.user { format!("{:?} ({})", u.global_name, u.username) } else { "unknown".to_string() },
-                    "Admin command triggered (admin_reload_slashs)",
-                    "The command 'admin_update_slashs' was triggered but no User ID were found."
+                    MessageBuilder::new()
+                        .set_content("> ✅ ** ** **La configuration a bien été mise à jour.**")
                 ).await;
-
-                return;
             }
+            Err(e) => {
+                let _ = payload.interaction.update(
+                    &ctx.skynet,
+                    MessageBuilder::new()
+                        .set_content(format!("> 💥 ** ** **La configuration n'a pas été modifiée, le fichier est invalide:** {e}"))
+                ).await;
+            }
+        }
+    }
+}
+
+pub(crate) mod admin_reload_slashs {
+    use chrono::Utc;
+    use client::manager::events::Context;
+    use client::models::components::Color;
+    use client::models::components::embed::Embed;
+    use client::models::events::InteractionCreate;
+    use client::models::interaction::CommandOptions;
+    use client::models::message::MessageBuilder;
+    use crate::application_commands_manager::synchronize;
+
+    pub(crate) async fn triggered(
+        ctx: &Context,
+        payload: &InteractionCreate
+    )
+    {
+        let dry_run = CommandOptions::of(&payload.interaction).get::<bool>("dry_run").unwrap_or(false);
+
+        let _ = payload.interaction.defer(&ctx.skynet, None).await;
+
+        let embed = match synchronize(ctx.skynet.as_ref(), ctx.cache.as_ref(), dry_run).await {
+            Ok(reports) => {
+                let changes = reports.iter().any(|r| r.has_changes());
+                let title = match (dry_run, changes) {
+                    (true, true) => "Changes to apply",
+                    (true, false) => "Nothing to apply",
+                    (false, true) => "Success of the operation",
+                    (false, false) => "Everything was already up to date"
+                };
+
+                Embed::new()
+                    .set_color(Color::from_hex(if dry_run { "#ffd117" } else { "#17ff79" }))
+                    .set_title(title)
+                    .set_description(
+                        reports.iter()
+                            .map(|r| r.to_string())
+                            .collect::<Vec<String>>()
+                            .join("\n\n")
+                    )
+            },
+            Err(e) => Embed::new()
+                .set_color(Color::from_hex("#ff174f"))
+                .set_title("Error")
+                .set_description(format!("```rust\n{e:#?}\n```"))
         };
 
-        let channel_id = match &payload.interaction.channel_id {
-            Some(id) => id,
+        let _ = payload.interaction.update(
+            &ctx.skynet,
+            MessageBuilder::new()
+                .add_embed(embed.set_timestamp(Utc::now()))
+        ).await;
+    }
+}
+
+pub(crate) mod admin_memory_report {
+    use client::manager::events::Context;
+    use client::models::events::InteractionCreate;
+    use client::models::message::MessageBuilder;
+    use config::Config;
+
+    pub(crate) async fn triggered(
+        ctx: &Context,
+        payload: &InteractionCreate
+    )
+    {
+        let config = match ctx.get_data::<Config>().await {
+            Some(c) => c,
             None => {
                 let _ = payload.interaction.reply(
                     &ctx.skynet,
                     MessageBuilder::new()
-                        .set_content("Cannot obtain the Channel ID")
-                ) .await;
+                        .set_content("> ❌ ** ** **Cannot get the object** `Config` **from the context.**")
+                ).await;
                 return;
             }
         };
 
-        if !is_admin(&user_id) {
-            let _ = payload.interaction.reply(
-                &ctx.skynet,
-                MessageBuilder::new()
-                    .set_content(message!(get_guild_locale(&payload.interaction.guild_locale), "errors::admin_only"))
-            ).await;
-
-            reports::report(
-                &ctx.skynet,
-                if let Some(u) = &payload.interaction.user { format!("{:?} ({})", u.global_name, u.username) } else { "unknown".to_string() },
-                "Admin command triggered (admin_reload_slashs)",
-                format!(
-                    "The command 'admin_update_slashs' was triggered by the user (above) with ID {user_id} in the channel {:?} from the guild {:?}.\n\nUser is not registered as administrator.\n\n> **Access denied successfully.**",
-                    payload.interaction.channel_id,
-                    payload.interaction.guild_id
-                )
-            ).await;
-
-            return;
-        }
-
         let _ = payload.interaction.defer(&ctx.skynet, None).await;
 
-        let (success, errors) = crate::application_commands_manager::instance_trigger(
-            ctx.skynet.clone(),
-            ctx.cache.clone()
+        let report = crate::tasks::report_memory_usage(
+            ctx.cache.clone(),
+            ctx.shard_manager.clone(),
+            config,
+            // get Kb
+            false
         ).await;
 
-        if success.is_none() && errors.is_some() {
-            let _ = payload.interaction.update(
-                &ctx.skynet,
-                MessageBuilder::new()
-                    .add_embed(
-                        Embed::new()
-                            .set_color(Color::from_hex("#ff174f"))
-                            .set_title("Error")
-                            .set_description(format!("{} errors were received.", errors.as_ref().unwrap().len()))
-                            .set_timestamp(Utc::now())
-                    )
-            ).await;
-
-            for error in errors.as_ref().unwrap().iter() {
-                let _ = channel_id.send_message(
+        match report {
+            Err(e) => {
+                let _ = payload.interaction.update(
                     &ctx.skynet,
                     MessageBuilder::new()
-                        .set_content(format!("```rust\n{error:#?}\n```"))
+                        .set_content(format!("> :x: ** ** **Une erreur est survenue:** {e}"))
                 ).await;
-            }
-        } else if success.is_some() && errors.is_none() {
-            let _ = payload.interaction.update(
-                &ctx.skynet,
-                MessageBuilder::new()
-                    .add_embed(
-                        Embed::new()
-                            .set_color(Color::from_hex("#17ff79"))
-                            .set_timestamp(Utc::now())
-                            .set_title("Success of the operation")
-                            .set_description(
-                                format!(
-                                    "> **Everything went alright:**\n{}",
-                                    success.as_ref().unwrap().iter()
-                                        .map(|s| format!("- {s}"))
-                                        .collect::<Vec<String>>()
-                                        .join("\n")
-                                )
-                            )
-                    )
-            ).await;
-        } else if success.is_none() && errors.is_some() {
-            let _ = payload.interaction.update(
-                &ctx.skynet,
-                MessageBuilder::new()
-                    .add_embed(
-                        Embed::new()
-                            .set_color(Color::from_hex("#ffd117"))
-                            .set_timestamp(Utc::now())
-                            .set_title("Some actions failed")
-                            .set_description(
-                                format!(
-                                    "> {} errors was received\n> **Actions done:**\n{}\n\n:warning: For errors, they will be sent under this message",
-                                    errors.as_ref().unwrap().len(),
-                                    success.as_ref().unwrap().iter()
-                                        .map(|s| format!("- {s}"))
-                                        .collect::<Vec<String>>()
-                                        .join("\n")
-                                )
-                            )
-                    )
-            ).await;
-
-            for error in errors.as_ref().unwrap().iter() {
-                let _ = channel_id.send_message(
+            },
+            Ok(report) => {
+                let _ = payload.interaction.update(
                     &ctx.skynet,
                     MessageBuilder::new()
-                        .set_content(format!("```rust\n{error:#?}\n```"))
+                        .set_content(format!("> :white_check_mark: ** ** **Rapport d'utilisation de la mémoire:**\n```rs\n{report:#?}\n```"))
                 ).await;
             }
-        } else if success.is_none() && errors.is_none() {
-            // some real shit is happening
-            let _ = payload.interaction.update(
-                &ctx.skynet,
-                MessageBuilder::new()
-                    .set_content("No informations was sent back from the app.\n\nSome real shit is happening right there, call Sedorriku NOW.")
-            ).await;
         }
     }
 }
 
-pub(crate) mod admin_memory_report {
+pub(crate) mod admin_errors {
     use client::manager::events::Context;
+    use client::models::components::Color;
+    use client::models::components::embed::{Embed, Field};
     use client::models::events::InteractionCreate;
-    use client::models::message::MessageBuilder;
-    use config::Config;
-    use translation::message;
-    use crate::scripts::{get_guild_locale, get_user_id};
-    use crate::scripts::slashs::admin::{is_admin, reports};
+    use client::models::interaction::CommandOptions;
+    use client::models::message::{AttachmentBuilder, MessageAttachmentBuilder, MessageBuilder};
+    use database::Database;
+    use database::model::errors::ErrorRecord;
+    use crate::scripts::database_unavailable;
+
+    const AVAILABLE_SUBCOMMANDS: &[&str] = &["list", "inspect", "resolve"];
+    /// The number of errors shown by `list`
+    const LIST_LIMIT: u64 = 15;
+    /// The minimum length of a fingerprint prefix, to avoid resolving every error at once
+    const MIN_FINGERPRINT_LENGTH: usize = 8;
+
+    /// Check that the value can be the beginning of a fingerprint, a hexadecimal SHA-256
+    ///
+    /// The value is used in a `LIKE`, so it must not contain `%` or `_`
+    fn is_fingerprint_prefix(value: &str) -> bool {
+        !value.is_empty() && value.len() <= 64 && value.chars().all(|c| c.is_ascii_hexdigit())
+    }
 
     pub(crate) async fn triggered(
         ctx: &Context,
         payload: &InteractionCreate
     )
     {
-        // if the slash command isn't called from a guild (no GuildMember), we refuse the interaction
-        if payload.interaction.member.is_none() {
-            let _ = payload.interaction.reply(
-                &ctx.skynet,
-                MessageBuilder::new()
-                    .set_content(message!(get_guild_locale(&payload.interaction.guild_locale), "errors::not_guild"))
-            ).await;
-
-            reports::report(
-                &ctx.skynet,
-                if let Some(u) = &payload.interaction.user { format!("{:?} ({})", u.global_name, u.username) } else { "unknown".to_string() },
-                "Admin command triggered (admin_reload_slashs)",
-                "The command 'admin_memory_report' was triggered but the guild_member isn't accessible."
-            ).await;
-
-            return;
-        }
-
-        let user_id = match get_user_id(&payload.interaction.user, &payload.interaction.member) {
-            Some(id) => id,
-            None => {
+        let (subcommand, options) = match CommandOptions::of(&payload.interaction).subcommand() {
+            Some((name, options)) if AVAILABLE_SUBCOMMANDS.contains(&name) => (name, options),
+            _ => {
                 let _ = payload.interaction.reply(
                     &ctx.skynet,
                     MessageBuilder::new()
-                        .set_content("INTERNAL ERROR")
+                        .set_content("> ❌ ** ** **Sous-commande inconnue.**")
                 ).await;
-
-                reports::report(
-                    &ctx.skynet,
-                    if let Some(u) = &payload.interaction.user { format!("{:?} ({})", u.global_name, u.username) } else { "unknown".to_string() },
-                    "Admin command triggered (admin_reload_slashs)",
-                    "The command 'admin_memory_report' was triggered but no User ID were found."
-                ).await;
-
                 return;
             }
         };
 
-        if !is_admin(&user_id) {
+        let fingerprint = options.get::<String>("fingerprint")
+            .map(|f| f.trim().to_lowercase())
+            .unwrap_or_default();
+
+        if subcommand != "list" && !is_fingerprint_prefix(&fingerprint) {
             let _ = payload.interaction.reply(
                 &ctx.skynet,
                 MessageBuilder::new()
-                    .set_content(message!(get_guild_locale(&payload.interaction.guild_locale), "errors::admin_only"))
-            ).await;
-
-            reports::report(
-                &ctx.skynet,
-                if let Some(u) = &payload.interaction.user { format!("{:?} ({})", u.global_name, u.username) } else { "unknown".to_string() },
-                "Admin command triggered (admin_reload_slashs)",
-                format!(
-                    "The command 'admin_memory_report' was triggered by the user (above) with ID {user_id} in the channel {:?} from the guild {:?}.\n\nUser is not registered as administrator.\n\n> **Access denied successfully.**",
-                    payload.interaction.channel_id,
-                    payload.interaction.guild_id
-                )
+                    .set_content("> ❌ ** ** **L'empreinte doit être hexadécimale.**")
             ).await;
-
             return;
         }
 
-        let config = match ctx.get_data::<Config>().await {
-            Some(c) => c,
+        let database = match ctx.get_data::<Database>().await {
+            Some(db) => db,
             None => {
                 let _ = payload.interaction.reply(
                     &ctx.skynet,
                     MessageBuilder::new()
-                        .set_content("> ❌ ** ** **Cannot get the object** `Config` **from the context.**")
+                        .set_content("> ❌ ** ** **Cannot get the object** `Database` **from the context.**")
                 ).await;
                 return;
             }
         };
 
+        let Ok(pool) = database.get_pool().await else { return database_unavailable(ctx, &payload.interaction).await };
+        let requests = database.get_requests().await;
+
         let _ = payload.interaction.defer(&ctx.skynet, None).await;
 
-        let report = crate::tasks::report_memory_usage(
-            ctx.cache.clone(),
-            ctx.shard_manager.clone(),
-            config,
-            // get Kb
-            false
-        ).await;
+        match subcommand {
+            "list" => {
+                let records = match ErrorRecord::list_unresolved(&pool, requests.system.errors.list_unresolved.as_str(), LIST_LIMIT).await {
+                    Ok(records) => records,
+                    Err(e) => {
+                        let _ = payload.interaction.update(
+                            &ctx.skynet,
+                            MessageBuilder::new()
+                                .set_content(format!("> :x: ** ** **Une erreur est survenue:** {e}"))
+                        ).await;
+                        return;
+                    }
+                };
+
+                let description = if records.is_empty() {
+                    "> ✅ ** ** **Aucune erreur non résolue.**".to_string()
+                } else {
+                    records.iter()
+                        .map(|r| format!(
+                            "`{}` `{}` **×{}** - {} - <t:{}:R>",
+                            &r.fingerprint[..12],
+                            r.code.as_deref().unwrap_or("-----"),
+                            r.occurrences,
+                            r.code_path,
+                            r.last_seen.timestamp()
+                        ))
+                        .collect::<Vec<String>>()
+                        .join("\n")
+                };
 
-        match report {
-            Err(e) => {
                 let _ = payload.interaction.update(
                     &ctx.skynet,
                     MessageBuilder::new()
-                        .set_content(format!("> :x: ** ** **Une erreur est survenue:** {e}"))
+                        .add_embed(
+                            Embed::new()
+                                .set_title("Erreurs non résolues")
+                                .set_description(description)
+                                .set_color(Color::from_rgb(255, 51, 51))
+                        )
                 ).await;
-            },
-            Ok(report) => {
+            }
+            "inspect" => {
+                let record = match ErrorRecord::get(&pool, requests.system.errors.get.as_str(), &fingerprint).await {
+                    Ok(Some(record)) => record,
+                    Ok(None) => {
+                        let _ = payload.interaction.update(
+                            &ctx.skynet,
+                            MessageBuilder::new()
+                                .set_content(format!("> ❌ ** ** **Aucune erreur ne correspond à** `{fingerprint}`"))
+                        ).await;
+                        return;
+                    }
+                    Err(e) => {
+                        let _ = payload.interaction.update(
+                            &ctx.skynet,
+                            MessageBuilder::new()
+                                .set_content(format!("> :x: ** ** **Une erreur est survenue:** {e}"))
+                        ).await;
+                        return;
+                    }
+                };
+
+                let file = AttachmentBuilder {
+                    bytes: record.last_report.as_bytes().to_vec(),
+                    content_type: "application/json".into(),
+                    description: None,
+                    filename: "report.json".into(),
+                    id: 0
+                };
+
+                let msg = MessageBuilder::new()
+                    .add_embed(
+                        Embed::new()
+                            .set_title(format!("Erreur `{}`", &record.fingerprint[..12]))
+                            .set_color(Color::from_rgb(51, 122, 255))
+                            .add_field(Field::new().set_name("Code").set_value(format!("`{}`", record.code.as_deref().unwrap_or("-----"))).set_inline(true))
+                            .add_field(Field::new().set_name("Occurrences").set_value(record.occurrences.to_string()).set_inline(true))
+                            .add_field(Field::new().set_name("Résolue").set_value(if record.resolved { "oui" } else { "non" }).set_inline(true))
+                            .add_field(Field::new().set_name("Code path").set_value(format!("```\n{}```", record.code_path)).set_inline(false))
+                            .add_field(Field::new().set_name("Première occurrence").set_value(format!("<t:{}:F>", record.first_seen.timestamp())).set_inline(true))
+                            .add_field(Field::new().set_name("Dernière occurrence").set_value(format!("<t:{}:F>", record.last_seen.timestamp())).set_inline(true))
+                    )
+                    .add_attachment(MessageAttachmentBuilder {
+                        name: "report.json".into(),
+                        description: None,
+                        content_type: "application/json".into(),
+                        id: 0
+                    });
+
+                let _ = payload.interaction.update_with_files(&ctx.skynet, msg, vec![file]).await;
+            }
+            _ => {
+                if fingerprint.len() < MIN_FINGERPRINT_LENGTH {
+                    let _ = payload.interaction.update(
+                        &ctx.skynet,
+                        MessageBuilder::new()
+                            .set_content(format!("> ❌ ** ** **L'empreinte doit contenir au moins {MIN_FINGERPRINT_LENGTH} caractères.**"))
+                    ).await;
+                    return;
+                }
+
+                let content = match ErrorRecord::resolve(&pool, requests.system.errors.resolve.as_str(), &fingerprint).await {
+                    Ok(0) => format!("> ❌ ** ** **Aucune erreur ne correspond à** `{fingerprint}`"),
+                    Ok(count) => format!("> ✅ ** ** **{count} erreur(s) marquée(s) comme résolue(s).**"),
+                    Err(e) => format!("> :x: ** ** **Une erreur est survenue:** {e}")
+                };
+
                 let _ = payload.interaction.update(
                     &ctx.skynet,
-                    MessageBuilder::new()
-                        .set_content(format!("> :white_check_mark: ** ** **Rapport d'utilisation de la mémoire:**\n```rs\n{report:#?}\n```"))
+                    MessageBuilder::new().set_content(content)
                 ).await;
             }
         }
     }
-}
\ No newline at end of file
+}
+
+pub(crate) mod admin_tasks {
+    use client::manager::events::Context;
+    use client::models::components::Color;
+    use client::models::components::embed::{Embed, Field};
+    use client::models::events::InteractionCreate;
+    use client::models::message::MessageBuilder;
+    use client::supervisor::{self, TaskState};
+
+    /// The maximum number of fields of an embed
+    const MAX_TASKS: usize = 25;
+
+    pub(crate) async fn triggered(
+        ctx: &Context,
+        payload: &InteractionCreate
+    )
+    {
+        let tasks = supervisor::health();
+        let unhealthy = tasks.iter().filter(|t| !t.is_healthy()).count();
+
+        let mut embed = Embed::new()
+            .set_title("État des tâches")
+            .set_description(format!("> {} tâches suivies, {unhealthy} en échec", tasks.len()))
+            .set_color(if unhealthy == 0 { Color::from_rgb(51, 255, 153) } else { Color::from_rgb(255, 51, 51) });
+
+        for task in tasks.iter().take(MAX_TASKS) {
+            let state = match task.state {
+                TaskState::Running => "🟢 en cours",
+                TaskState::Restarting => "🟠 redémarrage",
+                TaskState::Finished => "⚪ terminée",
+                TaskState::OneShot => "⚪ ponctuelle"
+            };
+
+            let mut value = format!(
+                "{state}\nDémarrée <t:{}:R>\nRedémarrages: `{}` • Paniques: `{}`",
+                task.started.timestamp(),
+                task.restarts,
+                task.panics
+            );
+            if let Some(last_panic) = &task.last_panic {
+                value.push_str(&format!("\n```\n{}```", last_panic.chars().take(200).collect::<String>()));
+            }
+
+            embed = embed.add_field(
+                Field::new()
+                    .set_name(&task.name)
+                    .set_value(value)
+                    .set_inline(false)
+            );
+        }
+
+        let _ = payload.interaction.reply(
+            &ctx.skynet,
+            MessageBuilder::new().add_embed(embed)
+        ).await;
+    }
+}
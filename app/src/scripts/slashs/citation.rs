@@ -6,19 +6,19 @@ use client::manager::events::Context;
 use client::models::components::Color;
 use client::models::components::embed::{Author, Embed};
 use client::models::events::InteractionCreate;
-use client::models::interaction::InteractionDataOptionValue;
+use client::models::interaction::CommandOptions;
 use client::models::message::MessageBuilder;
 use database::Database;
 use database::model::guild::Guild;
 use translation::message;
 use crate::assets;
-use crate::scripts::get_guild_locale;
+use crate::scripts::{database_unavailable, get_guild_locale};
 use crate::crates::error_broadcaster::*;
 use crate::broadcast_error;
 use crate::scripts::slashs::internal_error;
 
 pub(crate) async fn triggered(ctx: &Context, payload: &InteractionCreate) {
-    let local = get_guild_locale(&payload.interaction.guild_locale);
+    let local = get_guild_locale(&payload.interaction);
 
     let guild_id = match &payload.interaction.guild_id {
         Some(id) => id,
@@ -33,7 +33,7 @@ pub(crate) async fn triggered(ctx: &Context, payload: &InteractionCreate) {
 
     let database = ctx.get_data::<Database>().await.expect("Cannot acquire the database structure");
     let guild_data = {
-        let pool = database.get_pool().await;
+        let Ok(pool) = database.get_pool().await else { return database_unavailable(ctx, &payload.interaction).await };
         match Guild::from_pool(&pool, &database.get_requests().await.guilds.get, guild_id).await {
             Ok(g) => g,
             Err(e) => {
@@ -59,6 +59,8 @@ pub(crate) async fn triggered(ctx: &Context, payload: &InteractionCreate) {
         }
     };
 
+    let lang = guild_data.lang_or(&local);
+
     if !guild_data.citation_enabled.unwrap_or(false) {
         not_enabled(ctx, payload).await;
         return
@@ -117,89 +119,25 @@ pub(crate) async fn triggered(ctx: &Context, payload: &InteractionCreate) {
     }
 
     // we get the text
-    let text: String = match &payload.interaction.data {
-        Some(datas) => {
-            match &datas.options {
-                Some(options) => {
-                    if let Some(t) = &options.iter().find(|opt| opt.name == "citation") {
-                        match &t.value {
-                            Some(InteractionDataOptionValue::String(s)) => s.clone(),
-                            _ => {
-                                internal_error(ctx, &payload.interaction, local , "12006").await;
-
-                                broadcast_error!(
-                                    localisation: BroadcastLocalisation::default()
-                                        .set_guild(payload.interaction.guild_id.clone())
-                                        .set_channel(payload.interaction.channel_id.clone())
-                                        .set_code_path("app/src/scripts/slashs/citation.rs:133"),
-                                    interaction: BroadcastInteraction::default()
-                                        .set_name("citation")
-                                        .set_type(BroadcastInteractionType::SlashCommand),
-                                    details: BroadcastDetails::default()
-                                        .add("error", "Cannot get the citation's text")
-                                        .add("reason", "The text is not a string"),
-                                    ctx.skynet.as_ref()
-                                );
-
-                                return;
-                            }
-                        }
-                    } else {
-                        internal_error(ctx, &payload.interaction, local , "12005").await;
-
-                        broadcast_error!(
-                            localisation: BroadcastLocalisation::default()
-                                .set_guild(payload.interaction.guild_id.clone())
-                                .set_channel(payload.interaction.channel_id.clone())
-                                .set_code_path("app/src/scripts/slashs/citation.rs:152"),
-                            interaction: BroadcastInteraction::default()
-                                .set_name("citation")
-                                .set_type(BroadcastInteractionType::SlashCommand),
-                            details: BroadcastDetails::default()
-                                .add("error", "Cannot get the citation's text")
-                                .add("reason", "The text is not a string"),
-                            ctx.skynet.as_ref()
-                        );
-
-                        return;
-                    }
-                },
-                None => {
-                    internal_error(ctx, &payload.interaction, local , "12004").await;
-
-                    broadcast_error!(
-                        localisation: BroadcastLocalisation::default()
-                            .set_guild(payload.interaction.guild_id.clone())
-                            .set_channel(payload.interaction.channel_id.clone())
-                            .set_code_path("app/src/scripts/slashs/citation.rs:171"),
-                        interaction: BroadcastInteraction::default()
-                            .set_name("citation")
-                            .set_type(BroadcastInteractionType::SlashCommand),
-                        details: BroadcastDetails::default()
-                            .add("error", "Cannot get the citation's text")
-                            .add("reason", "The text is not a string"),
-                        ctx.skynet.as_ref()
-                    );
-                    return;
-                }
-            }
-        },
+    let text = match CommandOptions::of(&payload.interaction).get::<String>("citation") {
+        Some(text) => text,
         None => {
-            internal_error(ctx, &payload.interaction, local , "12003").await;
+            internal_error(ctx, &payload.interaction, local , "12005").await;
 
             broadcast_error!(
                 localisation: BroadcastLocalisation::default()
                     .set_guild(payload.interaction.guild_id.clone())
                     .set_channel(payload.interaction.channel_id.clone())
-                    .set_code_path("app/src/scripts/slashs/citation.rs:190"),
+                    .set_code_path("app/src/scripts/slashs/citation.rs:122"),
                 interaction: BroadcastInteraction::default()
                     .set_name("citation")
                     .set_type(BroadcastInteractionType::SlashCommand),
                 details: BroadcastDetails::default()
                     .add("error", "Cannot get the citation's text")
-                    .add("reason", "The text is not a string"),
+                    .add("reason", "The text is missing or is not a string"),
                 ctx.skynet.as_ref()
             );
+
             return;
         }
     };
@@ -251,7 +189,7 @@ pub(crate) async fn triggered(ctx: &Context, payload: &InteractionCreate) {
             let _ = payload.interaction.reply(
                 &ctx.skynet,
                 MessageBuilder::new()
-                    .set_content(message!(guild_data.lang, "features::citation::sent"))
+                    .set_content(message!(lang.clone(), "features::citation::sent"))
                     .set_ephemeral(true)
             ).await;
         },
@@ -294,7 +232,7 @@ pub(crate) async fn triggered(ctx: &Context, payload: &InteractionCreate) {
 }
 
 async fn cannot_get_guild_data(ctx: &Context, payload: &InteractionCreate) {
-    let guild_locale = get_guild_locale(&payload.interaction.guild_locale);
+    let guild_locale = get_guild_locale(&payload.interaction);
     let _ = payload.interaction.reply(
         &ctx.skynet,
         MessageBuilder::new()
@@ -304,7 +242,7 @@ async fn cannot_get_guild_data(ctx: &Context, payload: &InteractionCreate) {
 }
 
 async fn not_enabled(ctx: &Context, payload: &InteractionCreate) {
-    let guild_locale = get_guild_locale(&payload.interaction.guild_locale);
+    let guild_locale = get_guild_locale(&payload.interaction);
     let _ = payload.interaction.reply(
         &ctx.skynet,
         MessageBuilder::new()
@@ -314,7 +252,7 @@ async fn not_enabled(ctx: &Context, payload: &InteractionCreate) {
 }
 
 async fn no_valid_channel(ctx: &Context, payload: &InteractionCreate) {
-    let guild_locale = get_guild_locale(&payload.interaction.guild_locale);
+    let guild_locale = get_guild_locale(&payload.interaction);
     let _ = payload.interaction.reply(
         &ctx.skynet,
         MessageBuilder::new()
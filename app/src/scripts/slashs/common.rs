@@ -38,7 +38,7 @@ pub(crate) mod avatar_slash {
     use client::models::components::Color;
     use client::models::components::embed::{Author, Embed, EmbedImage, Footer};
     use client::models::events::InteractionCreate;
-    use client::models::interaction::{ApplicationCommandOptionType, InteractionDataOptionValue};
+    use client::models::interaction::CommandOptions;
     use client::models::message::MessageBuilder;
     use client::models::user::UserId;
     use features::coolors::colors::ColorCount;
@@ -47,29 +47,12 @@ pub(crate) mod avatar_slash {
     use crate::constants::DEFAULT_AVATAR;
     use crate::scripts::{get_guild_locale, get_user, get_user_id};
     use crate::scripts::slashs::common::get_vibrant_color;
-    use crate::scripts::slashs::internal_error;
 
     pub(crate) async fn triggered(ctx: &Context, payload: &InteractionCreate) {
-        let local = get_guild_locale(&payload.interaction.guild_locale);
-
-        let data = match &payload.interaction.data {
-            Some(d) => d,
-            None => return internal_error(ctx, &payload.interaction, local, "16001").await
-        };
+        let local = get_guild_locale(&payload.interaction);
 
-        let options = data.options.as_ref().cloned().unwrap_or(Vec::new());
-
-        let user = options.iter()
-            .find(|opt| opt.name.as_str() == "user" && opt.option_type == ApplicationCommandOptionType::User);
-
-        match user {
-            Some(u) => {
-                match &u.value {
-                    Some(InteractionDataOptionValue::String(user_id)) => self::user(ctx, payload, local, user_id.into()).await,
-                    Some(_) => internal_error(ctx, &payload.interaction, local, "16002").await,
-                    None => internal_error(ctx, &payload.interaction, local, "16003").await
-                }
-            },
+        match CommandOptions::of(&payload.interaction).get::<UserId>("user") {
+            Some(user_id) => self::user(ctx, payload, local, user_id).await,
             None => author(ctx, payload, local).await
         }
     }
@@ -234,7 +217,7 @@ pub(crate) mod banner_slash {
     use client::models::components::Color;
     use client::models::components::embed::{Author, Embed, EmbedImage, Footer};
     use client::models::events::InteractionCreate;
-    use client::models::interaction::{ApplicationCommandOptionType, InteractionDataOptionValue};
+    use client::models::interaction::CommandOptions;
     use client::models::message::MessageBuilder;
     use client::models::user::UserId;
     use features::coolors::colors::ColorCount;
@@ -243,48 +226,14 @@ pub(crate) mod banner_slash {
     use crate::constants::DEFAULT_AVATAR;
     use crate::scripts::{get_guild_locale, get_user, get_user_id};
     use crate::scripts::slashs::common::get_vibrant_color;
-    use crate::scripts::slashs::internal_error;
     use crate::crates::error_broadcaster::*;
     use crate::broadcast_error;
 
     pub(crate) async fn triggered(ctx: &Context, payload: &InteractionCreate) {
-        let local = get_guild_locale(&payload.interaction.guild_locale);
-
-        let data = match &payload.interaction.data {
-            Some(d) => d,
-            None => {
-                internal_error(ctx, &payload.interaction, local, "16001").await;
-
-                broadcast_error!(
-                    localisation: BroadcastLocalisation::default()
-                        .set_guild(payload.interaction.guild_id.clone())
-                        .set_channel(payload.interaction.channel_id.clone())
-                        .set_code_path("app/src/scripts/slashs/common.rs:banner_slash:262"),
-                    interaction: BroadcastInteraction::default()
-                        .set_name("banner")
-                        .set_type(BroadcastInteractionType::SlashCommand),
-                    details: BroadcastDetails::default()
-                        .add("reason", "Cannot get interaction data"),
-                    ctx.skynet.as_ref()
-                );
-
-                return
-            }
-        };
-
-        let options = data.options.as_ref().cloned().unwrap_or(Vec::new());
-
-        let user = options.iter()
-            .find(|opt| opt.name.as_str() == "user" && opt.option_type == ApplicationCommandOptionType::User);
+        let local = get_guild_locale(&payload.interaction);
 
-        match user {
-            Some(u) => {
-                match &u.value {
-                    Some(InteractionDataOptionValue::String(user_id)) => self::user(ctx, payload, local, user_id.into()).await,
-                    Some(_) => internal_error(ctx, &payload.interaction, local, "17002").await,
-                    None => internal_error(ctx, &payload.interaction, local, "17003").await
-                }
-            },
+        match CommandOptions::of(&payload.interaction).get::<UserId>("user") {
+            Some(user_id) => self::user(ctx, payload, local, user_id).await,
             None => author(ctx, payload, local).await
         }
     }
@@ -509,7 +458,7 @@ pub(crate) mod welcome {
     use crate::scripts::get_guild_locale;
 
     pub(crate) async fn triggered(ctx: &Context, payload: &InteractionCreate) {
-        let local = get_guild_locale(&payload.interaction.guild_locale);
+        let local = get_guild_locale(&payload.interaction);
         let _ = payload.interaction.reply(
             &ctx.skynet,
             MessageBuilder::new()
@@ -530,7 +479,7 @@ pub(crate) mod ping {
     use crate::scripts::get_guild_locale;
 
     pub(crate) async fn triggered(ctx: &Context, payload: &InteractionCreate) {
-        let local = get_guild_locale(&payload.interaction.guild_locale);
+        let local = get_guild_locale(&payload.interaction);
 
         let shard_manager = ctx.shard_manager.read().await;
 
@@ -590,7 +539,7 @@ pub(crate) mod user_info {
     use client::models::components::Color;
     use client::models::components::embed::{Author, Embed, Thumbnail};
     use client::models::events::InteractionCreate;
-    use client::models::interaction::{ApplicationCommandOptionType, InteractionDataOptionValue};
+    use client::models::interaction::CommandOptions;
     use client::models::message::MessageBuilder;
     use client::models::SnowflakeInfo;
     use client::models::user::{User, UserId};
@@ -601,42 +550,11 @@ pub(crate) mod user_info {
     use crate::scripts::slashs::internal_error;
 
     pub(crate) async fn triggered(ctx: &Context, payload: &InteractionCreate) {
-        let local = get_guild_locale(&payload.interaction.guild_locale);
-
-        let interaction_data_options = match &payload.interaction.data {
-            Some(data) => data.options.as_ref(),
-            None => {
-                internal_error(ctx, &payload.interaction, local, "17001").await;
-                return;
-            }
-        };
+        let local = get_guild_locale(&payload.interaction);
 
-        if let Some(options) = interaction_data_options {
-            let user_id = options.iter()
-                .find(|o| o.name == "user" && o.option_type == ApplicationCommandOptionType::User);
-
-            match user_id {
-                Some(user_id) => {
-                    match user_id.value.as_ref() {
-                        Some(InteractionDataOptionValue::String(id)) => {
-                            user_id_given(
-                                ctx,
-                                payload,
-                                local,
-                                id.clone()
-                            ).await;
-                        },
-                        _ => {
-                            internal_error(ctx, &payload.interaction, local, "17003").await;
-                        }
-                    }
-                }
-                None => {
-                    no_options_given(ctx, payload, local).await;
-                }
-            }
-        } else {
-            no_options_given(ctx, payload, local).await;
+        match CommandOptions::of(&payload.interaction).get::<UserId>("user") {
+            Some(user_id) => user_id_given(ctx, payload, local, user_id).await,
+            None => no_options_given(ctx, payload, local).await
         }
     }
 
@@ -644,17 +562,15 @@ pub(crate) mod user_info {
         ctx: &Context,
         payload: &InteractionCreate,
         local: String,
-        id: String
+        user_id: UserId
     ) {
-        let user_id = id.into();
-
         let user = match get_user(ctx, &user_id).await {
             Some(u) => u,
             None => {
                 let _ = payload.interaction.reply(
                     &ctx.skynet,
                     MessageBuilder::new()
-                        .set_content(message!(local, "errors:cannot_acquire_user"))
+                        .set_content(message!(local, "errors::cannot_acquire_user"))
                 ).await;
                 return;
             }
@@ -838,7 +754,7 @@ pub(crate) mod help {
     use log::error;
     use client::manager::events::Context;
     use client::models::events::InteractionCreate;
-    use client::models::interaction::ApplicationCommandOptionType;
+    use client::models::interaction::CommandOptions;
     use client::models::message::MessageBuilder;
     use translation::message;
     use crate::application_commands_manager::{COMMANDS, get_command_type};
@@ -847,20 +763,9 @@ pub(crate) mod help {
     use crate::scripts::slashs::internal_error;
 
     pub(crate) async fn triggered(ctx: &Context, payload: &InteractionCreate) {
-        let local = get_guild_locale(&payload.interaction.guild_locale);
-
-        let interaction_data = payload.interaction.data.as_ref()
-            .unwrap();
-
-        if let Some(command) = interaction_data.options.as_ref().unwrap_or(&Vec::new())
-            .iter()
-            .find(|opt| opt.name == "command" && opt.option_type == ApplicationCommandOptionType::String && opt.value.is_some())
-            .map(|opt| opt.value.clone().unwrap())
-        {
-            let name = command.to_string();
-
-            let local = get_guild_locale(&payload.interaction.guild_locale);
+        let local = get_guild_locale(&payload.interaction);
 
+        if let Some(name) = CommandOptions::of(&payload.interaction).get::<String>("command") {
             let commands = COMMANDS.read().await;
             if let Some(cmd_type) = get_command_type(&commands, name.as_str()) {
                 let _ = payload.interaction.reply(
@@ -903,6 +808,7 @@ pub(crate) mod kady {
     use client::models::components::Emoji;
     use client::models::components::message_components::{ActionRow, Button, ButtonStyle, Component, ComponentType, TextInput, TextInputStyle};
     use client::models::events::InteractionCreate;
+    use client::models::interaction::CommandOptions;
     use client::models::message::MessageBuilder;
     use config::Config;
     use translation::fmt::formatter::Formatter;
@@ -916,21 +822,14 @@ pub(crate) mod kady {
     const AVAILABLE_SUBCOMMANDS: &[&str] = &["invite", "support", "informations", "suggest", "issue", "review"];
 
     pub(crate) async fn triggered(ctx: &Context, payload: &InteractionCreate) {
-        let interaction_data = payload.interaction.data.as_ref()
-            .unwrap();
-
-        let local = get_guild_locale(&payload.interaction.guild_locale);
-
-        let options = interaction_data.options.clone()
-            .unwrap_or(Vec::new());
+        let local = get_guild_locale(&payload.interaction);
 
-        let subcommand = options.iter()
-            .find(|opt|
-                AVAILABLE_SUBCOMMANDS.contains(&opt.name.as_str()));
+        let subcommand = CommandOptions::of(&payload.interaction).subcommand()
+            .filter(|(name, _)| AVAILABLE_SUBCOMMANDS.contains(name));
 
         match subcommand {
-            Some(sub) => {
-                match sub.name.as_str() {
+            Some((name, _)) => {
+                match name {
                     "invite" => invite(ctx, payload, &local).await,
                     "support" => support(ctx, payload, &local).await,
                     "informations" => informations(ctx, payload, &local).await,
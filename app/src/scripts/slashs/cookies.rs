@@ -2,25 +2,23 @@
 
 use client::manager::events::Context;
 use client::models::events::InteractionCreate;
+use client::models::interaction::CommandOptions;
 use client::models::message::MessageBuilder;
 use database::Database;
 use database::model::users::User;
 use translation::message;
-use crate::scripts::{get_guild_locale, get_user_id};
-use crate::scripts::slashs::internal_error;
-use crate::crates::error_broadcaster::*;
-use crate::broadcast_error;
+use crate::scripts::{database_unavailable, get_guild_locale, get_user_id};
 
 mod give_cookies {
     use log::error;
     use sqlx::MySqlPool;
     use client::manager::events::Context;
     use client::models::events::InteractionCreate;
-    use client::models::interaction::{InteractionDataOption, InteractionDataOptionValue};
+    use client::models::interaction::CommandOptions;
     use client::models::message::MessageBuilder;
     use client::models::user::UserId;
     use database::dynamic_requests::DynamicRequest;
-    use database::model::users::UserCookie;
+    use database::model::users::{CookieOperation, UserCookie};
     use translation::fmt::formatter::Formatter;
     use translation::message;
     use crate::crates::cookies::notify_new_cookie;
@@ -33,69 +31,22 @@ mod give_cookies {
         ctx: &Context,
         payload: &InteractionCreate,
         local: String,
-        subcommand: &InteractionDataOption,
+        options: CommandOptions<'_>,
         pool: &MySqlPool,
         requests: &DynamicRequest
     )
     {
-        let options = match &subcommand.options {
-            Some(opts) => opts,
-            _ => {
-                internal_error(ctx, &payload.interaction, local, "15003").await;
-
-                broadcast_error!(
-                    localisation: BroadcastLocalisation::default()
-                        .set_guild(payload.interaction.guild_id.clone())
-                        .set_channel(payload.interaction.channel_id.clone())
-                        .set_code_path("app/src/scripts/slashs/cookies.rs:give_cookies::triggered:51"),
-                    interaction: BroadcastInteraction::default()
-                        .set_name("cookies/give")
-                        .set_type(BroadcastInteractionType::SlashCommand),
-                    details: BroadcastDetails::default()
-                        .add("code", "15003")
-                        .add("reason", "Cannot acquire the interaction data options"),
-                    ctx.skynet.as_ref()
-                );
-
-                return;
-            }
-        };
-
         // find user ID
-        let user_id = {
-            let opt = options.iter().find(|o| o.name == "user");
-
-            if let Some(option) = opt {
-                match &option.value {
-                    Some(InteractionDataOptionValue::String(v)) => v.to_string(),
-                    _ => {
-                        internal_error(ctx, &payload.interaction, local, "15004").await;
-
-                        broadcast_error!(
-                            localisation: BroadcastLocalisation::default()
-                                .set_guild(payload.interaction.guild_id.clone())
-                                .set_channel(payload.interaction.channel_id.clone())
-                                .set_code_path("app/src/scripts/slashs/cookies.rs:give_cookies::triggered:79"),
-                            interaction: BroadcastInteraction::default()
-                                .set_name("cookies/give")
-                                .set_type(BroadcastInteractionType::SlashCommand),
-                            details: BroadcastDetails::default()
-                                .add("code", "15004")
-                                .add("reason", "Cannot acquire the user ID"),
-                            ctx.skynet.as_ref()
-                        );
-
-                        return;
-                    }
-                }
-            } else {
+        let user_id = match options.get::<UserId>("user") {
+            Some(user_id) => user_id.to_string(),
+            None => {
                 internal_error(ctx, &payload.interaction, local, "15004").await;
 
                 broadcast_error!(
                     localisation: BroadcastLocalisation::default()
                         .set_guild(payload.interaction.guild_id.clone())
                         .set_channel(payload.interaction.channel_id.clone())
-                        .set_code_path("app/src/scripts/slashs/cookies.rs:give_cookies::triggered:99"),
+                        .set_code_path("app/src/scripts/slashs/cookies.rs:give_cookies::triggered:42"),
                     interaction: BroadcastInteraction::default()
                         .set_name("cookies/give")
                         .set_type(BroadcastInteractionType::SlashCommand),
@@ -161,62 +112,35 @@ mod give_cookies {
             }
         }
 
-        let cookies_given = {
-            let opt = options.iter().find(|o| o.name == "number");
-
-            if let Some(option) = opt {
-                match &option.value {
-                    Some(InteractionDataOptionValue::Integer(v)) if v > &0i64 => *v,
-                    Some(InteractionDataOptionValue::Integer(_)) => {
-                        let _ = payload.interaction.reply(
-                            &ctx.skynet,
-                            MessageBuilder::new()
-                                .set_content(message!(local, "features::cookies::cookies_number_null"))
-                                .set_ephemeral(true)
-                        ).await;
-                        return;
-                    }
-                    Some(InteractionDataOptionValue::Double(double)) => {
-                        if &double.round() != double {
-                            let _ = payload.interaction.reply(
-                                &ctx.skynet,
-                                MessageBuilder::new()
-                                    .set_content(message!(local, "features::cookies::cookies_number_as_float"))
-                                    .set_ephemeral(true)
-                            ).await;
-                            return;
-                        } else {
-                            double.round() as i64
-                        }
-                    }
-                    _ => {
-                        internal_error(ctx, &payload.interaction, local, "15005").await;
-
-                        broadcast_error!(
-                            localisation: BroadcastLocalisation::default()
-                                .set_guild(payload.interaction.guild_id.clone())
-                                .set_channel(payload.interaction.channel_id.clone())
-                                .set_code_path("app/src/scripts/slashs/cookies.rs:give_cookies::triggered:200"),
-                            interaction: BroadcastInteraction::default()
-                                .set_name("cookies/give")
-                                .set_type(BroadcastInteractionType::SlashCommand),
-                            details: BroadcastDetails::default()
-                                .add("code", "15005")
-                                .add("reason", "Cannot acquire the number of cookies to give"),
-                            ctx.skynet.as_ref()
-                        );
-
-                        return;
-                    }
-                }
-            } else {
+        // the option is a number, so an integer may be given as a float
+        let cookies_given = match options.get::<f64>("number") {
+            Some(number) if number.round() != number => {
+                let _ = payload.interaction.reply(
+                    &ctx.skynet,
+                    MessageBuilder::new()
+                        .set_content(message!(local, "features::cookies::cookies_number_as_float"))
+                        .set_ephemeral(true)
+                ).await;
+                return;
+            }
+            Some(number) if number <= 0.0 => {
+                let _ = payload.interaction.reply(
+                    &ctx.skynet,
+                    MessageBuilder::new()
+                        .set_content(message!(local, "features::cookies::cookies_number_null"))
+                        .set_ephemeral(true)
+                ).await;
+                return;
+            }
+            Some(number) => number as i64,
+            None => {
                 internal_error(ctx, &payload.interaction, local, "15005").await;
 
                 broadcast_error!(
                     localisation: BroadcastLocalisation::default()
                         .set_guild(payload.interaction.guild_id.clone())
                         .set_channel(payload.interaction.channel_id.clone())
-                        .set_code_path("app/src/scripts/slashs/cookies.rs:give_cookies::triggered:220"),
+                        .set_code_path("app/src/scripts/slashs/cookies.rs:give_cookies::triggered:118"),
                     interaction: BroadcastInteraction::default()
                         .set_name("cookies/give")
                         .set_type(BroadcastInteractionType::SlashCommand),
@@ -238,102 +162,46 @@ mod give_cookies {
             let _ = database::model::users::User::ensure(pool, requests.users.ensure.as_str(), user_id.as_str()).await;
         }
 
-        let mut author_cookies = match UserCookie::get_all_cookies(pool, &requests.users.cookies.get, &author_id).await {
-            Ok(cookies) => cookies,
-            Err(e) => {
-                error!(target: "Runtime", "An error occured while fetching all cookies to donate: {e:#?}");
-                internal_error_deferred(ctx, &payload.interaction, local, "15006").await;
-
-                broadcast_error!(
-                    localisation: BroadcastLocalisation::default()
-                        .set_guild(payload.interaction.guild_id.clone())
-                        .set_channel(payload.interaction.channel_id.clone())
-                        .set_code_path("app/src/scripts/slashs/cookies.rs:give_cookies::triggered:252"),
-                    interaction: BroadcastInteraction::default()
-                        .set_name("cookies/give")
-                        .set_type(BroadcastInteractionType::SlashCommand),
-                    details: BroadcastDetails::default()
-                        .add("code", "15006")
-                        .add("reason", "Cannot fetch all cookies to donate"),
-                    ctx.skynet.as_ref()
-                );
+        // the interaction ID is used as the idempotency key, a retried interaction won't donate twice
+        let key = format!("donate:{}", payload.interaction.id);
+        let guild = payload.interaction.guild_id.clone().map(|id| id.to_string());
+
+        let transfer = UserCookie::transfer(
+            pool,
+            &requests.users.cookies,
+            key.as_str(),
+            author_id.to_string(),
+            user_id.clone(),
+            guild,
+            cookies_given as u64
+        ).await;
 
+        match transfer {
+            Ok(CookieOperation::Applied(_)) | Ok(CookieOperation::AlreadyApplied) => {},
+            Ok(CookieOperation::NotEnoughCookies) => {
+                // check if the author has enough cookies :)
+                let _ = payload.interaction.update(
+                    &ctx.skynet,
+                    MessageBuilder::new()
+                        .set_content(message!(local, "features::cookies::not_enough_cookies"))
+                ).await;
                 return;
             }
-        };
-
-        // check if the author has enough cookies :)
-        if author_cookies.len() < cookies_given as usize {
-            let _ = payload.interaction.update(
-                &ctx.skynet,
-                MessageBuilder::new()
-                    .set_content(message!(local, "features::cookies::not_enough_cookies"))
-            ).await;
-            return;
-        }
-
-        // sort the cookies to have the older in first
-        author_cookies.sort_by(|a, b| a.timestamp.timestamp().cmp(&b.timestamp.timestamp()));
-
-        let guild = payload.interaction.guild_id.clone().map(|id| id.to_string()).unwrap_or("NULL".into());
-        for _ in 0..cookies_given {
-            // remove one cookie from the author
-            let last_cookie = author_cookies.pop();
-            if last_cookie.is_none() {
-                internal_error_deferred(ctx, &payload.interaction, local, "15007").await;
-                return
-            }
-            let last_cookie = last_cookie.unwrap();
-
-            let removed_cookie = sqlx::query(requests.users.cookies.remove_cookie.as_str())
-                .bind(&author_id.to_string())
-                .execute(pool)
-                .await;
-
-            if let Err(e) = removed_cookie {
-                error!(target: "Runtime", "An error occured while removing a cookies that was donate: {e:#?}");
+            Err(e) => {
+                error!(target: "Runtime", "An error occured while donating cookies: {e:#?}");
                 internal_error_deferred(ctx, &payload.interaction, local, "15008").await;
 
                 broadcast_error!(
                     localisation: BroadcastLocalisation::default()
                         .set_guild(payload.interaction.guild_id.clone())
                         .set_channel(payload.interaction.channel_id.clone())
-                        .set_code_path("app/src/scripts/slashs/cookies.rs:give_cookies::triggered:302"),
+                        .set_code_path("app/src/scripts/slashs/cookies.rs:give_cookies::triggered:271"),
                     interaction: BroadcastInteraction::default()
                         .set_name("cookies/give")
                         .set_type(BroadcastInteractionType::SlashCommand),
                     details: BroadcastDetails::default()
                         .add("code", "15008")
-                        .add("reason", "Cannot remove a cookie that was donated"),
-                    ctx.skynet.as_ref()
-                );
-
-                return;
-            }
-
-            // add a new cookie :)))
-            let cookie_given = sqlx::query(requests.users.cookies.give_cookie_in_guild.as_str())
-                .bind(last_cookie.user_from)
-                .bind(&user_id)
-                .bind(&guild)
-                .execute(pool)
-                .await;
-
-            if let Err(e) = cookie_given {
-                error!(target: "Runtime", "An error occured while removing a cookies that was donate: {e:#?}");
-                internal_error_deferred(ctx, &payload.interaction, local, "15009").await;
-
-                broadcast_error!(
-                    localisation: BroadcastLocalisation::default()
-                        .set_guild(payload.interaction.guild_id.clone())
-                        .set_channel(payload.interaction.channel_id.clone())
-                        .set_code_path("app/src/scripts/slashs/cookies.rs:give_cookies::triggered:331"),
-                    interaction: BroadcastInteraction::default()
-                        .set_name("cookies/give")
-                        .set_type(BroadcastInteractionType::SlashCommand),
-                    details: BroadcastDetails::default()
-                        .add("code", "15009")
-                        .add("reason", "Cannot give a cookie"),
+                        .add("reason", "Cannot transfer the donated cookies"),
                     ctx.skynet.as_ref()
                 );
 
@@ -690,60 +558,18 @@ mod daily {
 const AVAILABLE_CATEGORIES: [&str; 2] = ["daily", "donate"];
 
 pub(crate) async fn triggered(ctx: &Context, payload: &InteractionCreate) {
-    let local = get_guild_locale(&payload.interaction.guild_locale);
-
-    let data = if let Some(d) = &payload.interaction.data {
-        d
-    } else {
-        internal_error(ctx, &payload.interaction, local, "15001").await;
-
-        broadcast_error!(
-            localisation: BroadcastLocalisation::default()
-                .set_guild(payload.interaction.guild_id.clone())
-                .set_channel(payload.interaction.channel_id.clone())
-                .set_code_path("app/src/scripts/slashs/cookies.rs:triggered:705"),
-            interaction: BroadcastInteraction::default()
-                .set_name("cookies")
-                .set_type(BroadcastInteractionType::SlashCommand),
-            details: BroadcastDetails::default()
-                .add("code", "15001")
-                .add("reason", "Cannot acquire the interaction data"),
-            ctx.skynet.as_ref()
-        );
-
-        return;
-    };
-
-    if data.options.is_none() {
-        internal_error(ctx, &payload.interaction, local, "15002").await;
-
-        broadcast_error!(
-            localisation: BroadcastLocalisation::default()
-                .set_guild(payload.interaction.guild_id.clone())
-                .set_channel(payload.interaction.channel_id.clone())
-                .set_code_path("app/src/scripts/slashs/cookies.rs:triggered:725"),
-            interaction: BroadcastInteraction::default()
-                .set_name("cookies")
-                .set_type(BroadcastInteractionType::SlashCommand),
-            details: BroadcastDetails::default()
-                .add("code", "15002")
-                .add("reason", "Cannot acquire the interaction data options"),
-            ctx.skynet.as_ref()
-        );
-
-        return;
-    }
-    let options = data.options.as_ref().unwrap();
+    let local = get_guild_locale(&payload.interaction);
 
-    let subcommand = options.iter().find(|opt| AVAILABLE_CATEGORIES.contains(&opt.name.as_str()));
+    let subcommand = CommandOptions::of(&payload.interaction).subcommand()
+        .filter(|(name, _)| AVAILABLE_CATEGORIES.contains(name));
 
     match subcommand {
-        Some(sub) => {
-            match sub.name.as_str() {
+        Some((name, options)) => {
+            match name {
                 "donate" => {
                     let db = ctx.get_data::<Database>().await.expect("Cannot acquire the Database");
 
-                    let pool = db.get_pool().await;
+                    let Ok(pool) = db.get_pool().await else { return database_unavailable(ctx, &payload.interaction).await };
                     let requests = db.get_requests().await;
 
                     {
@@ -758,7 +584,7 @@ pub(crate) async fn triggered(ctx: &Context, payload: &InteractionCreate) {
                         ctx,
                         payload,
                         local,
-                        sub,
+                        options,
                         &pool,
                         &requests
                     ).await;
@@ -766,7 +592,7 @@ pub(crate) async fn triggered(ctx: &Context, payload: &InteractionCreate) {
                 "daily" => {
                     let db = ctx.get_data::<Database>().await.expect("Cannot acquire the Database");
 
-                    let pool = db.get_pool().await;
+                    let Ok(pool) = db.get_pool().await else { return database_unavailable(ctx, &payload.interaction).await };
                     let requests = db.get_requests().await;
 
                     {
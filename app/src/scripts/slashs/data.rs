@@ -0,0 +1,60 @@
+//! Error code: 19xxx
+//!
+//! Let the users export or erase every data stored about them (RGPD)
+
+use client::manager::events::Context;
+use client::models::components::Emoji;
+use client::models::components::message_components::{ActionRow, Button, ButtonStyle, Component};
+use client::models::events::InteractionCreate;
+use client::models::interaction::CommandOptions;
+use client::models::message::MessageBuilder;
+use translation::message;
+use crate::scripts::get_guild_locale;
+
+pub(crate) async fn triggered(ctx: &Context, payload: &InteractionCreate) {
+    let local = get_guild_locale(&payload.interaction);
+
+    let subcommand = CommandOptions::of(&payload.interaction).subcommand();
+
+    // each action must be confirmed with a button before being applied
+    let (content, custom_id, style) = match subcommand.map(|(name, _)| name) {
+        Some("export") => (message!(&local, "slashs::data::export::confirm"), "DATA_EXPORT", ButtonStyle::Primary),
+        Some("erase") => (message!(&local, "slashs::data::erase::confirm"), "DATA_ERASE", ButtonStyle::Danger),
+        _ => {
+            let _ = payload.interaction.reply(
+                &ctx.skynet,
+                MessageBuilder::new()
+                    .set_content(message!(local, "slashs::data::no_subcommand"))
+                    .set_ephemeral(true)
+            ).await;
+            return;
+        }
+    };
+
+    let _ = payload.interaction.reply(
+        &ctx.skynet,
+        MessageBuilder::new()
+            .set_content(content)
+            .set_ephemeral(true)
+            .add_component(
+                Component::ActionRow(
+                    ActionRow::new()
+                        .add_component(
+                            Component::Button(
+                                Button::new(custom_id)
+                                    .set_label(message!(&local, "slashs::data::confirm_btn"))
+                                    .set_emoji(Emoji::new(None, "✔️"))
+                                    .set_style(style)
+                            )
+                        )
+                        .add_component(
+                            Component::Button(
+                                Button::new("DATA_CANCEL")
+                                    .set_label(message!(&local, "slashs::data::cancel_btn"))
+                                    .set_style(ButtonStyle::Secondary)
+                            )
+                        )
+                )
+            )
+    ).await;
+}
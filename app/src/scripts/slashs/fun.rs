@@ -8,7 +8,7 @@ pub(crate) mod rateit {
     use crate::scripts::get_guild_locale;
 
     pub(crate) async fn triggered(ctx: &Context, payload: &InteractionCreate) {
-        let local = get_guild_locale(&payload.interaction.guild_locale);
+        let local = get_guild_locale(&payload.interaction);
         let note = {
             let mut thread_rng = rand::thread_rng();
 
@@ -63,6 +63,7 @@ pub(crate) mod eight_ball {
     use rand::Rng;
     use client::manager::events::Context;
     use client::models::events::InteractionCreate;
+    use client::models::interaction::CommandOptions;
     use client::models::message::MessageBuilder;
     use translation::fmt::formatter::Formatter;
     use translation::message;
@@ -71,7 +72,7 @@ pub(crate) mod eight_ball {
     use crate::broadcast_error;
 
     pub(crate) async fn triggered(ctx: &Context, payload: &InteractionCreate) {
-        let local = get_guild_locale(&payload.interaction.guild_locale);
+        let local = get_guild_locale(&payload.interaction);
 
         let choice = {
             let mut thread_rng = rand::thread_rng();
@@ -114,15 +115,7 @@ pub(crate) mod eight_ball {
             }
         };
 
-        let question = payload.interaction.data.as_ref()
-            .map(|d| {
-                d.options.as_ref().map(|options| {
-                    options.iter().find(|o| o.name.as_str() == "question")
-                        .map(|q| q.value.as_ref().map(|v| v.to_string()))
-                        .unwrap_or(None)
-                }).unwrap_or(None)
-            })
-            .unwrap_or(None);
+        let question = CommandOptions::of(&payload.interaction).get::<String>("question");
 
         if let Some(q) = question {
             let _ = payload.interaction.reply(
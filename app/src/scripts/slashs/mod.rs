@@ -1,6 +1,7 @@
 use client::manager::events::Context;
 use client::models::interaction::Interaction;
 use client::models::message::MessageBuilder;
+use error::ErrorReport;
 use translation::{message, fmt::formatter::Formatter};
 
 pub(crate) mod citation;
@@ -9,6 +10,7 @@ pub(crate) mod xp;
 pub(crate) mod top;
 pub(crate) mod cookies;
 pub(crate) mod common;
+pub(crate) mod data;
 pub(crate) mod fun;
 
 #[allow(unused)]
@@ -40,4 +42,36 @@ pub async fn internal_error_deferred(ctx: &Context, interaction: &Interaction, l
             )
             .set_ephemeral(true)
     ).await;
-}
\ No newline at end of file
+}
+
+/// Reply with the translated message of the report, the code is shown to the user
+pub async fn report_error(ctx: &Context, interaction: &Interaction, local: impl ToString, report: &ErrorReport) {
+    let _ = interaction.reply(
+        &ctx.skynet,
+        MessageBuilder::new()
+            .set_content(
+                message!(
+                    local.to_string(),
+                    report.translation_key(),
+                    Formatter::new().add("code", report.code)
+                )
+            )
+            .set_ephemeral(true)
+    ).await;
+}
+
+/// Same as `report_error` for a deferred interaction
+pub async fn report_error_deferred(ctx: &Context, interaction: &Interaction, local: impl ToString, report: &ErrorReport) {
+    let _ = interaction.update(
+        &ctx.skynet,
+        MessageBuilder::new()
+            .set_content(
+                message!(
+                    local.to_string(),
+                    report.translation_key(),
+                    Formatter::new().add("code", report.code)
+                )
+            )
+            .set_ephemeral(true)
+    ).await;
+}
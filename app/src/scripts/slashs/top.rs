@@ -2,12 +2,10 @@
 
 use client::manager::events::Context;
 use client::models::events::InteractionCreate;
+use client::models::interaction::CommandOptions;
 use client::models::message::MessageBuilder;
 use translation::message;
 use crate::scripts::get_guild_locale;
-use crate::scripts::slashs::internal_error;
-use crate::broadcast_error;
-use crate::crates::error_broadcaster::*;
 
 const AVAILABLE_CATEGORIES: &[&str] = &["cookies", "xp"];
 
@@ -16,53 +14,14 @@ pub(crate) async fn triggered(
     payload: &InteractionCreate
 )
 {
-    let local = get_guild_locale(&payload.interaction.guild_locale);
-
-    let data = if let Some(d) = &payload.interaction.data {
-        d
-    } else {
-        internal_error(ctx, &payload.interaction, local, "14001").await;
-
-        broadcast_error!(
-            localisation: BroadcastLocalisation::default()
-                .set_guild(payload.interaction.guild_id.clone())
-                .set_channel(payload.interaction.channel_id.clone())
-                .set_code_path("app/src/scripts/slashs/top.rs:triggered:30"),
-            interaction: BroadcastInteraction::default()
-                .set_name("top")
-                .set_type(BroadcastInteractionType::SlashCommand),
-            details: BroadcastDetails::default()
-                .add("reason", "Cannot acquire the interaction data"),
-            ctx.skynet.as_ref()
-        );
-
-        return;
-    };
-
-    if data.options.is_none() {
-        internal_error(ctx, &payload.interaction, local, "14002").await;
-
-        broadcast_error!(
-            localisation: BroadcastLocalisation::default()
-                .set_guild(payload.interaction.guild_id.clone())
-                .set_channel(payload.interaction.channel_id.clone())
-                .set_code_path("app/src/scripts/slashs/top.rs:triggered:49"),
-            interaction: BroadcastInteraction::default()
-                .set_name("top")
-                .set_type(BroadcastInteractionType::SlashCommand),
-            details: BroadcastDetails::default()
-                .add("reason", "Cannot acquire the interaction data options (None)"),
-            ctx.skynet.as_ref()
-        );
-        return;
-    }
-    let options = data.options.as_ref().unwrap();
+    let local = get_guild_locale(&payload.interaction);
 
-    let subcommand = options.iter().find(|opt| AVAILABLE_CATEGORIES.contains(&opt.name.as_str()));
+    let subcommand = CommandOptions::of(&payload.interaction).subcommand()
+        .filter(|(name, _)| AVAILABLE_CATEGORIES.contains(name));
 
     match subcommand {
-        Some(sub) => {
-            match sub.name.as_str() {
+        Some((name, _)) => {
+            match name {
                 "xp" => categories::xp(ctx, payload, local).await,
                 "cookies" => categories::cookies_global(ctx, payload, local).await,
                 _ => {
@@ -96,8 +55,9 @@ mod categories {
     use database::model::users::CookieTopRank;
     use translation::fmt::formatter::Formatter;
     use translation::message;
-    use crate::scripts::{get_client_user, get_guild, get_user, get_user_id};
-    use crate::scripts::slashs::{internal_error, internal_error_deferred};
+    use error::ErrorCode;
+    use crate::scripts::{database_unavailable, get_client_user, get_guild, get_user, get_user_id};
+    use crate::scripts::slashs::{internal_error, internal_error_deferred, report_error};
     use crate::scripts::slashs::top::cannot_get_guild_data;
     use crate::crates::error_broadcaster::*;
     use crate::broadcast_error;
@@ -120,7 +80,7 @@ mod categories {
         };
 
         let database = ctx.get_data::<Database>().await.expect("Cannot acquire the database structure");
-        let pool = database.get_pool().await;
+        let Ok(pool) = database.get_pool().await else { return database_unavailable(ctx, &payload.interaction).await };
         let requests = database.get_requests().await;
 
         let guild_data = {
@@ -148,11 +108,13 @@ mod categories {
             }
         };
 
+        let lang = guild_data.lang_or(&local);
+
         if !guild_data.xp_enabled.unwrap_or(false) {
             let _ = payload.interaction.reply(
                 &ctx.skynet,
                 MessageBuilder::new()
-                    .set_content(message!(guild_data.lang, "engagement::top::xp_disabled"))
+                    .set_content(message!(lang.clone(), "engagement::top::xp_disabled"))
             ).await;
             return;
         }
@@ -163,9 +125,13 @@ mod categories {
             Ok(rankings) => rankings,
             Err(e) => {
                 error!(target: "Runtime", "An error occured while querying the top 10 xp: {e:#?}");
-                internal_error(ctx, &payload.interaction, guild_data.lang, "14003").await;
+                let report = e.report(ErrorCode::new(14, 3))
+                    .with_context("Cannot acquire the top 10 xp from the database");
+
+                report_error(ctx, &payload.interaction, lang.clone(), &report).await;
 
                 broadcast_error!(
+                    report: report,
                     localisation: BroadcastLocalisation::default()
                         .set_guild(payload.interaction.guild_id.clone())
                         .set_channel(payload.interaction.channel_id.clone())
@@ -173,9 +139,6 @@ mod categories {
                     interaction: BroadcastInteraction::default()
                         .set_name("top")
                         .set_type(BroadcastInteractionType::SlashCommand),
-                    details: BroadcastDetails::default()
-                        .add("code", "14003")
-                        .add("reason", "Cannot acquire the top 10 xp from the database"),
                     ctx.skynet.as_ref()
                 );
 
@@ -187,7 +150,7 @@ mod categories {
             let _ = payload.interaction.reply(
                 &ctx.skynet,
                 MessageBuilder::new()
-                    .set_content(message!(guild_data.lang, "engagement::top::empty"))
+                    .set_content(message!(lang.clone(), "engagement::top::empty"))
                     .set_ephemeral(true)
             ).await;
             return;
@@ -210,7 +173,7 @@ mod categories {
 
             rankings.push_str(
                 message!(
-                    guild_data.lang.clone(),
+                    lang.clone(),
                     "engagement::top::xp_style",
                     Formatter::new()
                         .add("rank", index + 1)
@@ -237,7 +200,7 @@ mod categories {
                     Ok(q) => Some(q.rn),
                     Err(e) => {
                         error!(target: "Runtime", "An error occured while obtaining the rank of the user for the guild xp rank: {e:#?}");
-                        internal_error_deferred(ctx, &payload.interaction, guild_data.lang, "13004").await;
+                        internal_error_deferred(ctx, &payload.interaction, lang.clone(), "13004").await;
 
                         broadcast_error!(
                             localisation: BroadcastLocalisation::default()
@@ -263,7 +226,7 @@ mod categories {
         rankings.push_str("\n\n");
         rankings.push_str(
             message!(
-                guild_data.lang.clone(),
+                lang.clone(),
                 "engagement::top::xp_author_rank",
                 Formatter::new().add("rank", author_rank.unwrap_or(-1))
             ).to_string().as_str()
@@ -274,14 +237,14 @@ mod categories {
         let msg = MessageBuilder::new()
             .set_content(
                 message!(
-                    guild_data.lang.clone(),
+                    lang.clone(),
                     "engagement::top::xp_top",
                     Formatter::new().add("guild", guild.as_ref().map(|g| g.name.clone()).unwrap_or("UnknownGuild".to_string()))
                 )
             )
             .add_embed(
                 Embed::new()
-                    .set_color(Color::from_hex(message!(guild_data.lang, "const::palette::embed")))
+                    .set_color(Color::from_hex(message!(lang.clone(), "const::palette::embed")))
                     .set_thumbnail(
                         Thumbnail::new(
                             guild
@@ -311,7 +274,7 @@ mod categories {
                 let _ = payload.interaction.reply(
                     &ctx.skynet,
                     MessageBuilder::new()
-                        .set_content(message!(local, "engagement::top::global::no_dm"))
+                        .set_content(message!(local, "engagement::top::guild_only"))
                         .set_ephemeral(true)
                 ).await;
                 return;
@@ -319,7 +282,7 @@ mod categories {
         };
 
         let database = ctx.get_data::<Database>().await.expect("Cannot acquire the database structure");
-        let pool = database.get_pool().await;
+        let Ok(pool) = database.get_pool().await else { return database_unavailable(ctx, &payload.interaction).await };
         let requests = database.get_requests().await;
 
         let guild_data = match Guild::from_pool(&pool, requests.guilds.get.as_str(), guild_id).await {
@@ -345,6 +308,8 @@ mod categories {
                 }
             };
 
+        let lang = guild_data.lang_or(&local);
+
         let mut top_10 = {
             let query = sqlx::query_as::<_, database::model::users::CookieRanking>(requests.users.cookies.get_top_10_global.as_str());
 
@@ -352,7 +317,7 @@ mod categories {
                 Ok(rankings) => rankings,
                 Err(e) => {
                     error!(target: "Runtime", "An error occured while querying the top 10 xp: {e:#?}");
-                    internal_error(ctx, &payload.interaction, guild_data.lang, "14005").await;
+                    internal_error(ctx, &payload.interaction, lang.clone(), "14005").await;
 
                     broadcast_error!(
                         localisation: BroadcastLocalisation::default()
@@ -376,7 +341,7 @@ mod categories {
             let _ = payload.interaction.reply(
                 &ctx.skynet,
                 MessageBuilder::new()
-                    .set_content(message!(guild_data.lang, "engagement::top::empty"))
+                    .set_content(message!(lang.clone(), "engagement::top::empty"))
                     .set_ephemeral(true)
             ).await;
             return;
@@ -399,7 +364,7 @@ mod categories {
 
             rankings.push_str(
                 message!(
-                    guild_data.lang.clone(),
+                    lang.clone(),
                     "engagement::top::cookies_style",
                     Formatter::new()
                         .add("rank", index + 1)
@@ -442,7 +407,7 @@ mod categories {
                                 .add("reason", "Cannot acquire the rank of the user for the global cookies rank"),
                             ctx.skynet.as_ref()
                         );
-                        return internal_error_deferred(ctx, &payload.interaction, guild_data.lang, "13006").await;
+                        return internal_error_deferred(ctx, &payload.interaction, lang.clone(), "13006").await;
                     }
                 }
             } else {
@@ -454,7 +419,7 @@ mod categories {
         if let Some(rank) = author_rank {
             rankings.push_str(
                 message!(
-                    guild_data.lang.clone(),
+                    lang.clone(),
                     "engagement::top::cookies_author_rank",
                     Formatter::new().add("rank", rank)
                 ).to_string().as_str()
@@ -462,7 +427,7 @@ mod categories {
         } else {
             rankings.push_str(
                 message!(
-                    guild_data.lang.clone(),
+                    lang.clone(),
                     "engagement::top::no_cookies"
                 ).to_string().as_str()
             );
@@ -471,10 +436,10 @@ mod categories {
         let application = get_client_user(ctx).await;
 
         let msg = MessageBuilder::new()
-            .set_content(message!(guild_data.lang.clone(),"engagement::top::cookies_top_global"))
+            .set_content(message!(lang.clone(),"engagement::top::cookies_top_global"))
             .add_embed(
                 Embed::new()
-                    .set_color(Color::from_hex(message!(guild_data.lang, "const::palette::embed")))
+                    .set_color(Color::from_hex(message!(lang.clone(), "const::palette::embed")))
                     .set_author(
                         Author::new()
                             .set_icon_url(
@@ -504,7 +469,7 @@ mod categories {
 }
 
 async fn cannot_get_guild_data(ctx: &Context, payload: &InteractionCreate) {
-    let guild_locale = get_guild_locale(&payload.interaction.guild_locale);
+    let guild_locale = get_guild_locale(&payload.interaction);
     let _ = payload.interaction.reply(
         &ctx.skynet,
         MessageBuilder::new()
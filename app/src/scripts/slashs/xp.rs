@@ -3,21 +3,23 @@ pub(crate) mod guild_rank {
     use log::error;
     use client::manager::events::Context;
     use client::models::events::InteractionCreate;
-    use client::models::interaction::InteractionDataOptionValue;
+    use client::models::interaction::CommandOptions;
     use client::models::message::{AttachmentBuilder, MessageAttachmentBuilder, MessageBuilder};
+    use client::models::user::UserId;
     use database::Database;
     use database::model::guild::{Guild, GuildUserXp, UserXpRank};
     use database::model::users::User;
     use features::xp;
     use features::xp::image_gen::FontContainer;
     use translation::message;
-    use crate::scripts::get_guild_locale;
-    use crate::scripts::slashs::{internal_error_deferred};
+    use crate::scripts::{database_unavailable, get_guild_locale, get_user_id};
+    use error::ErrorCode;
+    use crate::scripts::slashs::{internal_error_deferred, report_error_deferred};
     use crate::broadcast_error;
     use crate::crates::error_broadcaster::*;
 
     pub(crate) async fn triggered(ctx: &Context, payload: &InteractionCreate) {
-        let local = get_guild_locale(&payload.interaction.guild_locale);
+        let local = get_guild_locale(&payload.interaction);
 
         let guild_id = match &payload.interaction.guild_id {
             Some(id) => id,
@@ -31,7 +33,7 @@ pub(crate) mod guild_rank {
         };
 
         let database = ctx.get_data::<Database>().await.expect("Cannot acquire the database structure");
-        let pool = database.get_pool().await;
+        let Ok(pool) = database.get_pool().await else { return database_unavailable(ctx, &payload.interaction).await };
         let requests = database.get_requests().await;
 
         let guild_data = {
@@ -59,6 +61,8 @@ pub(crate) mod guild_rank {
             }
         };
 
+        let lang = guild_data.lang_or(&local);
+
         // check if the functionality is enabled
         if !guild_data.xp_enabled.unwrap_or(false) {
             let _ = payload.interaction.reply(
@@ -70,44 +74,14 @@ pub(crate) mod guild_rank {
             return;
         }
 
-        let user_id = if let Some(data) = &payload.interaction.data {
-            let r: Option<String> = if let Some(options) = &data.options {
-                let opt = options.iter().find(|o| o.name == "user");
-
-                if let Some(option) = opt {
-                    match &option.value {
-                        Some(InteractionDataOptionValue::String(v)) => Some(v.to_string()),
-                        Some(_) => None,
-                        None => None
-                    }
-                } else {
-                    None
-                }
-            } else {
-                None
-            };
-
-            match r {
-                Some(v) => Some(v),
-                None => {
-                    if let Some(u) = &payload.interaction.user {
-                        Some(u.id.to_string())
-                    } else if let Some(g) = &payload.interaction.member {
-                        g.user.as_ref().map(|u| u.id.to_string())
-                    } else {
-                        None
-                    }
-                }
-            }
-        } else {
-            None
+        // the author is used if no user is given
+        let user_id = match CommandOptions::of(&payload.interaction).get::<UserId>("user")
+            .or_else(|| get_user_id(&payload.interaction.user, &payload.interaction.member))
+        {
+            Some(user_id) => user_id.to_string(),
+            None => return cannot_acquire_user(ctx, payload).await
         };
 
-        if user_id.is_none() {
-            return cannot_acquire_user(ctx, payload).await;
-        }
-        let user_id = user_id.unwrap();
-
         // defer the message response
         let _ = payload.interaction.defer(&ctx.skynet, None).await;
 
@@ -119,12 +93,18 @@ pub(crate) mod guild_rank {
                     let _ = payload.interaction.update(
                         &ctx.skynet,
                         MessageBuilder::new()
-                            .set_content(message!(guild_data.lang, "errors::not_a_member"))
+                            .set_content(message!(lang.clone(), "errors::not_a_member"))
                     ).await;
                     return;
                 }
                 Err(e) => {
+                    let report = e.report(ErrorCode::new(13, 11))
+                        .with_context("Cannot acquire the guild member");
+
+                    report_error_deferred(ctx, &payload.interaction, lang.clone(), &report).await;
+
                     broadcast_error!(
+                        report: report,
                         localisation: BroadcastLocalisation::default()
                             .set_guild(payload.interaction.guild_id.clone())
                             .set_channel(payload.interaction.channel_id.clone())
@@ -132,11 +112,9 @@ pub(crate) mod guild_rank {
                         interaction: BroadcastInteraction::default()
                             .set_name("guild_rank")
                             .set_type(BroadcastInteractionType::SlashCommand),
-                        details: BroadcastDetails::default()
-                            .add("reason", format!("Cannot acquire the guild member: {e:#?}")),
                         ctx.skynet.as_ref()
                     );
-                    return internal_error_deferred(ctx, &payload.interaction, guild_data.lang, "13011").await
+                    return;
                 }
             }
         }
@@ -187,7 +165,7 @@ pub(crate) mod guild_rank {
             let _ = payload.interaction.reply(
                 &ctx.skynet,
                 MessageBuilder::new()
-                    .set_content(message!(guild_data.lang, "errors::not_for_bot"))
+                    .set_content(message!(lang.clone(), "errors::not_for_bot"))
             ).await;
             return;
         }
@@ -235,7 +213,7 @@ pub(crate) mod guild_rank {
                             .add("reason", "Cannot ensure the presence of the user in the database"),
                         ctx.skynet.as_ref()
                     );
-                    return internal_error_deferred(ctx, &payload.interaction, guild_data.lang, "13001").await
+                    return internal_error_deferred(ctx, &payload.interaction, lang.clone(), "13001").await
                 }
             };
         }
@@ -259,7 +237,7 @@ pub(crate) mod guild_rank {
                         .add("reason", "Cannot ensure the presence of the user in the database"),
                     ctx.skynet.as_ref()
                 );
-                return internal_error_deferred(ctx, &payload.interaction, guild_data.lang, "13002").await
+                return internal_error_deferred(ctx, &payload.interaction, lang.clone(), "13002").await
             }
         };
 
@@ -281,7 +259,7 @@ pub(crate) mod guild_rank {
                         .add("reason", "Cannot acquire the font container from the context"),
                     ctx.skynet.as_ref()
                 );
-                return internal_error_deferred(ctx, &payload.interaction, guild_data.lang, "13003").await
+                return internal_error_deferred(ctx, &payload.interaction, lang.clone(), "13003").await
             }
         };
 
@@ -307,7 +285,7 @@ pub(crate) mod guild_rank {
                         .add("reason", "Cannot acquire the rank of the user"),
                     ctx.skynet.as_ref()
                 );
-                return internal_error_deferred(ctx, &payload.interaction, guild_data.lang, "13004").await;
+                return internal_error_deferred(ctx, &payload.interaction, lang.clone(), "13004").await;
             }
         };
 
@@ -338,7 +316,7 @@ pub(crate) mod guild_rank {
                                 .add("reason", "Cannot acquire the guild data"),
                             ctx.skynet.as_ref()
                         );
-                        return internal_error_deferred(ctx, &payload.interaction, guild_data.lang, "13005").await;
+                        return internal_error_deferred(ctx, &payload.interaction, lang.clone(), "13005").await;
                     }
                     Err(e) => {
                         error!(target: "Runtime", "An error occured while trying to fetch the Guild for the guild_rank command: {e:#?}");
@@ -356,7 +334,7 @@ pub(crate) mod guild_rank {
                                 .add("reason", "Cannot acquire the guild data"),
                             ctx.skynet.as_ref()
                         );
-                        return internal_error_deferred(ctx, &payload.interaction, guild_data.lang, "13006").await;
+                        return internal_error_deferred(ctx, &payload.interaction, lang.clone(), "13006").await;
                     }
                 }
             }
@@ -383,7 +361,7 @@ pub(crate) mod guild_rank {
                             .add("reason", "Cannot acquire the user's avatar"),
                         ctx.skynet.as_ref()
                     );
-                    return internal_error_deferred(ctx, &payload.interaction, guild_data.lang, "13007").await
+                    return internal_error_deferred(ctx, &payload.interaction, lang.clone(), "13007").await
                 }
             },
             Err(e) => {
@@ -402,7 +380,7 @@ pub(crate) mod guild_rank {
                         .add("reason", "Cannot acquire the user's avatar"),
                     ctx.skynet.as_ref()
                 );
-                return internal_error_deferred(ctx, &payload.interaction, guild_data.lang, "13008").await
+                return internal_error_deferred(ctx, &payload.interaction, lang.clone(), "13008").await
             }
         };
 
@@ -412,7 +390,7 @@ pub(crate) mod guild_rank {
             xp_data.xp,
             &guild_name,
             rank.rn as u32,
-            message!(guild_data.lang.clone(), "features::xp::rank").to_string(),
+            message!(lang.clone(), "features::xp::rank").to_string(),
             &font_container,
             xp_algo
         ).await;
@@ -422,7 +400,7 @@ pub(crate) mod guild_rank {
                 let mut bytes: Vec<u8> = Vec::new();
                 if let Err(e) = img.write_to(&mut Cursor::new(&mut bytes), image::ImageOutputFormat::Png) {
                     error!(target: "Runtime", "An error occured while manipulating the bytes of the xp guild rank image: {e:#?}");
-                    internal_error_deferred(ctx, &payload.interaction, guild_data.lang, "13010").await;
+                    internal_error_deferred(ctx, &payload.interaction, lang.clone(), "13010").await;
                 };
 
                 let file = AttachmentBuilder {
@@ -449,7 +427,7 @@ pub(crate) mod guild_rank {
             },
             Err(e) => {
                 error!(target: "Runtime", "An error occured while generating the guild xp card: {e:#?}");
-                internal_error_deferred(ctx, &payload.interaction, guild_data.lang, "13009").await;
+                internal_error_deferred(ctx, &payload.interaction, lang.clone(), "13009").await;
 
                 broadcast_error!(
                     localisation: BroadcastLocalisation::default()
@@ -469,7 +447,7 @@ pub(crate) mod guild_rank {
     }
 
     async fn cannot_get_guild_data(ctx: &Context, payload: &InteractionCreate) {
-        let guild_locale = get_guild_locale(&payload.interaction.guild_locale);
+        let guild_locale = get_guild_locale(&payload.interaction);
         let _ = payload.interaction.reply(
             &ctx.skynet,
             MessageBuilder::new()
@@ -479,7 +457,7 @@ pub(crate) mod guild_rank {
     }
 
     async fn cannot_acquire_user(ctx: &Context, payload: &InteractionCreate) {
-        let guild_locale = get_guild_locale(&payload.interaction.guild_locale);
+        let guild_locale = get_guild_locale(&payload.interaction);
         let _ = payload.interaction.reply(
             &ctx.skynet,
             MessageBuilder::new()
@@ -18,6 +18,8 @@ use signal_hook::iterator::SignalsInfo;
 use tokio::sync::RwLock;
 use client::manager::http::HttpManager;
 use client::manager::shard::ShardManager;
+#[cfg(unix)]
+use client::supervisor;
 use config::Config;
 
 #[cfg(unix)]
@@ -31,7 +33,7 @@ pub(crate) fn spawn_manager(
 
     let mut signals = SignalsInfo::<SignalOnly>::new(&sigs).expect("Failed to register signals");
 
-    tokio::spawn(async move {
+    supervisor::spawn("signals", async move {
         for info in &mut signals {
             match info {
                 SIGINT => {
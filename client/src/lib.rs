@@ -18,11 +18,13 @@ use crate::models::events::{GuildCreate, GuildDelete, GuildMemberAdd, GuildMembe
 use crate::models::guild::GuildMember;
 use crate::models::interaction::Interaction;
 use crate::models::message::Message;
+use crate::supervisor::RestartPolicy;
 use crate::typemap::{Type, TypeMap};
 
 pub mod manager;
 pub mod models;
 mod utils;
+pub mod supervisor;
 pub mod constants;
 pub mod typemap;
 
@@ -187,7 +189,8 @@ impl Client {
 
                     match shard {
                         None => (true, false),
-                        Some(shard) => (*shard.run.lock().await, *shard.connected.lock().await)
+                        // a shard whose reader stopped, e.g. after a panic, has to be reconnected
+                        Some(shard) => (*shard.run.lock().await, *shard.connected.lock().await && !shard.threads.received.is_finished())
                     }
                 };
 
@@ -227,7 +230,7 @@ impl Client {
                     // init shard
                     let ShardChannels {
                         shard,
-                        mut received
+                        received
                     } = Shard::connect(
                         i,
                         client.shards_count,
@@ -242,38 +245,24 @@ impl Client {
                     }
 
                     let client_arc = arc_client.clone();
-                    tokio::spawn(async move {
-                        // loop {
-                        //     // we will manage each event received
-                        //
-                        //     // get the message from the channel
-                        //     let data: Value = match received.next().await {
-                        //         Some(d) => d,
-                        //         None =>  break
-                        //     };
-                        //
-                        //     // match operators to get the event type
-                        //     let op = match data["op"].as_u64() {
-                        //         Some(d) => d,
-                        //         None => continue
-                        //     };
-                        //
-                        //     {
-                        //         let mut client = client_arc.lock().await;
-                        //         client.event_triggered(op.into(), data, i);
-                        //     }
-                        // }
-
-                        while let Some(data) = received.next().await {
-                            // match operators to get the event type
-                            let op = match data["op"].as_u64() {
-                                Some(d) => d,
-                                None => continue
-                            };
-
-                            {
-                                let mut client = client_arc.lock().await;
-                                client.event_triggered(op.into(), data, i);
+                    let received = Arc::new(tokio::sync::Mutex::new(received));
+                    supervisor::spawn_loop(format!("shard::{i}::events"), RestartPolicy::default(), move || {
+                        let client_arc = client_arc.clone();
+                        let received = received.clone();
+                        async move {
+                            let mut received = received.lock().await;
+
+                            while let Some(data) = received.next().await {
+                                // match operators to get the event type
+                                let op = match data["op"].as_u64() {
+                                    Some(d) => d,
+                                    None => continue
+                                };
+
+                                {
+                                    let mut client = client_arc.lock().await;
+                                    client.event_triggered(op.into(), data, i);
+                                }
                             }
                         }
                     });
@@ -301,7 +290,7 @@ impl Client {
                     );
 
                     let events_clone = events.clone();
-                    tokio::spawn(async move {
+                    supervisor::spawn("event::start", async move {
                         let _ = events_clone.start(ctx).await;
                     });
                 }
@@ -366,7 +355,7 @@ impl Client {
                     );
 
                     let events_clone = events.clone();
-                    tokio::spawn(async move {
+                    supervisor::spawn("event::ready", async move {
                         let _ = events_clone.ready(ctx, ready).await;
                     });
                 }
@@ -392,7 +381,7 @@ impl Client {
                     );
 
                     let events_clone = events.clone();
-                    tokio::spawn(async move {
+                    supervisor::spawn("event::guild_create", async move {
                         let _ = events_clone.guild_create(ctx, guild_create).await;
                     });
                 }
@@ -418,7 +407,7 @@ impl Client {
                     );
 
                     let events_clone = events.clone();
-                    tokio::spawn(async move {
+                    supervisor::spawn("event::guild_delete", async move {
                         let _ = events_clone.guild_delete(ctx, guild_delete).await;
                     });
                 }
@@ -449,7 +438,7 @@ impl Client {
                     );
 
                     let events_clone = events.clone();
-                    tokio::spawn(async move {
+                    supervisor::spawn("event::message_create", async move {
                         let _ = events_clone.message_create(ctx, message_create).await;
                     });
                 }
@@ -475,7 +464,7 @@ impl Client {
                     );
 
                     let events_clone = events.clone();
-                    tokio::spawn(async move {
+                    supervisor::spawn("event::message_delete", async move {
                         let _ = events_clone.message_delete(ctx, message_delete).await;
                     });
                 }
@@ -506,7 +495,7 @@ impl Client {
                     );
 
                     let events_clone = events.clone();
-                    tokio::spawn(async move {
+                    supervisor::spawn("event::guild_member_add", async move {
                         let _ = events_clone.guild_member_add(ctx, guild_member_add).await;
                     });
                 }
@@ -537,7 +526,7 @@ impl Client {
                     );
 
                     let events_clone = events.clone();
-                    tokio::spawn(async move {
+                    supervisor::spawn("event::guild_member_update", async move {
                         let _ = events_clone.guild_member_update(ctx, guild_member_update).await;
                     });
                 }
@@ -564,7 +553,7 @@ impl Client {
                     );
 
                     let events_clone = events.clone();
-                    tokio::spawn(async move {
+                    supervisor::spawn("event::interaction_create", async move {
                         let _ = events_clone.interaction_create(ctx, interaction_create).await;
                     });
                 }
@@ -16,6 +16,7 @@ use error::{ Result, ApiError, Error };
 use crate::models::user::{Application, ClientUser, User, UserId};
 #[allow(unused_imports)] // Used in a macro
 use crate::constants::API_URL;
+use crate::supervisor::{self, RestartPolicy};
 use crate::models::channel::{Channel, ChannelId, Dm};
 use crate::models::guild::{Guild, GuildId, GuildMember, Role};
 use crate::models::interaction::{ApplicationCommand, InteractionCallbackType};
@@ -138,12 +139,12 @@ impl Request {
 }
 
 pub struct HttpManager {
-    pub(crate) configuration: HttpConfiguration,
+    pub(crate) configuration: Arc<RwLock<HttpConfiguration>>,
     pub(crate) rest: reqwest::Client,
     pub        client: Arc<Http>,
     pub        run: Arc<Mutex<bool>>,
                queue: Arc<RwLock<UnboundedReceiver<Request>>>,
-               tasks: Arc<Mutex<Vec<JoinHandle<()>>>>
+               tasks: Arc<Mutex<Vec<JoinHandle<Option<()>>>>>
 }
 
 impl HttpManager {
@@ -153,7 +154,7 @@ impl HttpManager {
         let queue = Arc::new(RwLock::new(rx));
 
         Self {
-            configuration,
+            configuration: Arc::new(RwLock::new(configuration)),
             client: Arc::new(Http { queue: Arc::new(RwLock::new(tx)) }),
             rest: rest_client,
             run: Arc::new(Mutex::new(true)),
@@ -162,6 +163,11 @@ impl HttpManager {
         }
     }
 
+    /// Replace the configuration used for the next requests
+    pub async fn set_configuration(&self, configuration: HttpConfiguration) {
+        *self.configuration.write().await = configuration;
+    }
+
     async fn send_request(configuration: HttpConfiguration, request: &Request, rest: &reqwest::Client) -> Result<Value> {
         let mut retries = 0;
 
@@ -239,68 +245,75 @@ impl HttpManager {
         let rest = self.rest.clone();
         let run = self.run.clone();
         let tasks = self.tasks.clone();
-        tokio::spawn(async move {
-            loop {
-                if !*run.lock().await {
-                    break;
-                }
-
-                let mut queue = queue.write().await;
-
-                let request = match queue.next().await {
-                    Some(request) => request,
-                    None => continue
-                };
-
-                // send request and send the response back to the requester
-                let configuration = configuration.clone();
-                let rest = rest.clone();
-                let tasks = tasks.clone();
-                // spawn a new task to send the request
-                let task = tokio::spawn(async move {
-                    let sender = request.sender.lock().await;
-
-                    if sender.is_closed() { return; }
-
-                    // let mut built_request = rest.request(request.method.clone(), request.url.clone())
-                    //     .timeout(configuration.connect_timeout);
-                    //     //.body(request.body.clone().unwrap_or_default());
-                    //
-                    // // set the headers if there are any
-                    // if let Some(header_map) = request.headers {
-                    //     for (key, value) in header_map.iter() {
-                    //         built_request = built_request.header(key, value);
-                    //     }
-                    // }
-                    //
-                    // if let Some(multipart) = request.multipart {
-                    //     let mut form = multipart::Form::new();
-                    //
-                    //     form = form.text("payload_json", request.body.unwrap_or_default());
-                    //
-                    //     for file in multipart.iter() {
-                    //         form = form.part(file.filename.clone(), multipart::Part::bytes(file.bytes.to_vec()));
-                    //     }
-                    //
-                    //     built_request = built_request.multipart(form);
-                    // } else if request.body.is_some() {
-                    //     // set the content type to json if there is a body
-                    //     built_request = built_request.header("Content-Type", "application/json").body(request.body.unwrap_or_default());
-                    // };
-
-
-                    let res = Self::send_request(configuration.clone(), &request, &rest).await;
-
-                    if let Err(e) = sender.unbounded_send(res) {
-                        error!("Failed to send response back to requester: {}", e);
+        supervisor::spawn_loop("http::queue", RestartPolicy::default(), move || {
+            let queue = queue.clone();
+            let configuration = configuration.clone();
+            let rest = rest.clone();
+            let run = run.clone();
+            let tasks = tasks.clone();
+            async move {
+                loop {
+                    if !*run.lock().await {
+                        break;
                     }
-                });
-                tasks.lock().await.push(task);
 
-                // automatically remove all finished tasks
-                {
-                    let mut tasks = tasks.lock().await;
-                    tasks.retain(|task| !task.is_finished());
+                    let mut queue = queue.write().await;
+
+                    let request = match queue.next().await {
+                        Some(request) => request,
+                        None => continue
+                    };
+
+                    // send request and send the response back to the requester
+                    let configuration = configuration.read().await.clone();
+                    let rest = rest.clone();
+                    let tasks = tasks.clone();
+                    // spawn a new task to send the request
+                    let task = supervisor::spawn("http::request", async move {
+                        let sender = request.sender.lock().await;
+
+                        if sender.is_closed() { return; }
+
+                        // let mut built_request = rest.request(request.method.clone(), request.url.clone())
+                        //     .timeout(configuration.connect_timeout);
+                        //     //.body(request.body.clone().unwrap_or_default());
+                        //
+                        // // set the headers if there are any
+                        // if let Some(header_map) = request.headers {
+                        //     for (key, value) in header_map.iter() {
+                        //         built_request = built_request.header(key, value);
+                        //     }
+                        // }
+                        //
+                        // if let Some(multipart) = request.multipart {
+                        //     let mut form = multipart::Form::new();
+                        //
+                        //     form = form.text("payload_json", request.body.unwrap_or_default());
+                        //
+                        //     for file in multipart.iter() {
+                        //         form = form.part(file.filename.clone(), multipart::Part::bytes(file.bytes.to_vec()));
+                        //     }
+                        //
+                        //     built_request = built_request.multipart(form);
+                        // } else if request.body.is_some() {
+                        //     // set the content type to json if there is a body
+                        //     built_request = built_request.header("Content-Type", "application/json").body(request.body.unwrap_or_default());
+                        // };
+
+
+                        let res = Self::send_request(configuration.clone(), &request, &rest).await;
+
+                        if let Err(e) = sender.unbounded_send(res) {
+                            error!("Failed to send response back to requester: {}", e);
+                        }
+                    });
+                    tasks.lock().await.push(task);
+
+                    // automatically remove all finished tasks
+                    {
+                        let mut tasks = tasks.lock().await;
+                        tasks.retain(|task| !task.is_finished());
+                    }
                 }
             }
         });
@@ -1032,7 +1045,7 @@ impl Http {
 
         let request = Request {
             method: reqwest::Method::GET,
-            url: format!("{API_URL}/applications/{}/guilds/{}/commands", application_id.0, guild_id.0),
+            url: format!("{API_URL}/applications/{}/guilds/{}/commands?with_localizations=true", application_id.0, guild_id.0),
             body: None,
             sender: Arc::new(Mutex::new(tx)),
             headers: None,
@@ -1100,7 +1113,7 @@ impl Http {
 
         let request = Request {
             method: reqwest::Method::GET,
-            url: format!("{API_URL}/applications/{}/commands", application_id.0),
+            url: format!("{API_URL}/applications/{}/commands?with_localizations=true", application_id.0),
             body: None,
             sender: Arc::new(Mutex::new(tx)),
             headers: None,
@@ -1155,4 +1168,53 @@ impl Http {
             Err(e) => Err(e)
         }
     }
-}
\ No newline at end of file
+
+    /// Replace all the global commands of the application, the commands with the same name keep their id.
+    ///
+    /// Reference:
+    /// - [Bulk Overwrite Global Application Commands](https://discord.com/developers/docs/interactions/application-commands#bulk-overwrite-global-application-commands)
+    pub async fn bulk_overwrite_global_commands(&self, application_id: &Snowflake, commands: &[ApplicationCommand]) -> Result<ApiResult<Vec<ApplicationCommand>>> {
+        self.bulk_overwrite_commands(format!("{API_URL}/applications/{}/commands", application_id.0), commands).await
+    }
+
+    /// Replace all the commands of the application in a guild, the commands with the same name keep their id.
+    ///
+    /// Reference:
+    /// - [Bulk Overwrite Guild Application Commands](https://discord.com/developers/docs/interactions/application-commands#bulk-overwrite-guild-application-commands)
+    pub async fn bulk_overwrite_guild_commands(&self, application_id: &Snowflake, guild_id: &GuildId, commands: &[ApplicationCommand]) -> Result<ApiResult<Vec<ApplicationCommand>>> {
+        self.bulk_overwrite_commands(format!("{API_URL}/applications/{}/guilds/{}/commands", application_id.0, guild_id.0), commands).await
+    }
+
+    async fn bulk_overwrite_commands(&self, url: String, commands: &[ApplicationCommand]) -> Result<ApiResult<Vec<ApplicationCommand>>> {
+        let (tx, rx) = futures_channel::mpsc::unbounded();
+
+        let request = Request {
+            method: reqwest::Method::PUT,
+            url,
+            body: Some(Value::Array(commands.iter().map(ApplicationCommand::to_json).collect()).to_string()),
+            sender: Arc::new(Mutex::new(tx)),
+            headers: None,
+            multipart: None
+        };
+
+        let res = self.send_raw(request, rx).await;
+
+        match res {
+            Ok(Value::Array(values)) => {
+                let mut commands = Vec::new();
+
+                for command in values {
+                    match convert_value(command, None) {
+                        Ok(Ok(command)) => commands.push(command),
+                        Ok(Err(e)) => return Ok(Err(e)),
+                        Err(e) => error!("Failed to convert command: {e:#?}")
+                    }
+                }
+
+                Ok(Ok(commands))
+            },
+            Ok(value) => convert_value(value, None),
+            Err(e) => Err(e)
+        }
+    }
+}
@@ -18,6 +18,7 @@ use tokio_tungstenite::tungstenite::protocol::CloseFrame;
 use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
 #[allow(unused_imports)] // They are used in the 'json!' macro
 use crate::constants::{ GATEWAY_URL, BROWSER, DEVICE };
+use crate::supervisor::{self, RestartPolicy};
 use error::{ Result, Error, GatewayError };
 use crate::models::presence::Presence;
 
@@ -73,8 +74,8 @@ pub(crate) struct ShardChannels {
 #[derive(Debug)]
 pub(crate) struct ShardThreads {
     pub(crate) heartbeat: JoinHandle<()>,
-    pub(crate) sending: JoinHandle<()>,
-    pub(crate) received: JoinHandle<()>
+    pub(crate) sending: JoinHandle<Option<()>>,
+    pub(crate) received: JoinHandle<Option<()>>
 }
 
 impl Shard {
@@ -142,40 +143,45 @@ impl Shard {
         let last_heartbeat_clone = last_heartbeat.clone();
         let run_shard_clone = run_shard.clone();
         let sending_tx_clone = sending_tx.clone();
-        let heartbeat_thread = tokio::spawn(async move {
-            'heartbeat: loop {
-                // if we want to stop the heartbeat system, we simply check this
-                if !*run_shard_clone.lock().await {
-                    break 'heartbeat;
-                }
+        let heartbeat_thread = supervisor::spawn_loop(format!("shard::{id}::heartbeat"), RestartPolicy::default(), move || {
+            let last_heartbeat_clone = last_heartbeat_clone.clone();
+            let run_shard_clone = run_shard_clone.clone();
+            let sending_tx_clone = sending_tx_clone.clone();
+            async move {
+                'heartbeat: loop {
+                    // if we want to stop the heartbeat system, we simply check this
+                    if !*run_shard_clone.lock().await {
+                        break 'heartbeat;
+                    }
 
-                let mut last_heartbeat = last_heartbeat_clone.lock().await;
-                // send heartbeat
-                let msg = json!({
-                    "op": 1,
-                    "d": last_heartbeat.elapsed().as_millis()
-                });
-                // update last heartbeat
-                *last_heartbeat = std::time::Instant::now();
-
-                // free the lock
-                drop(last_heartbeat);
-
-                // request a message to be sent
-                if let Err(e) = sending_tx_clone.unbounded_send(Message::Text(msg.to_string())) {
-                    error!(target: "HeartbeatShard", "Error while sending heartbeat: {:?}", e);
-                    continue
-                }
+                    let mut last_heartbeat = last_heartbeat_clone.lock().await;
+                    // send heartbeat
+                    let msg = json!({
+                        "op": 1,
+                        "d": last_heartbeat.elapsed().as_millis()
+                    });
+                    // update last heartbeat
+                    *last_heartbeat = std::time::Instant::now();
+
+                    // free the lock
+                    drop(last_heartbeat);
+
+                    // request a message to be sent
+                    if let Err(e) = sending_tx_clone.unbounded_send(Message::Text(msg.to_string())) {
+                        error!(target: "HeartbeatShard", "Error while sending heartbeat: {:?}", e);
+                        continue
+                    }
 
-                // wait for heartbeat interval
-                sleep(Duration::from_millis(heartbeat_interval)).await;
+                    // wait for heartbeat interval
+                    sleep(Duration::from_millis(heartbeat_interval)).await;
+                }
             }
         });
 
         // Spawn thread to send messages
         let run_shard_clone = run_shard.clone();
         let is_shard_connected_clone = is_shard_connected.clone();
-        let sending_thread = tokio::spawn(async move {
+        let sending_thread = supervisor::spawn(format!("shard::{id}::sending"), async move {
             'sending: loop {
                 // if we want to stop the heartbeat system, we simply check this
                 if !*run_shard_clone.lock().await {
@@ -211,7 +217,7 @@ impl Shard {
         let run_shard_clone = run_shard.clone();
         let is_shard_connected_clone = is_shard_connected.clone();
         let ping_clone = ping.clone();
-        let received_thread = tokio::spawn(async move {
+        let received_thread = supervisor::spawn(format!("shard::{id}::received"), async move {
             'receive: loop {
                 // if we want to stop the heartbeat system, we simply check this
                 if !*run_shard_clone.lock().await {
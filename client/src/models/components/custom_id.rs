@@ -0,0 +1,215 @@
+//! Typed custom_ids for the buttons, select menus and modals
+//!
+//! A component declares its data as a struct deriving `Serialize` and `Deserialize`, and implements [`CustomId`]:
+//!
+//! ```ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct CaptchaTry { code: String, instance: Uuid }
+//!
+//! impl CustomId for CaptchaTry {
+//!     const NAME: &'static str = "CAPTCHA_TRY";
+//!     const SIGNED: bool = true;
+//! }
+//!
+//! let button = Button::new(CaptchaTry { code, instance }.encode()?);
+//! ```
+//!
+//! The custom_id is `NAME:VERSION:FIELDS[:SIGNATURE]`: the fields are url-encoded, so they can contain any character,
+//! and the signature is a truncated HMAC-SHA256 of the rest, with the key given to [`set_signing_key`].
+//! The custom_ids of the older versions are refused, like the ones without the `:` that were written as `NAME&key=value`,
+//! which are still read for the unsigned components.
+
+use std::sync::RwLock;
+use hmac::{Hmac, Mac};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sha2::Sha256;
+use error::{Error, ModelError, Result};
+
+/// The maximum length of a custom_id accepted by Discord
+pub const MAX_LENGTH: usize = 100;
+/// The number of bytes of the HMAC kept in the signature
+const SIGNATURE_BYTES: usize = 8;
+const SEPARATOR: char = ':';
+/// The separator of the custom_ids written before the versions
+const LEGACY_SEPARATOR: char = '&';
+
+static SIGNING_KEY: RwLock<Option<Vec<u8>>> = RwLock::new(None);
+
+/// The data of a component, stored in its custom_id
+pub trait CustomId: Serialize + DeserializeOwned {
+    /// The name routing the interaction to its handler, it cannot contain `:` nor `&`
+    const NAME: &'static str;
+    /// To increase when the fields change, the custom_ids of the other versions are refused
+    const VERSION: u8 = 1;
+    /// Sign the custom_id, so a forged or edited one is refused
+    const SIGNED: bool = false;
+
+    fn encode(&self) -> Result<String> {
+        encode(self)
+    }
+
+    fn decode(custom_id: &str) -> Result<Self> {
+        decode(custom_id)
+    }
+}
+
+/// Set the key of the signatures, the signed custom_ids cannot be encoded nor decoded without it
+pub fn set_signing_key(key: impl Into<Vec<u8>>) {
+    if let Ok(mut signing_key) = SIGNING_KEY.write() {
+        *signing_key = Some(key.into());
+    }
+}
+
+/// Get the name of a custom_id, to route the interaction to its handler
+pub fn name(custom_id: &str) -> &str {
+    custom_id.split([SEPARATOR, LEGACY_SEPARATOR]).next().unwrap_or_default()
+}
+
+pub fn encode<T: CustomId>(data: &T) -> Result<String> {
+    debug_assert!(!T::NAME.contains([SEPARATOR, LEGACY_SEPARATOR]), "invalid custom_id name {}", T::NAME);
+
+    let fields = serde_urlencoded::to_string(data)
+        .map_err(|e| invalid(format!("Cannot encode the custom_id {}: {e}", T::NAME)))?;
+    let mut custom_id = format!("{}{SEPARATOR}{}{SEPARATOR}{fields}", T::NAME, T::VERSION);
+
+    if T::SIGNED {
+        let signature = sign(&custom_id)?;
+        custom_id.push(SEPARATOR);
+        custom_id.push_str(&hex(&signature[..SIGNATURE_BYTES]));
+    }
+
+    if custom_id.chars().count() > MAX_LENGTH {
+        return Err(Error::Model(ModelError::CustomIdTooLong(custom_id)));
+    }
+
+    Ok(custom_id)
+}
+
+pub fn decode<T: CustomId>(custom_id: &str) -> Result<T> {
+    if name(custom_id) != T::NAME {
+        return Err(invalid(format!("The custom_id {custom_id:?} isn't a {}", T::NAME)));
+    }
+
+    let fields = match custom_id.split_once(SEPARATOR) {
+        Some((_, rest)) => {
+            let (version, rest) = rest.split_once(SEPARATOR)
+                .ok_or_else(|| invalid(format!("The custom_id {custom_id:?} has no version")))?;
+            if version != T::VERSION.to_string() {
+                return Err(Error::Model(ModelError::OutdatedCustomId(format!("{custom_id:?} is not at the version {}", T::VERSION))));
+            }
+
+            if T::SIGNED {
+                let (fields, signature) = rest.split_once(SEPARATOR)
+                    .ok_or_else(|| Error::Model(ModelError::InvalidSignature(format!("The custom_id {custom_id:?} isn't signed"))))?;
+                verify(&custom_id[..custom_id.len() - signature.len() - 1], signature)?;
+                fields
+            } else {
+                rest
+            }
+        },
+        None if T::SIGNED => return Err(Error::Model(ModelError::InvalidSignature(format!("The custom_id {custom_id:?} isn't signed")))),
+        None => custom_id.split_once(LEGACY_SEPARATOR).map(|(_, fields)| fields).unwrap_or_default()
+    };
+
+    serde_urlencoded::from_str(fields)
+        .map_err(|e| invalid(format!("Cannot decode the custom_id {custom_id:?}: {e}")))
+}
+
+fn mac() -> Result<Hmac<Sha256>> {
+    let key = SIGNING_KEY.read().ok().and_then(|key| key.clone())
+        .ok_or_else(|| Error::Model(ModelError::InvalidSignature("No signing key was set".into())))?;
+
+    Hmac::<Sha256>::new_from_slice(&key)
+        .map_err(|e| Error::Model(ModelError::InvalidSignature(e.to_string())))
+}
+
+fn sign(content: &str) -> Result<Vec<u8>> {
+    let mut mac = mac()?;
+    mac.update(content.as_bytes());
+    Ok(mac.finalize().into_bytes().to_vec())
+}
+
+fn verify(content: &str, signature: &str) -> Result<()> {
+    let refused = || Error::Model(ModelError::InvalidSignature(format!("The signature of {content:?} doesn't match")));
+
+    let signature = unhex(signature).filter(|s| s.len() == SIGNATURE_BYTES).ok_or_else(refused)?;
+    let mut mac = mac()?;
+    mac.update(content.as_bytes());
+    // compared in constant time
+    mac.verify_truncated_left(&signature).map_err(|_| refused())
+}
+
+fn invalid(reason: String) -> Error {
+    Error::Model(ModelError::InvalidCustomId(reason))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn unhex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) || !hex.is_ascii() { return None; }
+
+    (0..hex.len()).step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use serde::{Deserialize, Serialize};
+    use error::{Error, ModelError};
+    use crate::models::components::custom_id::{CustomId, name, set_signing_key};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Quiz {
+        question: String,
+        page: u32
+    }
+
+    impl CustomId for Quiz {
+        const NAME: &'static str = "QUIZ";
+        const VERSION: u8 = 2;
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Captcha {
+        code: String
+    }
+
+    impl CustomId for Captcha {
+        const NAME: &'static str = "CAPTCHA";
+        const SIGNED: bool = true;
+    }
+
+    #[test]
+    fn values_are_escaped() {
+        let quiz = Quiz { question: "a:b&c=d %".into(), page: 3 };
+        let custom_id = quiz.encode().unwrap();
+
+        assert_eq!(name(&custom_id), "QUIZ");
+        assert_eq!(Quiz::decode(&custom_id).unwrap(), quiz);
+        // written before the versions
+        assert_eq!(Quiz::decode("QUIZ&question=why&page=1").unwrap(), Quiz { question: "why".into(), page: 1 });
+        assert!(matches!(Quiz::decode("QUIZ:1:question=why&page=1"), Err(Error::Model(ModelError::OutdatedCustomId(_)))));
+        assert!(matches!(Captcha::decode(&custom_id), Err(Error::Model(ModelError::InvalidCustomId(_)))));
+    }
+
+    #[test]
+    fn signatures_are_checked() {
+        set_signing_key("secret");
+        let custom_id = Captcha { code: "ab12".into() }.encode().unwrap();
+
+        assert_eq!(Captcha::decode(&custom_id).unwrap().code, "ab12");
+        let forged = custom_id.replace("ab12", "zz99");
+        assert!(matches!(Captcha::decode(&forged), Err(Error::Model(ModelError::InvalidSignature(_)))));
+        assert!(matches!(Captcha::decode("CAPTCHA&code=ab12"), Err(Error::Model(ModelError::InvalidSignature(_)))));
+    }
+
+    #[test]
+    fn the_length_is_limited() {
+        let quiz = Quiz { question: "?".repeat(100), page: 1 };
+        assert!(matches!(quiz.encode(), Err(Error::Model(ModelError::CustomIdTooLong(_)))));
+    }
+}
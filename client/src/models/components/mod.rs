@@ -1,6 +1,7 @@
 pub mod embed;
 pub mod sticker;
 pub mod message_components;
+pub mod custom_id;
 
 use serde::{ Serialize, Deserialize };
 use serde_json::{json, Value};
@@ -328,6 +328,96 @@ impl Default for InteractionDataOptionValue {
     }
 }
 
+/// Typed access to the options of a command or a subcommand
+#[derive(Debug, Clone, Copy)]
+pub struct CommandOptions<'a>(&'a [InteractionDataOption]);
+
+impl<'a> CommandOptions<'a> {
+    pub fn new(options: Option<&'a Vec<InteractionDataOption>>) -> Self {
+        Self(options.map(Vec::as_slice).unwrap_or_default())
+    }
+
+    /// The options given to the command of an interaction
+    pub fn of(interaction: &'a Interaction) -> Self {
+        Self::new(interaction.data.as_ref().and_then(|d| d.options.as_ref()))
+    }
+
+    /// The subcommand (or subcommand group) used, with its own options
+    pub fn subcommand(&self) -> Option<(&'a str, CommandOptions<'a>)> {
+        self.0.iter()
+            .find(|o| matches!(o.option_type, ApplicationCommandOptionType::SubCommand | ApplicationCommandOptionType::SubCommandGroup))
+            .map(|o| (o.name.as_str(), CommandOptions::new(o.options.as_ref())))
+    }
+
+    /// Get the value of an option, None if it is missing or of another type
+    pub fn get<T: FromOptionValue>(&self, name: &str) -> Option<T> {
+        self.0.iter()
+            .find(|o| o.name == name)
+            .and_then(|o| o.value.as_ref())
+            .and_then(T::from_option_value)
+    }
+}
+
+/// A type that can be read from the value of an option
+pub trait FromOptionValue: Sized {
+    fn from_option_value(value: &InteractionDataOptionValue) -> Option<Self>;
+}
+
+impl FromOptionValue for String {
+    fn from_option_value(value: &InteractionDataOptionValue) -> Option<Self> {
+        match value {
+            InteractionDataOptionValue::String(s) => Some(s.clone()),
+            _ => None
+        }
+    }
+}
+
+impl FromOptionValue for i64 {
+    fn from_option_value(value: &InteractionDataOptionValue) -> Option<Self> {
+        match value {
+            InteractionDataOptionValue::Integer(i) => Some(*i),
+            _ => None
+        }
+    }
+}
+
+impl FromOptionValue for f64 {
+    fn from_option_value(value: &InteractionDataOptionValue) -> Option<Self> {
+        match value {
+            InteractionDataOptionValue::Double(f) => Some(*f),
+            InteractionDataOptionValue::Integer(i) => Some(*i as f64),
+            _ => None
+        }
+    }
+}
+
+impl FromOptionValue for bool {
+    fn from_option_value(value: &InteractionDataOptionValue) -> Option<Self> {
+        match value {
+            InteractionDataOptionValue::Boolean(b) => Some(*b),
+            _ => None
+        }
+    }
+}
+
+impl FromOptionValue for Snowflake {
+    fn from_option_value(value: &InteractionDataOptionValue) -> Option<Self> {
+        String::from_option_value(value).map(Snowflake)
+    }
+}
+
+impl FromOptionValue for UserId {
+    fn from_option_value(value: &InteractionDataOptionValue) -> Option<Self> {
+        Snowflake::from_option_value(value).map(UserId)
+    }
+}
+
+impl FromOptionValue for ChannelId {
+    fn from_option_value(value: &InteractionDataOptionValue) -> Option<Self> {
+        Snowflake::from_option_value(value).map(ChannelId)
+    }
+}
+
 impl Display for InteractionDataOptionValue {
     fn fmt(&self, f1: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let str = match self {
@@ -498,7 +588,7 @@ impl ApplicationCommand {
             description_localizations: None,
             options: None,
             default_member_permissions: None,
-            dm_permission: false,
+            dm_permission: true,
             nsfw: false,
             version: Snowflake(String::new()),
         }
@@ -558,17 +648,58 @@ impl ApplicationCommand {
 
     /// Turn the command into a JSON value to be send as a POST request to Discord
     pub fn to_json(&self) -> Value {
-        json!({
+        let mut json = json!({
             "name": self.name,
+            "name_localizations": self.name_localizations,
             "description": self.description,
+            "description_localizations": self.description_localizations,
             "options": self.options.as_ref().map(|options| {
                 options.iter().map(|option| option.to_json()).collect::<Vec<Value>>()
             }),
-            "default_permission": self.default_member_permissions,
+            "default_member_permissions": self.default_member_permissions,
             "type": self.command_type.to_number(),
             "version": self.version,
-        })
+        });
+
+        // only the global commands can be used in DMs
+        if self.guild_id.is_none() {
+            json["dm_permission"] = Value::Bool(self.dm_permission);
+        }
+
+        json
+    }
+
+    /// Check if two commands have the same definition: name, description, options, localizations and permissions
+    ///
+    /// The ids and versions given by Discord are ignored, like the empty localizations and the unset fields
+    pub fn same_definition(&self, other: &Self) -> bool {
+        definition(self.to_json()) == definition(other.to_json())
+    }
+}
+
+/// Remove from a command what Discord doesn't compare
+fn definition(mut json: Value) -> Value {
+    fn normalize(value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                map.retain(|key, value| match value {
+                    Value::Null => false,
+                    Value::Bool(false) => key != "required",
+                    Value::Object(o) => !(o.is_empty() && key.ends_with("_localizations")),
+                    _ => true
+                });
+                map.values_mut().for_each(normalize);
+            },
+            Value::Array(values) => values.iter_mut().for_each(normalize),
+            _ => ()
+        }
     }
+
+    if let Some(map) = json.as_object_mut() {
+        map.remove("version");
+    }
+    normalize(&mut json);
+    json
 }
 
 /// Represents a command option
@@ -672,7 +803,9 @@ impl ApplicationCommandOption {
         json!({
             "type": self.option_type.to_json(),
             "name": self.name,
+            "name_localizations": self.name_localizations,
             "description": self.description,
+            "description_localizations": self.description_localizations,
             "required": self.required,
             "choices": self.choices.as_ref().map(|choices| {
                 choices.iter().map(|choice| choice.to_json()).collect::<Vec<Value>>()
@@ -854,4 +987,49 @@ impl CommandChoiceValue {
             Self::Double(d) => Value::Number(Number::from_f64(*d).unwrap()),
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+    use crate::models::interaction::{ApplicationCommand, ApplicationCommandOption, ApplicationCommandOptionType, ApplicationCommandType, CommandOptions, InteractionDataOption, InteractionDataOptionValue};
+    use crate::models::Snowflake;
+    use crate::models::user::UserId;
+
+    fn option(name: &str, option_type: ApplicationCommandOptionType, value: Option<InteractionDataOptionValue>, options: Option<Vec<InteractionDataOption>>) -> InteractionDataOption {
+        InteractionDataOption { name: name.to_string(), option_type, value, options, focused: None }
+    }
+
+    #[test]
+    fn definitions_ignore_discord_fields() {
+        let local = ApplicationCommand::new_global("data", "Manage your data", ApplicationCommandType::ChatInput)
+            .add_option(ApplicationCommandOption::new(ApplicationCommandOptionType::SubCommand, "export", "Export", false));
+
+        let mut remote = local.clone();
+        remote.id = Snowflake("1".into());
+        remote.version = Snowflake("2".into());
+        remote.name_localizations = Some(HashMap::new());
+        assert!(local.same_definition(&remote));
+
+        remote.description_localizations = Some(HashMap::from([("fr".to_string(), "Gérez vos données".to_string())]));
+        assert!(!local.same_definition(&remote));
+        assert!(!local.same_definition(&local.clone().set_dm_permission(false)));
+    }
+
+    #[test]
+    fn options_are_typed() {
+        let options = vec![
+            option("donate", ApplicationCommandOptionType::SubCommand, None, Some(vec![
+                option("user", ApplicationCommandOptionType::User, Some(InteractionDataOptionValue::String("42".into())), None),
+                option("number", ApplicationCommandOptionType::Number, Some(InteractionDataOptionValue::Integer(3)), None)
+            ]))
+        ];
+
+        let (name, options) = CommandOptions::new(Some(&options)).subcommand().unwrap();
+        assert_eq!(name, "donate");
+        assert_eq!(options.get::<UserId>("user").map(|u| u.to_string()), Some("42".to_string()));
+        assert_eq!(options.get::<f64>("number"), Some(3.0));
+        assert_eq!(options.get::<bool>("number"), None);
+        assert_eq!(options.get::<String>("missing"), None);
+    }
+}
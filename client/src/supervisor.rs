@@ -0,0 +1,372 @@
+//! Supervise the tasks spawned by the client and the app
+//!
+//! Each task is named, its panics are captured with their backtrace and sent to the panic handler,
+//! and the long-lived loops are restarted with an exponential backoff.
+//!
+//! ```ignore
+//! supervisor::spawn("event::ready", async move { ... });
+//! supervisor::spawn_loop("nugget_updater", RestartPolicy::default(), move || {
+//!     let database = database.clone();
+//!     async move { ... }
+//! });
+//! ```
+
+use std::any::Any;
+use std::backtrace::Backtrace;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::future::Future;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, Once, OnceLock};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Utc};
+use log::{error, warn};
+use serde::Serialize;
+use tokio::task::JoinHandle;
+use crate::utils::spawn_named;
+
+/// Called with each panic captured in a supervised task
+pub type PanicHandler = Arc<dyn Fn(TaskPanic) + Send + Sync>;
+
+/// The state of a supervised task
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum TaskState {
+    Running,
+    /// The task panicked and waits before being restarted
+    Restarting,
+    Finished,
+    /// A one-shot task that panicked, only its panics are counted since it's never restarted
+    OneShot
+}
+
+/// The health of a supervised task
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskHealth {
+    pub name: String,
+    pub state: TaskState,
+    /// The last time the task was started
+    pub started: DateTime<Utc>,
+    pub restarts: u32,
+    pub panics: u32,
+    pub last_panic: Option<String>
+}
+
+impl TaskHealth {
+    /// Return false while a long-lived task is waiting to be restarted after a panic
+    ///
+    /// The one-shot tasks are always healthy, their panics are reported but nothing is left running
+    pub fn is_healthy(&self) -> bool {
+        self.state != TaskState::Restarting
+    }
+}
+
+/// A panic captured in a supervised task
+#[derive(Debug, Clone)]
+pub struct TaskPanic {
+    pub task: String,
+    pub message: String,
+    /// `file:line:column`
+    pub location: Option<String>,
+    pub backtrace: String
+}
+
+/// How a long-lived loop is restarted after a panic: `base_delay * 2^attempt`, capped to `max_delay`
+#[derive(Debug, Clone)]
+pub struct RestartPolicy {
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    /// The attempts are reset once the task ran this long without panicking
+    pub stable_after: Duration
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(300),
+            stable_after: Duration::from_secs(60)
+        }
+    }
+}
+
+impl RestartPolicy {
+    /// Get the delay before the given restart attempt
+    pub fn delay(&self, attempt: u32) -> Duration {
+        self.base_delay.checked_mul(2u32.saturating_pow(attempt))
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+}
+
+/// The panic being unwound on this thread, filled by the panic hook
+struct CapturedPanic {
+    message: String,
+    location: Option<String>,
+    backtrace: String
+}
+
+thread_local! {
+    static LAST_PANIC: RefCell<Option<CapturedPanic>> = const { RefCell::new(None) };
+}
+
+fn tasks() -> &'static Mutex<HashMap<String, TaskHealth>> {
+    static TASKS: OnceLock<Mutex<HashMap<String, TaskHealth>>> = OnceLock::new();
+    TASKS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn panic_handler() -> &'static Mutex<Option<PanicHandler>> {
+    static HANDLER: OnceLock<Mutex<Option<PanicHandler>>> = OnceLock::new();
+    HANDLER.get_or_init(|| Mutex::new(None))
+}
+
+/// Install a panic hook saving the backtrace of the panics, the previous hook is still called
+fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+
+    INSTALL.call_once(|| {
+        let previous = std::panic::take_hook();
+
+        std::panic::set_hook(Box::new(move |info| {
+            let message = match info.payload().downcast_ref::<&str>() {
+                Some(s) => s.to_string(),
+                None => info.payload().downcast_ref::<String>().cloned().unwrap_or_else(|| "Box<dyn Any>".into())
+            };
+
+            LAST_PANIC.with(|last| {
+                *last.borrow_mut() = Some(CapturedPanic {
+                    message,
+                    location: info.location().map(|l| l.to_string()),
+                    backtrace: Backtrace::force_capture().to_string()
+                });
+            });
+
+            previous(info);
+        }));
+    });
+}
+
+/// Set the function called with each captured panic, e.g. to broadcast it
+pub fn set_panic_handler(handler: impl Fn(TaskPanic) + Send + Sync + 'static) {
+    if let Ok(mut current) = panic_handler().lock() {
+        *current = Some(Arc::new(handler));
+    }
+}
+
+/// Get the health of the long-lived tasks and the panics of the one-shot tasks
+pub fn health() -> Vec<TaskHealth> {
+    let mut health = match tasks().lock() {
+        Ok(tasks) => tasks.values().cloned().collect::<Vec<TaskHealth>>(),
+        Err(_) => Vec::new()
+    };
+
+    health.sort_by(|a, b| a.name.cmp(&b.name));
+    health
+}
+
+/// Spawn a named task, its panic is captured and reported
+///
+/// Resolves to `None` if the task panicked
+pub fn spawn<F, T>(name: impl ToString, future: F) -> JoinHandle<Option<T>>
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static
+{
+    install_panic_hook();
+    let name = name.to_string();
+
+    spawn_named(&name.clone(), async move {
+        match CatchUnwind::new(future).await {
+            Ok(output) => Some(output),
+            Err(panic) => {
+                update(&name, |task| {
+                    task.state = TaskState::OneShot;
+                    task.panics += 1;
+                    task.last_panic = Some(panic.message.clone());
+                });
+                report(&name, panic);
+                None
+            }
+        }
+    })
+}
+
+/// Spawn a long-lived loop, restarted with the policy each time it panics
+///
+/// `factory` creates the future of each run, the loop stops once a run ends without panicking
+pub fn spawn_loop<F, Fut>(name: impl ToString, policy: RestartPolicy, mut factory: F) -> JoinHandle<()>
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static
+{
+    install_panic_hook();
+    let name = name.to_string();
+
+    spawn_named(&name.clone(), async move {
+        let mut attempt = 0;
+
+        loop {
+            update(&name, |task| {
+                task.state = TaskState::Running;
+                task.started = Utc::now();
+            });
+            let started = Instant::now();
+
+            match CatchUnwind::new(factory()).await {
+                Ok(()) => {
+                    update(&name, |task| task.state = TaskState::Finished);
+                    break;
+                }
+                Err(panic) => {
+                    if started.elapsed() >= policy.stable_after {
+                        attempt = 0;
+                    }
+
+                    let delay = policy.delay(attempt);
+                    attempt = attempt.saturating_add(1);
+
+                    update(&name, |task| {
+                        task.state = TaskState::Restarting;
+                        task.panics += 1;
+                        task.restarts += 1;
+                        task.last_panic = Some(panic.message.clone());
+                    });
+                    report(&name, panic);
+
+                    warn!(target: "Supervisor", "The task '{name}' will be restarted in {delay:?}");
+                    tokio::time::sleep(delay).await;
+                }
+            }
+        }
+    })
+}
+
+fn update(name: &str, f: impl FnOnce(&mut TaskHealth)) {
+    if let Ok(mut tasks) = tasks().lock() {
+        let task = tasks.entry(name.to_string()).or_insert_with(|| TaskHealth {
+            name: name.to_string(),
+            state: TaskState::Running,
+            started: Utc::now(),
+            restarts: 0,
+            panics: 0,
+            last_panic: None
+        });
+
+        f(task);
+    }
+}
+
+fn report(name: &str, mut panic: TaskPanic) {
+    panic.task = name.to_string();
+    error!(target: "Supervisor", "The task '{name}' panicked at {}: {}", panic.location.as_deref().unwrap_or("unknown"), panic.message);
+
+    let handler = panic_handler().lock().ok().and_then(|h| h.clone());
+    if let Some(handler) = handler {
+        handler(panic);
+    }
+}
+
+/// Catch the panics of the inner future
+struct CatchUnwind<F> {
+    future: Pin<Box<F>>
+}
+
+impl<F: Future> CatchUnwind<F> {
+    fn new(future: F) -> Self {
+        Self { future: Box::pin(future) }
+    }
+}
+
+impl<F: Future> Future for CatchUnwind<F> {
+    type Output = Result<F::Output, TaskPanic>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match catch_unwind(AssertUnwindSafe(|| self.future.as_mut().poll(cx))) {
+            Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+            Ok(Poll::Pending) => Poll::Pending,
+            Err(payload) => Poll::Ready(Err(to_task_panic(payload)))
+        }
+    }
+}
+
+fn to_task_panic(payload: Box<dyn Any + Send>) -> TaskPanic {
+    let captured = LAST_PANIC.with(|last| last.borrow_mut().take());
+
+    match captured {
+        Some(captured) => TaskPanic {
+            task: String::new(),
+            message: captured.message,
+            location: captured.location,
+            backtrace: captured.backtrace
+        },
+        None => TaskPanic {
+            task: String::new(),
+            message: payload.downcast_ref::<&str>().map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "Box<dyn Any>".into()),
+            location: None,
+            backtrace: String::new()
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::time::Duration;
+    use crate::supervisor::{health, spawn, spawn_loop, RestartPolicy, TaskState};
+
+    #[tokio::test]
+    async fn panic_is_captured() {
+        let result = spawn("test::panic", async { panic!("boom") }).await.unwrap();
+        assert!(result.is_none());
+
+        let task = health().into_iter().find(|t| t.name == "test::panic").unwrap();
+        assert_eq!(task.state, TaskState::OneShot);
+        assert_eq!(task.last_panic.as_deref(), Some("boom"));
+        assert!(task.is_healthy());
+
+        spawn("test::panic", async { panic!("boom again") }).await.unwrap();
+        let task = health().into_iter().find(|t| t.name == "test::panic").unwrap();
+        assert_eq!(task.panics, 2);
+
+        assert_eq!(spawn("test::ok", async { 42 }).await.unwrap(), Some(42));
+    }
+
+    #[tokio::test]
+    async fn loop_is_restarted() {
+        let runs = Arc::new(AtomicU32::new(0));
+        let policy = RestartPolicy {
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+            stable_after: Duration::from_secs(60)
+        };
+
+        let counter = runs.clone();
+        spawn_loop("test::loop", policy, move || {
+            let counter = counter.clone();
+            async move {
+                if counter.fetch_add(1, Ordering::SeqCst) < 2 {
+                    panic!("not yet");
+                }
+            }
+        }).await.unwrap();
+
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+
+        let task = health().into_iter().find(|t| t.name == "test::loop").unwrap();
+        assert_eq!(task.state, TaskState::Finished);
+        assert_eq!(task.restarts, 2);
+    }
+
+    #[test]
+    fn backoff() {
+        let policy = RestartPolicy::default();
+        assert_eq!(policy.delay(0), Duration::from_secs(1));
+        assert_eq!(policy.delay(3), Duration::from_secs(8));
+        assert_eq!(policy.delay(40), Duration::from_secs(300));
+    }
+}
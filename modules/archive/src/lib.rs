@@ -2,13 +2,45 @@
 //! Ce protocole a été conçu par [lilevil](https://github.com/lil-evil)
 
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicU8, Ordering};
 use chrono::{DateTime, LocalResult, TimeZone, Utc};
 use serde::{ Serialize, Deserialize };
-use error::{ Result, Error, ArchiveError, FileError };
-use crate::constants::{BLOAT, check_magic1, check_magic2, MAGIC1, MAGIC2, OWNER_PID, VERSION};
+use error::{ Result, Error, ArchiveError };
+use crate::constants::{BLOAT, check_magic1, check_magic3, FORMAT_VERSION, LEGACY_FORMAT_VERSION, MAGIC1, MAGIC2, MAGIC3, OWNER_PID, VERSION};
 use crate::security::{bytes_to_string, read_file};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// The environment variable holding the passphrase of the archives
+pub const PASSPHRASE_ENV: &str = "KADY_ARCHIVE_PASSPHRASE";
+/// The environment variable holding the path of the key file of the archives
+pub const KEY_FILE_ENV: &str = "KADY_ARCHIVE_KEY_FILE";
+
+/// The secret from which the encryption key of an archive is derived
+#[derive(Debug, Clone)]
+pub enum ArchiveKey {
+    Passphrase(String),
+    /// A file whose whole content is used as the passphrase
+    KeyFile(PathBuf)
+}
+
+impl ArchiveKey {
+    /// Get the key from `KADY_ARCHIVE_PASSPHRASE` or `KADY_ARCHIVE_KEY_FILE`
+    pub fn from_env() -> Option<Self> {
+        if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+            return Some(Self::Passphrase(passphrase));
+        }
+
+        std::env::var(KEY_FILE_ENV).ok().map(|path| Self::KeyFile(path.into()))
+    }
+
+    fn secret(&self) -> Result<Vec<u8>> {
+        match self {
+            Self::Passphrase(p) => Ok(p.as_bytes().to_vec()),
+            Self::KeyFile(path) => read_file(path)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ArchiveDataFormat {
     Toml = 0,
     Json = 1,
@@ -25,6 +57,24 @@ impl From<ArchiveDataFormat> for u8 {
     }
 }
 
+impl std::str::FromStr for ArchiveDataFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "toml" => Ok(ArchiveDataFormat::Toml),
+            "json" => Ok(ArchiveDataFormat::Json),
+            "yaml" | "yml" => Ok(ArchiveDataFormat::Yaml),
+            _ => Err(Error::Archive(ArchiveError::UnsupportedFormat(format!("The data type '{s}' is unknown"))))
+        }
+    }
+}
+
+/// Convert a body written in the format `from` to the format `to`
+pub fn convert(origin: &str, from: ArchiveDataFormat, to: ArchiveDataFormat) -> Result<String> {
+    parser::format_body(to, &parser::parse_declared(from, origin)?)
+}
+
 
 #[derive(Serialize, Deserialize)]
 pub struct Archive {
@@ -45,7 +95,13 @@ pub struct Archive {
     /// The pid of the user who created the archive
     pub owner_pid: u64,
     /// The data stored in the archive
-    body: serde_json::Value
+    body: serde_json::Value,
+    /// The key used to encrypt the archive, the legacy format is used without it
+    #[serde(skip)]
+    key: Option<ArchiveKey>,
+    /// The format version of the file on the disk, updated by each save
+    #[serde(skip)]
+    format_version: AtomicU8
 }
 
 impl Archive {
@@ -55,7 +111,7 @@ impl Archive {
             "{".to_string(),
             format!(
                 "data_type={},bloat={},data_size={},version={},creation={:?},last_modification={:?},owner_pid={}",
-                self.data_type.clone() as u8,
+                self.data_type as u8,
                 self.bloat,
                 self.data_size,
                 self.version,
@@ -69,14 +125,24 @@ impl Archive {
 
     /// Use Self to format a String that contain the formatted body
     fn format_body(&self) -> Result<String> {
-        match serde_json::to_string(&self.body) {
-            Ok(s) => Ok(s),
-            Err(e) => Err(Error::Archive(ArchiveError::CannotSerializeBody(format!("{:?}", e))))
-        }
+        parser::format_body(self.data_type, &self.body)
     }
 
-    /// Encrypt the archive
+    /// Encrypt the archive, with the AEAD format if a key is set
     fn encrypt(&self) -> Result<Vec<u8>> {
+        let key = match &self.key {
+            Some(key) => key,
+            None => return self.encrypt_legacy()
+        };
+
+        let header = self.format_header();
+        let plain = [format!("{}:", header.len()).as_bytes(), header.as_bytes(), self.format_body()?.as_bytes()].concat();
+
+        Ok([&MAGIC3[..], &[FORMAT_VERSION], &cipher::seal(key, &plain)?].concat())
+    }
+
+    /// Encrypt the archive with the obfuscation of the first format version
+    fn encrypt_legacy(&self) -> Result<Vec<u8>> {
         let encrypted_header = self.format_header();
 
         let header_size = encrypted_header.len() as u64;
@@ -86,53 +152,95 @@ impl Archive {
         Ok([&MAGIC1[..], format!("{header_size}").as_bytes(), b":", encrypted].concat())
     }
 
-    /// Save the archive
+    /// Save the archive atomically
+    ///
+    /// The previous content is kept encrypted in `<file>.bak`, the archive and its backup are only readable by their owner
     pub fn save(&self) -> Result<()> {
-        match std::fs::write(&self.path, self.encrypt()?) {
-            Ok(_) => Ok(()),
-            Err(e) => Err(Error::Fs(FileError::CannotWriteFile(e.to_string())))
-        }
+        storage::write_private(&self.path, self.encrypt()?)?;
+
+        let format_version = if self.key.is_some() { FORMAT_VERSION } else { LEGACY_FORMAT_VERSION };
+        self.format_version.store(format_version, Ordering::Relaxed);
+        Ok(())
     }
 
     /// Create a new archive with the given path and data type
     ///
     /// Will automatically save the archive
-    pub fn create(path: PathBuf, data_type: ArchiveDataFormat) -> Self {
+    pub fn create(path: PathBuf, data_type: ArchiveDataFormat) -> Result<Self> {
+        Self::create_with(path, data_type, None)
+    }
+
+    /// Create a new archive encrypted with the given key
+    ///
+    /// Will automatically save the archive
+    pub fn create_with_key(path: PathBuf, data_type: ArchiveDataFormat, key: ArchiveKey) -> Result<Self> {
+        Self::create_with(path, data_type, Some(key))
+    }
+
+    fn create_with(path: PathBuf, data_type: ArchiveDataFormat, key: Option<ArchiveKey>) -> Result<Self> {
         let body = serde_json::Value::Object(serde_json::Map::new());
 
         let arch = Self {
-            path, data_type, body,
+            path, data_type, body, key,
             bloat: BLOAT.to_string(),
             data_size: 0,
             version: VERSION.into(),
             creation: Utc::now(),
             last_modification: Utc::now(),
             owner_pid: OWNER_PID,
+            format_version: AtomicU8::new(0)
         };
 
-        let _ = arch.save();
-        arch
+        arch.save()?;
+        Ok(arch)
     }
 
-    /// Open an archive
+    /// Open an archive, only the archives of the legacy format can be opened without a key
     pub fn open(path: PathBuf) -> Result<Self> {
-        // read file
-        let raw = read_file(&path)?;
+        Self::open_with(path, None)
+    }
 
-        // check if the magic1 is present
-        if !check_magic1(&raw) { return Err(Error::Archive(ArchiveError::CorruptedArchive("Magic1 wasn't found".into()))); }
-        let raw = &mut raw[MAGIC1.len()..].to_vec();
+    /// Open an archive with its key
+    ///
+    /// An archive of the legacy format is opened too, and will be saved with the new format
+    pub fn open_with_key(path: PathBuf, key: ArchiveKey) -> Result<Self> {
+        Self::open_with(path, Some(key))
+    }
+
+    fn open_with(path: PathBuf, key: Option<ArchiveKey>) -> Result<Self> {
+        // read file
+        let raw = storage::read(&path)?;
+
+        let (header_size, decrypted, format_version) = if check_magic1(&raw) {
+            let (header_size, decrypted) = security::decode_legacy(&raw[MAGIC1.len()..])?;
+            (header_size, decrypted, LEGACY_FORMAT_VERSION)
+        } else if check_magic3(&raw) {
+            let raw = &raw[MAGIC3.len()..];
+
+            match raw.first() {
+                Some(&FORMAT_VERSION) => {},
+                Some(v) => return Err(Error::Archive(ArchiveError::UnsupportedFormat(format!("The format version {v} is not supported")))),
+                None => return Err(Error::Archive(ArchiveError::CorruptedArchive("The format version is missing".into())))
+            }
 
-        let (header_position, header_size) = security::get_header_position(raw)?;
+            let key = match &key {
+                Some(key) => key,
+                None => return Err(Error::Archive(ArchiveError::MissingKey(format!("{path:?} is encrypted, a key is required"))))
+            };
 
-        let decrypted = &mut raw[(header_position + 1)..];
+            let plain = cipher::open(key, &raw[1..])?;
+            let (position, header_size) = security::get_header_position(&plain)?;
 
-        security::decrypt(decrypted, header_size);
+            (header_size, plain[(position + 1)..].to_vec(), FORMAT_VERSION)
+        } else {
+            return Err(Error::Archive(ArchiveError::CorruptedArchive("Magic1 wasn't found".into())));
+        };
+        let decrypted = &decrypted[..];
 
-        // check if the magic2 is present
-        if !check_magic2(decrypted) { return Err(Error::Archive(ArchiveError::CorruptedArchive("Magic2 wasn't found in the decrypted informations".into()))); }
+        if decrypted.len() < header_size as usize {
+            return Err(Error::Archive(ArchiveError::CorruptedArchive("The header is truncated".into())));
+        }
 
-        let decrypted = &mut decrypted[MAGIC2.len()..];
         let header = {
             let raw = match bytes_to_string(&decrypted[..header_size as usize]) {
                 Ok(s) => s,
@@ -162,49 +270,155 @@ impl Archive {
                 Err(e) => return Err(Error::Archive(ArchiveError::CorruptedArchive(format!("Cannot convert the body to a string: {:?}", e)))),
             };
 
-            match parser::parse_body(data_type.clone(), &raw) {
-                Ok(b) => b,
-                Err(e) => return Err(Error::Archive(ArchiveError::InvalidBody(format!("Cannot parse the body: {:?}", e)))),
-            }
+            parser::parse_declared(data_type, &raw)?
         };
 
         Ok(Self {
-            body, data_type, version, bloat, path, data_size, creation, last_modification, owner_pid
+            body, data_type, version, bloat, path, data_size, creation, last_modification, owner_pid, key,
+            format_version: AtomicU8::new(format_version)
         })
     }
 
+    /// Set the key used by the next saves, to migrate a legacy archive or to rotate its key
+    pub fn set_key(&mut self, key: ArchiveKey) {
+        self.key = Some(key);
+    }
+
+    /// Get the format version of the file on the disk, as read by the opening or written by the last save
+    pub fn format_version(&self) -> u8 {
+        self.format_version.load(Ordering::Relaxed)
+    }
+
+    /// Return true if the file on the disk uses the AEAD format
+    ///
+    /// A legacy archive opened with a key isn't encrypted until it's saved
+    pub fn is_encrypted(&self) -> bool {
+        self.format_version() == FORMAT_VERSION
+    }
+
+    /// Get the format of the body
+    pub fn data_type(&self) -> ArchiveDataFormat {
+        self.data_type
+    }
+
+    /// Change the format used to write the body by the next saves
+    ///
+    /// Fails if the body cannot be written in the new format, e.g. a `null` value in TOML
+    pub fn set_data_type(&mut self, data_type: ArchiveDataFormat) -> Result<()> {
+        parser::format_body(data_type, &self.body)?;
+        self.data_type = data_type;
+        Ok(())
+    }
+
     /// Get the views value from the archive
+    ///
+    /// The key is a dotted path (`database.password`) or a JSON pointer (`/database/password`),
+    /// a key containing a `.` can only be reached with a JSON pointer (`/api.example.com/token`)
     pub fn get_raw(&self, key: &str) -> Option<&serde_json::Value> {
-        self.body.get(key)
+        let parts = path::split(key);
+        if parts.is_empty() { return None; }
+
+        parts.iter().try_fold(&self.body, |current, part| match current {
+            serde_json::Value::Object(map) => map.get(part),
+            serde_json::Value::Array(array) => array.get(part.parse::<usize>().ok()?),
+            _ => None
+        })
     }
 
     /// Get a value from the archive and deserialize it
     pub fn get<T: serde::de::DeserializeOwned>(&self, key: &str) -> Option<T> {
-        match self.body.get(key) {
-            Some(v) => match serde_json::from_value(v.clone()) {
-                Ok(v) => Some(v),
-                Err(_) => None,
-            },
+        match self.get_raw(key) {
+            Some(v) => serde_json::from_value(v.clone()).ok(),
             None => None,
         }
     }
 
-    /// Set a value in the archive
-    pub fn set_raw(&mut self, key: &str, value: serde_json::Value) {
-        self.body[key] = value
+    /// Get a whole section of the archive as a struct
+    ///
+    /// Unlike `get`, the error tells if the section is missing or which field cannot be read
+    pub fn section<T: serde::de::DeserializeOwned>(&self, key: &str) -> Result<T> {
+        let value = match self.get_raw(key) {
+            Some(v) => v.clone(),
+            None => return Err(Error::Archive(ArchiveError::MissingEntry(format!("'{key}' doesn't exist in the archive"))))
+        };
+
+        match serde_json::from_value(value) {
+            Ok(section) => Ok(section),
+            Err(e) => Err(Error::Archive(ArchiveError::InvalidBodyValue(format!("Cannot read '{key}': {e}"))))
+        }
     }
 
-    /// Set a value in the archive and serialize it
-    pub fn set<T: serde::Serialize>(&mut self, key: &str, value: T) -> Result<()>  {
-        match serde_json::to_value(value) {
-            Ok(v) => {
-                self.set_raw(key, v);
+    /// Set a value in the archive, the missing objects of the path are created
+    ///
+    /// Fails if the path goes through a value which is not an object
+    pub fn set_raw(&mut self, key: &str, value: serde_json::Value) -> Result<()> {
+        let parts = path::split(key);
+        let (last, parents) = match parts.split_last() {
+            Some(split) => split,
+            None => return Err(Error::Archive(ArchiveError::InvalidBodyValue("The key is empty".into())))
+        };
+
+        let mut current = &mut self.body;
+        for (i, part) in parents.iter().enumerate() {
+            let map = match current {
+                serde_json::Value::Object(map) => map,
+                _ => return Err(Error::Archive(ArchiveError::InvalidBodyValue(format!("'{}' is not an object", path::join(&parts[..i]))))),
+            };
+            current = map.entry(part.clone()).or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()));
+        }
+
+        match current {
+            serde_json::Value::Object(map) => {
+                map.insert(last.clone(), value);
                 Ok(())
             },
+            _ => Err(Error::Archive(ArchiveError::InvalidBodyValue(format!("'{}' is not an object", path::join(parents))))),
+        }
+    }
+
+    /// Set a value in the archive and serialize it, a struct is written as a whole section
+    pub fn set<T: serde::Serialize>(&mut self, key: &str, value: T) -> Result<()>  {
+        match serde_json::to_value(value) {
+            Ok(v) => self.set_raw(key, v),
             Err(e) => Err(Error::Archive(ArchiveError::InvalidBodyValue(format!("Cannot serialize the value: {:?}", e)))),
         }
     }
 
+    /// Remove a value from the archive, return it if it existed
+    ///
+    /// A segment can be the index of an array (`admins.0`), the next items of the array are shifted
+    pub fn remove(&mut self, key: &str) -> Option<serde_json::Value> {
+        let parts = path::split(key);
+        let (last, parents) = parts.split_last()?;
+
+        let mut current = &mut self.body;
+        for part in parents {
+            current = match current {
+                serde_json::Value::Object(map) => map.get_mut(part)?,
+                serde_json::Value::Array(array) => array.get_mut(part.parse::<usize>().ok()?)?,
+                _ => return None
+            };
+        }
+
+        match current {
+            serde_json::Value::Object(map) => map.remove(last),
+            serde_json::Value::Array(array) => {
+                let index = last.parse::<usize>().ok()?;
+                (index < array.len()).then(|| array.remove(index))
+            },
+            _ => None
+        }
+    }
+
+    /// List the paths of every value of the archive, the objects are walked through
+    ///
+    /// A path is dotted, unless one of its keys contains a `.` and it's then written as a JSON pointer
+    pub fn keys(&self) -> Vec<String> {
+        let mut keys = Vec::new();
+        path::leaves(&self.body, &mut Vec::new(), &mut keys);
+        keys
+    }
+
     #[cfg(feature = "total_access")]
     pub fn copy_body(&self) -> serde_json::Value { self.body.clone() }
 
@@ -296,6 +510,8 @@ fn get_owner_pid(from: &serde_json::Value) -> Result<u64> {
 /// Contain:
 /// - `parse_header` to parse the header of an archive
 /// - `parse_body` to parse the body of an archive
+/// - `parse_declared` to parse the body of an archive and check it against its declared format
+/// - `format_body` to write the body of an archive
 mod parser {
     use std::collections::HashMap;
     use error::{ArchiveError, Error, Result};
@@ -359,7 +575,186 @@ mod parser {
                     Err(e) => Err(Error::Archive(ArchiveError::InvalidBody(e.to_string())))
                 }
             }
-            _ => unimplemented!("The data type {:?} is not implemented yet", data_type)
+            ArchiveDataFormat::Toml => {
+                match toml::from_str(origin) {
+                    Ok(body) => Ok(body),
+                    Err(e) => Err(Error::Archive(ArchiveError::InvalidBody(e.to_string())))
+                }
+            }
+            ArchiveDataFormat::Yaml => {
+                match serde_yaml::from_str(origin) {
+                    Ok(body) => Ok(body),
+                    Err(e) => Err(Error::Archive(ArchiveError::InvalidBody(e.to_string())))
+                }
+            }
+        }
+    }
+
+    /// Write the body of the archive
+    pub(super) fn format_body(data_type: ArchiveDataFormat, body: &serde_json::Value) -> Result<String> {
+        let formatted = match data_type {
+            ArchiveDataFormat::Json => serde_json::to_string(body).map_err(|e| e.to_string()),
+            ArchiveDataFormat::Toml => toml::to_string(body).map_err(|e| e.to_string()),
+            ArchiveDataFormat::Yaml => serde_yaml::to_string(body).map_err(|e| e.to_string()),
+        };
+
+        match formatted {
+            Ok(s) => Ok(s),
+            Err(e) => Err(Error::Archive(ArchiveError::CannotSerializeBody(format!("Cannot write the body in {data_type:?}: {e}"))))
+        }
+    }
+
+    /// Parse the body of the archive written in the declared format
+    ///
+    /// The body must be an object, and a body that is also valid in a stricter format
+    /// (e.g. JSON declared as YAML) is reported as not matching its header
+    pub(super) fn parse_declared(data_type: ArchiveDataFormat, origin: &str) -> Result<serde_json::Value> {
+        let mismatch = |found: ArchiveDataFormat| Error::Archive(ArchiveError::InvalidHeader(format!("The data type is {data_type:?} but the body is written in {found:?}")));
+
+        let body = match parse_body(data_type, origin) {
+            Ok(body) => body,
+            Err(e) => return match detect_format(origin) {
+                Some(found) => Err(mismatch(found)),
+                None => Err(Error::Archive(ArchiveError::InvalidBody(format!("Cannot parse the body: {:?}", e))))
+            }
+        };
+
+        let empty = match body.as_object() {
+            Some(object) => object.is_empty(),
+            None => return Err(Error::Archive(ArchiveError::InvalidBody(format!("The body must be an object, found {}", kind(&body)))))
+        };
+
+        // an empty object is written the same way in JSON and in YAML
+        match detect_format(origin) {
+            Some(found) if found != data_type && !empty => Err(mismatch(found)),
+            _ => Ok(body)
+        }
+    }
+
+    fn kind(value: &serde_json::Value) -> &'static str {
+        match value {
+            serde_json::Value::Null => "null",
+            serde_json::Value::Bool(_) => "a boolean",
+            serde_json::Value::Number(_) => "a number",
+            serde_json::Value::String(_) => "a string",
+            serde_json::Value::Array(_) => "an array",
+            serde_json::Value::Object(_) => "an object"
+        }
+    }
+
+    /// Find the strictest format in which the body is an object
+    ///
+    /// YAML is tried last since a JSON document is valid YAML
+    fn detect_format(origin: &str) -> Option<ArchiveDataFormat> {
+        [ArchiveDataFormat::Json, ArchiveDataFormat::Toml, ArchiveDataFormat::Yaml]
+            .into_iter()
+            .find(|data_type| parse_body(*data_type, origin).map(|body| body.is_object()).unwrap_or(false))
+    }
+}
+
+/// The AEAD encryption of the archives
+///
+/// The encrypted data is `salt (16 bytes) | nonce (12 bytes) | ciphertext with its tag`,
+/// the key is derived from the [`ArchiveKey`](crate::ArchiveKey) and the salt with Argon2id
+mod cipher {
+    use argon2::Argon2;
+    use chacha20poly1305::{ChaCha20Poly1305, Key, KeyInit, Nonce};
+    use chacha20poly1305::aead::{Aead, Payload};
+    use rand::RngCore;
+    use rand::rngs::OsRng;
+    use error::{ArchiveError, Error, Result};
+    use crate::ArchiveKey;
+    use crate::constants::{FORMAT_VERSION, MAGIC3};
+
+    const SALT_SIZE: usize = 16;
+    const NONCE_SIZE: usize = 12;
+
+    fn derive(key: &ArchiveKey, salt: &[u8]) -> Result<ChaCha20Poly1305> {
+        let mut derived = [0u8; 32];
+
+        if let Err(e) = Argon2::default().hash_password_into(&key.secret()?, salt, &mut derived) {
+            return Err(Error::Archive(ArchiveError::InvalidKey(e.to_string())));
+        }
+
+        Ok(ChaCha20Poly1305::new(Key::from_slice(&derived)))
+    }
+
+    /// The magic and the version are authenticated with the data
+    fn aad() -> Vec<u8> {
+        [&MAGIC3[..], &[FORMAT_VERSION]].concat()
+    }
+
+    pub(super) fn seal(key: &ArchiveKey, plain: &[u8]) -> Result<Vec<u8>> {
+        let mut salt = [0u8; SALT_SIZE];
+        let mut nonce = [0u8; NONCE_SIZE];
+        OsRng.fill_bytes(&mut salt);
+        OsRng.fill_bytes(&mut nonce);
+
+        let encrypted = derive(key, &salt)?
+            .encrypt(Nonce::from_slice(&nonce), Payload { msg: plain, aad: &aad() });
+
+        match encrypted {
+            Ok(encrypted) => Ok([&salt[..], &nonce[..], &encrypted].concat()),
+            Err(_) => Err(Error::Archive(ArchiveError::CannotSerializeBody("Cannot encrypt the archive".into())))
+        }
+    }
+
+    /// Decrypt the data, a wrong key or any modification is reported as a corrupted archive
+    pub(super) fn open(key: &ArchiveKey, raw: &[u8]) -> Result<Vec<u8>> {
+        if raw.len() < SALT_SIZE + NONCE_SIZE {
+            return Err(Error::Archive(ArchiveError::CorruptedArchive("The archive is truncated".into())));
+        }
+
+        let (salt, rest) = raw.split_at(SALT_SIZE);
+        let (nonce, encrypted) = rest.split_at(NONCE_SIZE);
+
+        match derive(key, salt)?.decrypt(Nonce::from_slice(nonce), Payload { msg: encrypted, aad: &aad() }) {
+            Ok(plain) => Ok(plain),
+            Err(_) => Err(Error::Archive(ArchiveError::CorruptedArchive("The archive was modified or the key is wrong".into())))
+        }
+    }
+}
+
+/// The paths used to reach the nested values of the body
+///
+/// A path is either dotted (`database.password`) or a JSON pointer (`/database/password`)
+mod path {
+    /// Split a path into its keys
+    ///
+    /// A JSON pointer starts with `/`, its `~1` and `~0` are unescaped into `/` and `~`
+    pub(super) fn split(key: &str) -> Vec<String> {
+        if let Some(pointer) = key.strip_prefix('/') {
+            pointer.split('/').map(|part| part.replace("~1", "/").replace("~0", "~")).collect()
+        } else if key.is_empty() {
+            Vec::new()
+        } else {
+            key.split('.').map(|part| part.to_string()).collect()
+        }
+    }
+
+    /// Join the keys into a dotted path, or into a JSON pointer if a key cannot be written in a dotted path
+    pub(super) fn join(parts: &[String]) -> String {
+        let dotted = parts.iter().all(|part| !part.is_empty() && !part.contains('.'))
+            && !parts.first().is_some_and(|part| part.starts_with('/'));
+
+        if !dotted {
+            parts.iter().map(|part| format!("/{}", part.replace('~', "~0").replace('/', "~1"))).collect()
+        } else {
+            parts.join(".")
+        }
+    }
+
+    /// Collect the path of every value which is not an object
+    pub(super) fn leaves(value: &serde_json::Value, current: &mut Vec<String>, keys: &mut Vec<String>) {
+        match value {
+            serde_json::Value::Object(map) if !map.is_empty() || current.is_empty() => {
+                for (key, value) in map {
+                    current.push(key.clone());
+                    leaves(value, current, keys);
+                    current.pop();
+                }
+            }
+            _ => keys.push(join(current))
         }
     }
 }
@@ -378,6 +773,13 @@ mod constants {
 
     pub(super) const MAGIC1: [u8; 5] = [127u8, 69u8, 42u8, 68u8, 127u8];
     pub(super) const MAGIC2: [u8; 5] = [127u8, 85u8, 42u8, 68u8, 127u8];
+    /// Start the archives using the AEAD format, followed by the format version byte
+    pub(super) const MAGIC3: [u8; 5] = [127u8, 80u8, 78u8, 68u8, 82u8];
+
+    /// The version of the AEAD format
+    pub(super) const FORMAT_VERSION: u8 = 2;
+    /// The version of the first format, only obfuscated
+    pub(super) const LEGACY_FORMAT_VERSION: u8 = 1;
 
     pub(super) fn check_magic1(bytes: &[u8]) -> bool {
         if bytes.len() < MAGIC1.len() { return false; }
@@ -387,22 +789,26 @@ mod constants {
         if bytes.len() < MAGIC2.len() { return false; }
         bytes[0..MAGIC2.len()] == MAGIC2[..]
     }
+    pub(super) fn check_magic3(bytes: &[u8]) -> bool {
+        if bytes.len() < MAGIC3.len() { return false; }
+        bytes[0..MAGIC3.len()] == MAGIC3[..]
+    }
 }
 
 /// A container that store security functions
 mod security {
     use std::path::PathBuf;
     use error::{Result, Error, FileError, ArchiveError};
-    use crate::constants::BLEP;
+    use crate::constants::{BLEP, check_magic2, MAGIC2};
 
     /// Encrypt the data
     /// Will modify the reference
     pub(super) fn encrypt(data: &mut [u8], header_size: u64) {
         for i in 0..(data.len() as u64) {
             if i % 2 == 0 {
-                data[i as usize] += ((header_size + i) % BLEP) as u8
+                data[i as usize] = data[i as usize].wrapping_add(((header_size + i) % BLEP) as u8)
             } else {
-                data[i as usize] -= ((header_size + i) % BLEP) as u8
+                data[i as usize] = data[i as usize].wrapping_sub(((header_size + i) % BLEP) as u8)
             }
         }
     }
@@ -412,14 +818,29 @@ mod security {
     pub(super) fn decrypt(data: &mut [u8], header_size: u64) {
         for i in 0..(data.len() as u64) {
             if i % 2 == 0 {
-                data[i as usize] -= ((header_size + i) % BLEP) as u8
+                data[i as usize] = data[i as usize].wrapping_sub(((header_size + i) % BLEP) as u8)
             } else {
-                data[i as usize] += ((header_size + i) % BLEP) as u8
+                data[i as usize] = data[i as usize].wrapping_add(((header_size + i) % BLEP) as u8)
             }
         }
     }
 
-    /// Encrypt the data
+    /// Decode an archive of the legacy format, without its first magic
+    ///
+    /// Return the header size and the decoded header and body
+    pub(super) fn decode_legacy(raw: &[u8]) -> Result<(u64, Vec<u8>)> {
+        let (header_position, header_size) = get_header_position(raw)?;
+
+        let mut decrypted = raw[(header_position + 1)..].to_vec();
+        decrypt(&mut decrypted, header_size);
+
+        // check if the magic2 is present
+        if !check_magic2(&decrypted) { return Err(Error::Archive(ArchiveError::CorruptedArchive("Magic2 wasn't found in the decrypted informations".into()))); }
+
+        Ok((header_size, decrypted[MAGIC2.len()..].to_vec()))
+    }
+
+    /// Read a file
     pub(super) fn read_file(path: &PathBuf) -> Result<Vec<u8>> {
         match std::fs::read(path) {
             Ok(data) => Ok(data),
@@ -457,12 +878,28 @@ mod security {
 
 #[cfg(test)]
 mod test {
-    use std::path::Path;
-    use crate::{Archive, ArchiveDataFormat};
+    use std::path::{Path, PathBuf};
+    use error::{ArchiveError, Error};
+    use crate::{Archive, ArchiveDataFormat, ArchiveKey, cipher, convert, parser};
+    use crate::constants::{FORMAT_VERSION, MAGIC3};
+
+    fn temp(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("kady_archive_{}_{name}.pndr", std::process::id()))
+    }
+
+    fn clean(path: &Path) {
+        for file in [path.to_path_buf(), storage::backup_path(path), storage::lock_path(path)] {
+            let _ = std::fs::remove_file(file);
+        }
+    }
+
+    fn key(passphrase: &str) -> ArchiveKey {
+        ArchiveKey::Passphrase(passphrase.into())
+    }
 
     #[test]
     fn create() {
-        let archive = Archive::create(Path::new("test.pndr").to_path_buf(), ArchiveDataFormat::Json);
+        let archive = Archive::create(Path::new("test.pndr").to_path_buf(), ArchiveDataFormat::Json).unwrap();
 
         archive.save().unwrap();
 
@@ -488,4 +925,229 @@ mod test {
             assert!(archive.get::<String>("Hello").unwrap() == "World");
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn encrypted_round_trip() {
+        let path = temp("round_trip");
+        {
+            let mut archive = Archive::create_with_key(path.clone(), ArchiveDataFormat::Json, key("passphrase")).unwrap();
+            archive.set::<String>("discord_token", "secret".into()).unwrap();
+            archive.save().unwrap();
+        }
+
+        assert!(!String::from_utf8_lossy(&std::fs::read(&path).unwrap()).contains("secret"));
+
+        let archive = Archive::open_with_key(path.clone(), key("passphrase")).unwrap();
+        assert_eq!(archive.get::<String>("discord_token").unwrap(), "secret");
+
+        assert!(matches!(Archive::open(path.clone()), Err(Error::Archive(ArchiveError::MissingKey(_)))));
+
+        clean(&path);
+    }
+
+    #[test]
+    fn wrong_key_or_tampering_is_corruption() {
+        let path = temp("tampering");
+        Archive::create_with_key(path.clone(), ArchiveDataFormat::Json, key("passphrase")).unwrap();
+
+        assert!(matches!(
+            Archive::open_with_key(path.clone(), key("wrong")),
+            Err(Error::Archive(ArchiveError::CorruptedArchive(_)))
+        ));
+
+        let mut raw = std::fs::read(&path).unwrap();
+        let last = raw.len() - 1;
+        raw[last] ^= 1;
+        std::fs::write(&path, raw).unwrap();
+
+        assert!(matches!(
+            Archive::open_with_key(path.clone(), key("passphrase")),
+            Err(Error::Archive(ArchiveError::CorruptedArchive(_)))
+        ));
+
+        clean(&path);
+    }
+
+    #[test]
+    fn legacy_archive_is_migrated() {
+        let path = temp("legacy");
+        {
+            let mut archive = Archive::create(path.clone(), ArchiveDataFormat::Json).unwrap();
+            archive.set::<String>("Hello", "World".into()).unwrap();
+            archive.save().unwrap();
+        }
+
+        // a legacy archive can be opened with a key, and is saved with the new format
+        let archive = Archive::open_with_key(path.clone(), key("passphrase")).unwrap();
+        assert!(!archive.is_encrypted());
+        archive.save().unwrap();
+        assert!(archive.is_encrypted());
+
+        assert!(Archive::open(path.clone()).is_err());
+        let archive = Archive::open_with_key(path.clone(), key("passphrase")).unwrap();
+        assert!(archive.is_encrypted());
+        assert_eq!(archive.get::<String>("Hello").unwrap(), "World");
+
+        clean(&path);
+    }
+
+    #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+    struct Database {
+        username: String,
+        password: String,
+        port: u16
+    }
+
+    #[test]
+    fn nested_paths() {
+        let path = temp("nested");
+        let mut archive = Archive::create_with_key(path.clone(), ArchiveDataFormat::Json, key("passphrase")).unwrap();
+
+        archive.set("database.password", "secret").unwrap();
+        archive.set("/api/github~1token", "ghp").unwrap();
+        archive.set("channels", ["1", "2"]).unwrap();
+
+        assert_eq!(archive.get::<String>("/database/password").unwrap(), "secret");
+        assert_eq!(archive.get::<String>("api.github/token").unwrap(), "ghp");
+        assert_eq!(archive.get::<String>("channels.1").unwrap(), "2");
+        assert_eq!(archive.keys(), vec!["api.github/token", "channels", "database.password"]);
+
+        assert!(archive.set("channels.first", "1").is_err());
+        assert!(archive.set("", "1").is_err());
+
+        assert_eq!(archive.remove("database.password").unwrap(), "secret");
+        assert!(archive.remove("database.password").is_none());
+        assert_eq!(archive.keys(), vec!["api.github/token", "channels", "database"]);
+
+        assert_eq!(archive.remove("channels.0").unwrap(), "1");
+        assert!(archive.remove("channels.1").is_none());
+        assert_eq!(archive.get::<Vec<String>>("channels").unwrap(), vec!["2"]);
+
+        // a key containing a dot is listed and reached as a JSON pointer
+        archive.set("/hosts/api.example.com", "token").unwrap();
+        assert!(archive.keys().contains(&"/hosts/api.example.com".to_string()));
+        assert_eq!(archive.remove("/hosts/api.example.com").unwrap(), "token");
+
+        clean(&path);
+    }
+
+    #[test]
+    fn typed_sections() {
+        let path = temp("sections");
+        let mut archive = Archive::create_with_key(path.clone(), ArchiveDataFormat::Json, key("passphrase")).unwrap();
+
+        let database = Database { username: "kady".into(), password: "secret".into(), port: 3306 };
+        archive.set("services.database", &database).unwrap();
+        assert_eq!(archive.section::<Database>("services.database").unwrap(), database);
+
+        assert!(matches!(
+            archive.section::<Database>("services.cache"),
+            Err(Error::Archive(ArchiveError::MissingEntry(_)))
+        ));
+
+        archive.remove("services.database.password");
+        match archive.section::<Database>("services.database") {
+            Err(Error::Archive(ArchiveError::InvalidBodyValue(e))) => assert!(e.contains("missing field `password`")),
+            other => panic!("unexpected result: {other:?}")
+        }
+
+        clean(&path);
+    }
+
+    fn sample() -> serde_json::Value {
+        serde_json::json!({
+            "discord_token": "secret",
+            "database": { "username": "kady", "port": 3306, "ssl": true },
+            "channels": ["1", "2"]
+        })
+    }
+
+    #[test]
+    fn every_format_round_trips() {
+        for data_type in [ArchiveDataFormat::Toml, ArchiveDataFormat::Json, ArchiveDataFormat::Yaml] {
+            let path = temp(&format!("format_{data_type:?}"));
+            {
+                let mut archive = Archive::create_with_key(path.clone(), data_type, key("passphrase")).unwrap();
+                archive.body = sample();
+                archive.save().unwrap();
+            }
+
+            let archive = Archive::open_with_key(path.clone(), key("passphrase")).unwrap();
+            assert_eq!(archive.data_type(), data_type);
+            assert_eq!(archive.body, sample());
+
+            clean(&path);
+        }
+    }
+
+    #[test]
+    fn convert_between_formats() {
+        let json = serde_json::to_string(&sample()).unwrap();
+
+        let toml = convert(&json, ArchiveDataFormat::Json, ArchiveDataFormat::Toml).unwrap();
+        assert!(toml.contains("[database]"));
+
+        let yaml = convert(&toml, ArchiveDataFormat::Toml, ArchiveDataFormat::Yaml).unwrap();
+        let back = convert(&yaml, ArchiveDataFormat::Yaml, ArchiveDataFormat::Json).unwrap();
+        assert_eq!(serde_json::from_str::<serde_json::Value>(&back).unwrap(), sample());
+
+        assert!(convert("{\"a\": null}", ArchiveDataFormat::Json, ArchiveDataFormat::Toml).is_err());
+    }
+
+    #[test]
+    fn mismatched_data_type_is_rejected() {
+        let path = temp("mismatch");
+        {
+            let mut archive = Archive::create_with_key(path.clone(), ArchiveDataFormat::Toml, key("passphrase")).unwrap();
+            archive.body = sample();
+            archive.save().unwrap();
+        }
+
+        // rewrite the header to declare JSON while the body is still TOML
+        let mut archive = Archive::open_with_key(path.clone(), key("passphrase")).unwrap();
+        archive.data_type = ArchiveDataFormat::Json;
+        let plain = [
+            format!("{}:", archive.format_header().len()).into_bytes(),
+            archive.format_header().into_bytes(),
+            parser::format_body(ArchiveDataFormat::Toml, &sample()).unwrap().into_bytes()
+        ].concat();
+        let sealed = cipher::seal(&key("passphrase"), &plain).unwrap();
+        std::fs::write(&path, [&MAGIC3[..], &[FORMAT_VERSION], &sealed].concat()).unwrap();
+
+        assert!(matches!(
+            Archive::open_with_key(path.clone(), key("passphrase")),
+            Err(Error::Archive(ArchiveError::InvalidHeader(_)))
+        ));
+
+        clean(&path);
+    }
+    #[test]
+    fn declared_format_is_checked_strictly() {
+        let json = serde_json::to_string(&sample()).unwrap();
+
+        // a JSON document is valid YAML, but it's not what the header declares
+        assert!(matches!(
+            parser::parse_declared(ArchiveDataFormat::Yaml, &json),
+            Err(Error::Archive(ArchiveError::InvalidHeader(_)))
+        ));
+        assert_eq!(parser::parse_declared(ArchiveDataFormat::Json, &json).unwrap(), sample());
+
+        let empty = parser::format_body(ArchiveDataFormat::Yaml, &serde_json::json!({})).unwrap();
+        assert_eq!(parser::parse_declared(ArchiveDataFormat::Yaml, &empty).unwrap(), serde_json::json!({}));
+    }
+
+    #[test]
+    fn non_object_body_is_rejected() {
+        for (data_type, body) in [
+            (ArchiveDataFormat::Json, "[1, 2]"),
+            (ArchiveDataFormat::Json, "\"token\""),
+            (ArchiveDataFormat::Yaml, "- 1\n- 2\n"),
+            (ArchiveDataFormat::Yaml, "token")
+        ] {
+            assert!(matches!(
+                parser::parse_declared(data_type, body),
+                Err(Error::Archive(ArchiveError::InvalidBody(_)))
+            ), "{data_type:?} {body}");
+        }
+    }
+}
@@ -0,0 +1,249 @@
+//! Build the config from several layers, each one overriding the previous ones:
+//! 1. the base file, given by the `--config` argument
+//! 2. the environment file `<name>.<KADY_ENV>.toml`, next to the base file
+//! 3. the secrets file `<name>.secrets.toml`, next to the base file, that must never be committed
+//! 4. the PNDR archive given by `security.archive_path` (see [`crate::secrets`])
+//! 5. the `KADY_*` environment variables, `__` separating the sections (e.g. `KADY_DATABASE__PASSWORD`),
+//!    except the ones listed in [`ENV_EXCLUDED`]
+//! 6. the `KADY_*_FILE` environment variables, whose value is the path of a file holding the secret
+//!
+//! The relative paths of the config are then resolved against the directory of the base file.
+
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+use error::{ConfigError, Error, Result};
+
+/// The prefix of the environment variables read by the config
+pub const ENV_PREFIX: &str = "KADY_";
+/// The environment variable holding the name of the environment (e.g. `prod`)
+pub const ENV_NAME: &str = "KADY_ENV";
+/// The `KADY_*` variables that aren't config overrides, they hold the key of the archive
+pub const ENV_EXCLUDED: [&str; 4] = [
+    ENV_NAME,
+    "KADY_ARCHIVE_KEY_FILE",
+    "KADY_ARCHIVE_PASSPHRASE",
+    "KADY_ARCHIVE_NEW_PASSPHRASE"
+];
+
+/// Check if an environment variable overrides a value of the config
+fn is_override(key: &str) -> bool {
+    key.starts_with(ENV_PREFIX) && !ENV_EXCLUDED.contains(&key)
+}
+
+/// Read a TOML file into a table
+pub(crate) fn read_table(path: &Path) -> Result<Table> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(_) => return Err(Error::Config(ConfigError::CannotReadFile(path.to_string_lossy().to_string())))
+    };
+
+    match toml::from_str::<Table>(content.as_str()) {
+        Ok(table) => Ok(table),
+        Err(e) => Err(Error::Config(ConfigError::InvalidFile(format!("{path:?} ({e:?})"))))
+    }
+}
+
+/// Read all the file layers of the config, the missing optional files are skipped
+pub(crate) fn read_files(path: &Path, env: Option<&str>) -> Result<Table> {
+    let mut table = read_table(path)?;
+
+    let mut optional = Vec::new();
+    if let Some(env) = env {
+        optional.push(sibling(path, env));
+    }
+    optional.push(sibling(path, "secrets"));
+
+    for layer in optional {
+        if layer.exists() {
+            merge(&mut table, read_table(&layer)?);
+        }
+    }
+
+    Ok(table)
+}
+
+/// Get every file the config is read from, the optional layers are listed even if they don't exist yet
+pub(crate) fn files(path: &Path, env: Option<&str>, vars: impl IntoIterator<Item = (String, String)>) -> Vec<PathBuf> {
+    let mut files = vec![path.to_path_buf()];
+
+    if let Some(env) = env {
+        files.push(sibling(path, env));
+    }
+    files.push(sibling(path, "secrets"));
+
+    let mut secrets = vars.into_iter()
+        .filter(|(key, _)| is_override(key) && key.ends_with("_FILE"))
+        .map(|(_, path)| PathBuf::from(path))
+        .collect::<Vec<PathBuf>>();
+    secrets.sort();
+    files.extend(secrets);
+
+    files
+}
+
+/// Get `<dir>/<stem>.<suffix>.toml` from `<dir>/<stem>.toml`
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let stem = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    path.with_file_name(format!("{stem}.{suffix}.toml"))
+}
+
+/// Merge `layer` into `base`, the tables are merged recursively and the other values are replaced
+pub(crate) fn merge(base: &mut Table, layer: Table) {
+    for (key, value) in layer {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base)), Value::Table(layer)) => merge(base, layer),
+            (_, value) => { base.insert(key, value); }
+        }
+    }
+}
+
+/// Apply the `KADY_*` and `KADY_*_FILE` variables to the table
+pub(crate) fn apply_env(table: &mut Table, vars: impl IntoIterator<Item = (String, String)>) -> Result<()> {
+    let mut vars = vars.into_iter()
+        .filter(|(key, _)| is_override(key))
+        .collect::<Vec<(String, String)>>();
+
+    // the secret files are applied last, so they win over a plain variable
+    vars.sort_by_key(|(key, _)| key.ends_with("_FILE"));
+
+    for (key, raw) in vars {
+        let (key, raw) = match key.strip_suffix("_FILE") {
+            Some(key) => match std::fs::read_to_string(&raw) {
+                Ok(content) => (key.to_string(), content.trim_end().to_string()),
+                Err(_) => return Err(Error::Config(ConfigError::CannotReadFile(raw)))
+            },
+            None => (key, raw)
+        };
+
+        let path = key[ENV_PREFIX.len()..]
+            .split("__")
+            .map(|part| part.to_lowercase())
+            .collect::<Vec<String>>();
+
+        set(table, &path, raw);
+    }
+
+    Ok(())
+}
+
+/// Set the value at the given path, creating the missing sections
+///
+/// The raw value is parsed as a TOML value, unless the current value is a string
+fn set(table: &mut Table, path: &[String], raw: String) {
+    let (last, sections) = match path.split_last() {
+        Some(split) => split,
+        None => return
+    };
+
+    let mut current = table;
+    for section in sections {
+        let entry = current.entry(section.clone()).or_insert_with(|| Value::Table(Table::new()));
+
+        if !entry.is_table() {
+            *entry = Value::Table(Table::new());
+        }
+
+        current = match entry {
+            Value::Table(t) => t,
+            _ => return
+        };
+    }
+
+    let value = match current.get(last) {
+        Some(Value::String(_)) => Value::String(raw),
+        _ => parse_value(raw)
+    };
+
+    current.insert(last.clone(), value);
+}
+
+fn parse_value(raw: String) -> Value {
+    match toml::from_str::<Table>(format!("value = {raw}").as_str()) {
+        Ok(mut t) => t.remove("value").unwrap_or(Value::String(raw)),
+        Err(_) => Value::String(raw)
+    }
+}
+
+/// Resolve a relative path against the directory of the config
+pub(crate) fn resolve(dir: &Path, path: &mut String) {
+    if path.is_empty() || Path::new(path.as_str()).is_absolute() {
+        return;
+    }
+
+    *path = dir.join(path.as_str()).to_string_lossy().to_string();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+    use toml::{Table, Value};
+    use super::{apply_env, files, merge, resolve};
+
+    fn table(content: &str) -> Table {
+        toml::from_str(content).unwrap()
+    }
+
+    #[test]
+    fn merge_keeps_the_untouched_keys() {
+        let mut base = table("intents = 1\n[database]\nhost = \"localhost\"\nport = 3306");
+        merge(&mut base, table("[database]\nport = 3307"));
+
+        assert_eq!(base["intents"], Value::Integer(1));
+        assert_eq!(base["database"]["host"], Value::String("localhost".into()));
+        assert_eq!(base["database"]["port"], Value::Integer(3307));
+    }
+
+    #[test]
+    fn env_overrides_nested_values() {
+        let mut base = table("intents = 1\n[database]\npassword = \"old\"\nport = 3306");
+
+        apply_env(&mut base, vec![
+            ("KADY_INTENTS".to_string(), "42".to_string()),
+            ("KADY_DATABASE__PASSWORD".to_string(), "1234".to_string()),
+            ("KADY_DATABASE__PORT".to_string(), "3307".to_string()),
+            ("KADY_ENV".to_string(), "prod".to_string()),
+            ("KADY_ARCHIVE_PASSPHRASE".to_string(), "passphrase".to_string()),
+            // would fail the load if it was read as a config override
+            ("KADY_ARCHIVE_KEY_FILE".to_string(), "/this/key/does/not/exist".to_string()),
+            ("HOME".to_string(), "/root".to_string())
+        ]).unwrap();
+
+        assert_eq!(base["intents"], Value::Integer(42));
+        // an existing string stays a string, even if it looks like a number
+        assert_eq!(base["database"]["password"], Value::String("1234".into()));
+        assert_eq!(base["database"]["port"], Value::Integer(3307));
+        assert!(base.get("env").is_none());
+        assert!(base.get("archive").is_none());
+        assert!(base.get("home").is_none());
+    }
+
+    #[test]
+    fn every_layer_is_listed() {
+        let files = files(Path::new("/etc/kady/config.toml"), Some("prod"), vec![
+            ("KADY_DATABASE__PASSWORD_FILE".to_string(), "/run/secrets/db".to_string()),
+            ("KADY_DATABASE__PORT".to_string(), "3307".to_string()),
+            ("KADY_ARCHIVE_KEY_FILE".to_string(), "/run/secrets/archive.key".to_string()),
+            ("OTHER_FILE".to_string(), "/tmp/other".to_string())
+        ]);
+
+        assert_eq!(files, vec![
+            PathBuf::from("/etc/kady/config.toml"),
+            PathBuf::from("/etc/kady/config.prod.toml"),
+            PathBuf::from("/etc/kady/config.secrets.toml"),
+            PathBuf::from("/run/secrets/db")
+        ]);
+    }
+
+    #[test]
+    fn relative_paths_are_resolved() {
+        let dir = Path::new("/etc/kady");
+
+        let mut relative = "langs".to_string();
+        resolve(dir, &mut relative);
+        assert_eq!(relative, "/etc/kady/langs");
+
+        let mut absolute = "/var/lib/kady/langs".to_string();
+        resolve(dir, &mut absolute);
+        assert_eq!(absolute, "/var/lib/kady/langs");
+    }
+}
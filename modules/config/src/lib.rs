@@ -1,10 +1,17 @@
-use std::path::PathBuf;
+mod service;
+mod validation;
+pub mod layers;
+pub mod secrets;
+
+use std::path::{Path, PathBuf};
 use serde::{Deserialize, Serialize };
 use client::models::presence::Activity;
 use client::typemap::Type;
 
 use error::Result;
 
+pub use service::ConfigService;
+
 /// Contain the full configuration of the client
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Config {
@@ -12,6 +19,9 @@ pub struct Config {
     pub version: String,
     pub build: String,
     pub langs: String,
+    /// The language used when a translation is missing in the requested one
+    #[serde(default = "default_lang")]
+    pub default_lang: String,
     pub dynamic_requests: String,
     pub memory_report_path: String,
     pub logs_path: String,
@@ -27,27 +37,76 @@ pub struct Config {
 
 impl Config {
     pub fn save(path: String, config: &Config) -> Result<()> {
-        let content: String = match toml::to_string(&config) {
+        let content: String = match config.to_toml() {
             Ok(content) => content,
             Err(_) => return Err(error::Error::Config(error::ConfigError::CannotWriteFile(path)))
         };
 
-        match std::fs::write(path.clone(), content) {
+        match storage::write(Path::new(&path), content) {
             Ok(_) => Ok(()),
-            Err(_) => Err(error::Error::Config(error::ConfigError::CannotWriteFile(path)))
+            Err(e) => Err(error::Error::Config(error::ConfigError::CannotWriteFile(format!("{path}: {e}"))))
+        }
+    }
+
+    /// Serialize the config as TOML
+    pub fn to_toml(&self) -> Result<String> {
+        match toml::to_string(self) {
+            Ok(content) => Ok(content),
+            Err(e) => Err(error::Error::Config(error::ConfigError::InvalidFile(e.to_string())))
         }
     }
 
+    /// Replace the whole config by the one at the given path, if it's valid
     pub fn reload(&mut self, path: String) -> Result<()> {
         let config = load_from(path)?;
+        config.validate()?;
 
-        self.intents = config.intents;
-        self.api = config.api;
+        *self = config;
 
         Ok(())
     }
+
+    /// Resolve all the relative paths against the given directory
+    fn resolve_paths(&mut self, dir: &std::path::Path) {
+        for path in [
+            &mut self.langs,
+            &mut self.dynamic_requests,
+            &mut self.memory_report_path,
+            &mut self.logs_path,
+            &mut self.core_path,
+            &mut self.pid,
+            &mut self.security.archive_path
+        ] {
+            layers::resolve(dir, path);
+        }
+
+        if let Some(path) = self.security.archive_key_file.as_mut() {
+            layers::resolve(dir, path);
+        }
+
+        for (_, path, _) in self.api.declared_files.iter_mut() {
+            layers::resolve(dir, path);
+        }
+    }
+
+    /// Get a copy of the config without its secrets, that can be printed safely
+    pub fn redacted(&self) -> Config {
+        let mut config = self.clone();
+
+        config.security.discord_token = REDACTED.to_string();
+        config.database.password = REDACTED.to_string();
+
+        config
+    }
+}
+
+fn default_lang() -> String {
+    "fr".to_string()
 }
 
+/// The value of the secrets in a redacted config
+pub const REDACTED: &str = "<redacted>";
+
 impl Type for Config {
     type Value = Self;
 }
@@ -63,7 +122,13 @@ pub struct ApiConfig {
 /// Contain security settings
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct SecurityConfig {
+    /// The archive holding the secrets, empty if the secrets are given by the environment
+    #[serde(default)]
     pub archive_path: String,
+    /// The file holding the key of the archive, `KADY_ARCHIVE_PASSPHRASE` is used if it's not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive_key_file: Option<String>,
+    #[serde(default)]
     pub discord_token: String
 }
 
@@ -103,24 +168,101 @@ pub struct CoreConfig {
 pub struct DatabaseConfig {
     pub host: String,
     pub port: u16,
+    #[serde(default)]
     pub username: String,
+    #[serde(default)]
     pub password: String,
-    pub database_name: String
+    pub database_name: String,
+    #[serde(default)]
+    pub pool: PoolConfig,
+    #[serde(default)]
+    pub cleaner: CleanerConfig
 }
 
+/// Contain the settings of the database pool and of its health monitor
+///
+/// All durations are in seconds
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(default)]
+pub struct PoolConfig {
+    pub max_connections: u32,
+    pub min_connections: u32,
+    /// The maximum time to wait for a connection before giving up
+    pub acquire_timeout: u64,
+    /// Close the connections that were idle for this long
+    pub idle_timeout: Option<u64>,
+    /// Close the connections that were opened for this long
+    pub max_lifetime: Option<u64>,
+    /// The number of prepared statements cached per connection
+    pub statement_cache_capacity: usize,
+    /// The interval between two health checks
+    pub health_check_interval: u64,
+    /// The first delay before trying to reconnect, doubled after each failure
+    pub reconnect_base_delay: u64,
+    /// The maximum delay between two reconnection attempts
+    pub reconnect_max_delay: u64
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            max_connections: 10,
+            min_connections: 0,
+            acquire_timeout: 30,
+            idle_timeout: Some(10 * 60),
+            max_lifetime: Some(30 * 60),
+            statement_cache_capacity: 100,
+            health_check_interval: 30,
+            reconnect_base_delay: 1,
+            reconnect_max_delay: 5 * 60
+        }
+    }
+}
+
+/// Contain the settings of the database cleaner
+///
+/// All durations are in seconds
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(default)]
+pub struct CleanerConfig {
+    /// Only log what would be purged, without deleting anything
+    pub dry_run: bool,
+    /// The number of users or guilds deleted per batch
+    pub batch_size: u64,
+    /// Delete the users that weren't seen for this long and that aren't a known member of any guild
+    pub user_lifetime: i64,
+    /// Delete the guilds that weren't seen for this long
+    pub guild_lifetime: i64
+}
+
+impl Default for CleanerConfig {
+    fn default() -> Self {
+        Self {
+            dry_run: true,
+            batch_size: 100,
+            user_lifetime: 60 * 60 * 24 * 14,
+            guild_lifetime: 60 * 60 * 24 * 14
+        }
+    }
+}
+
+/// Load the config from the base file and all its layers (see [`layers`])
 pub fn load_from(path: impl Into<PathBuf>) -> Result<Config> {
     let path = path.into();
 
-    let content: String = match std::fs::read_to_string(&path) {
-        Ok(content) => content,
-        Err(_) => return Err(error::Error::Config(error::ConfigError::CannotReadFile(path.to_string_lossy().to_string())))
+    let dir = path.parent().map(|d| d.to_path_buf()).unwrap_or_default();
+
+    let env = std::env::var(layers::ENV_NAME).ok();
+    let mut table = layers::read_files(&path, env.as_deref())?;
+    secrets::apply_archive(&mut table, &dir)?;
+    layers::apply_env(&mut table, std::env::vars())?;
+
+    let mut config: Config = match toml::Value::Table(table).try_into() {
+        Ok(config) => config,
+        Err(e) => return Err(error::Error::Config(error::ConfigError::InvalidFile(format!("{path:?}: {}", e.message()))))
     };
 
-    match toml::from_str(content.as_str()) {
-        Ok(config) => Ok(config),
-        Err(e) => {
-            eprintln!("{}", e);
-            Err(error::Error::Config(error::ConfigError::InvalidFile(format!("{path:?} ({e:?})"))))
-        }
-    }
-}
\ No newline at end of file
+    config.resolve_paths(&dir);
+
+    Ok(config)
+}
@@ -0,0 +1,85 @@
+//! Load the secrets from the PNDR archive given by `security.archive_path`
+//!
+//! The archive is decrypted with the key given by `KADY_ARCHIVE_PASSPHRASE`, `KADY_ARCHIVE_KEY_FILE`
+//! or `security.archive_key_file`. It's created and edited with the `archive` binary, it can contain:
+//! - `discord_token`: the token of the bot
+//! - `database`: an object with `username`, `password`, `host`, `port` and `database_name`
+
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use toml::{Table, Value};
+use archive::{Archive, ArchiveKey};
+use error::{ArchiveError, Error, Result};
+use crate::layers::{merge, resolve};
+
+/// The key of the Discord token in the archive
+pub const DISCORD_TOKEN: &str = "discord_token";
+/// The key of the database credentials in the archive
+pub const DATABASE: &str = "database";
+
+/// The database credentials stored in the archive, each one is optional
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct DatabaseSecrets {
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub host: Option<String>,
+    pub port: Option<u16>,
+    pub database_name: Option<String>
+}
+
+/// Merge the secrets of the archive into the table, if an archive is configured
+pub(crate) fn apply_archive(table: &mut Table, dir: &Path) -> Result<()> {
+    let mut path = match table.get("security").and_then(|s| s.get("archive_path")).and_then(|p| p.as_str()) {
+        Some(path) if !path.is_empty() => path.to_string(),
+        _ => return Ok(())
+    };
+    resolve(dir, &mut path);
+
+    let key = ArchiveKey::from_env().or_else(|| {
+        let mut key_file = table.get("security")?.get("archive_key_file")?.as_str()?.to_string();
+        resolve(dir, &mut key_file);
+        Some(ArchiveKey::KeyFile(key_file.into()))
+    });
+
+    let archive = match key {
+        Some(key) => Archive::open_with_key(path.into(), key)?,
+        None => Archive::open(path.into())?
+    };
+
+    let mut layer = Table::new();
+
+    if let Some(token) = archive.get::<String>(DISCORD_TOKEN) {
+        layer.insert("security".into(), Value::Table(Table::from_iter([("discord_token".to_string(), Value::String(token))])));
+    }
+
+    let database = match archive.section::<DatabaseSecrets>(DATABASE) {
+        Ok(database) => Some(database),
+        Err(Error::Archive(ArchiveError::MissingEntry(_))) => None,
+        Err(e) => return Err(e)
+    };
+
+    if let Some(database) = database {
+        let mut section = Table::new();
+
+        let strings = [
+            ("username", database.username),
+            ("password", database.password),
+            ("host", database.host),
+            ("database_name", database.database_name)
+        ];
+        for (key, value) in strings {
+            if let Some(value) = value {
+                section.insert(key.into(), Value::String(value));
+            }
+        }
+        if let Some(port) = database.port {
+            section.insert("port".into(), Value::Integer(port as i64));
+        }
+
+        layer.insert("database".into(), Value::Table(section));
+    }
+
+    merge(table, layer);
+
+    Ok(())
+}
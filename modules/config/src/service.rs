@@ -0,0 +1,138 @@
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use log::{error, info};
+use tokio::sync::watch;
+use client::supervisor::{self, RestartPolicy};
+use client::typemap::Type;
+use error::Result;
+use crate::{Config, layers, load_from};
+
+/// The modification date of each file the config is read from
+type Snapshot = Vec<(PathBuf, Option<SystemTime>)>;
+
+/// Own the current configuration, reload it from its files and notify the subscribers when it changes
+///
+/// The whole config is swapped at once, so a reader never sees half of an old and half of a new config
+#[derive(Clone)]
+pub struct ConfigService {
+    path: PathBuf,
+    sender: Arc<watch::Sender<Arc<Config>>>,
+    last_modified: Arc<Mutex<Snapshot>>
+}
+
+impl Type for ConfigService {
+    type Value = Self;
+}
+
+impl ConfigService {
+    /// Load and validate the config at the given path
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+
+        let config = load_from(&path)?;
+        config.validate()?;
+
+        let last_modified = snapshot(layer_files(&path, &config), &[]);
+
+        Ok(Self {
+            path,
+            sender: Arc::new(watch::channel(Arc::new(config)).0),
+            last_modified: Arc::new(Mutex::new(last_modified))
+        })
+    }
+
+    /// Get the current config
+    pub fn get(&self) -> Arc<Config> {
+        self.sender.borrow().clone()
+    }
+
+    /// Get a receiver that is notified each time the config is reloaded
+    pub fn subscribe(&self) -> watch::Receiver<Arc<Config>> {
+        self.sender.subscribe()
+    }
+
+    /// Read the config files again, and replace the current config if the new one is valid
+    ///
+    /// The current config is kept untouched if a file is invalid
+    pub fn reload(&self) -> Result<Arc<Config>> {
+        let before = snapshot(layer_files(&self.path, &self.get()), &[]);
+        if let Ok(mut last_modified) = self.last_modified.lock() {
+            *last_modified = before.clone();
+        }
+
+        let config = load_from(&self.path)?;
+        config.validate()?;
+
+        // the new config can point to other files (e.g. another archive), they are watched from now on
+        if let Ok(mut last_modified) = self.last_modified.lock() {
+            *last_modified = snapshot(layer_files(&self.path, &config), &before);
+        }
+
+        let config = Arc::new(config);
+        self.sender.send_replace(config.clone());
+
+        Ok(config)
+    }
+
+    /// Check the modification date of every config file at each interval, and reload the config when one changes
+    ///
+    /// The watched files are the base file, its environment and secrets layers, the `KADY_*_FILE` secrets,
+    /// the archive and its key file
+    pub fn watch(&self, interval: Duration) {
+        let service = self.clone();
+
+        supervisor::spawn_loop("config_watcher", RestartPolicy::default(), move || {
+            let service = service.clone();
+            async move {
+                loop {
+                    tokio::time::sleep(interval).await;
+
+                    let changed = match service.last_modified.lock() {
+                        Ok(last_modified) => last_modified.iter().any(|(path, time)| modified(path) != *time),
+                        Err(_) => false
+                    };
+
+                    if !changed { continue; }
+
+                    match service.reload() {
+                        Ok(_) => info!(target: "ConfigService", "The config file {:?} was reloaded", service.path),
+                        Err(e) => error!(target: "ConfigService", "The config file {:?} changed but cannot be loaded, the current config is kept: {e:?}", service.path)
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Get every file the given config was read from
+fn layer_files(path: &Path, config: &Config) -> Vec<PathBuf> {
+    let env = std::env::var(layers::ENV_NAME).ok();
+    let mut files = layers::files(path, env.as_deref(), std::env::vars());
+
+    if !config.security.archive_path.is_empty() {
+        files.push(PathBuf::from(&config.security.archive_path));
+    }
+    if let Some(key_file) = &config.security.archive_key_file {
+        files.push(PathBuf::from(key_file));
+    }
+
+    files
+}
+
+/// Get the modification date of each file, the dates already known in `previous` are kept
+fn snapshot(files: Vec<PathBuf>, previous: &[(PathBuf, Option<SystemTime>)]) -> Snapshot {
+    files.into_iter()
+        .map(|file| {
+            let time = match previous.iter().find(|(path, _)| *path == file) {
+                Some((_, time)) => *time,
+                None => modified(&file)
+            };
+            (file, time)
+        })
+        .collect()
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
@@ -0,0 +1,165 @@
+use std::path::Path;
+use error::{ConfigError, ConfigProblem, Error, Result};
+use crate::Config;
+
+impl Config {
+    /// Check the values that cannot be checked by the deserialization
+    ///
+    /// Every problem found is returned at once in `ConfigError::InvalidConfig`
+    pub fn validate(&self) -> Result<()> {
+        let problems = self.problems();
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Config(ConfigError::InvalidConfig(problems)))
+        }
+    }
+
+    /// Get every problem of the config
+    pub fn problems(&self) -> Vec<ConfigProblem> {
+        let mut problems = Vec::new();
+
+        // paths
+        if !Path::new(&self.langs).is_dir() {
+            problems.push(ConfigProblem::new("langs", format!("the directory '{}' does not exist", self.langs)));
+        } else if !Path::new(&self.langs).join(&self.default_lang).is_dir() {
+            problems.push(ConfigProblem::new("default_lang", format!("there is no '{}' language in '{}'", self.default_lang, self.langs)));
+        }
+        if !Path::new(&self.dynamic_requests).is_file() {
+            problems.push(ConfigProblem::new("dynamic_requests", format!("the file '{}' does not exist", self.dynamic_requests)));
+        }
+        if let Some(parent) = Path::new(&self.pid).parent() {
+            if !parent.as_os_str().is_empty() && !parent.is_dir() {
+                problems.push(ConfigProblem::new("pid", format!("the directory '{}' does not exist", parent.display())));
+            }
+        }
+
+        // security
+        if self.security.discord_token.trim().is_empty() {
+            problems.push(ConfigProblem::new("security.discord_token", "the token is empty, set it in the archive or with KADY_SECURITY__DISCORD_TOKEN"));
+        }
+
+        // api
+        if self.api.retry_limit == 0 {
+            problems.push(ConfigProblem::new("api.retry_limit", "must be greater than 0"));
+        }
+        for (i, (name, path, _)) in self.api.declared_files.iter().enumerate() {
+            if !Path::new(path).is_file() {
+                problems.push(ConfigProblem::new(format!("api.declared_files[{i}]"), format!("the file '{path}' declared as '{name}' does not exist")));
+            }
+        }
+
+        // status
+        if self.status.alternate.is_empty() {
+            problems.push(ConfigProblem::new("status.alternate", "must contain at least one activity"));
+        }
+        if self.status.interval == 0 {
+            problems.push(ConfigProblem::new("status.interval", "must be greater than 0"));
+        }
+
+        // client
+        let channels = [
+            ("client.guild_add_channel", self.client.guild_add_channel.as_ref()),
+            ("client.guild_remove_channel", self.client.guild_remove_channel.as_ref()),
+            ("client.suggestion_channel", Some(&self.client.suggestion_channel)),
+            ("client.issue_channel", Some(&self.client.issue_channel)),
+            ("client.review_channel", Some(&self.client.review_channel))
+        ];
+        for (field, channel) in channels {
+            if let Some(channel) = channel {
+                if !is_snowflake(channel) {
+                    problems.push(ConfigProblem::new(field, format!("'{channel}' is not a valid channel ID")));
+                }
+            }
+        }
+
+        // database
+        if self.database.username.is_empty() {
+            problems.push(ConfigProblem::new("database.username", "the username is empty, set it in the archive or with KADY_DATABASE__USERNAME"));
+        }
+        if self.database.port == 0 {
+            problems.push(ConfigProblem::new("database.port", "must be greater than 0"));
+        }
+        if self.database.pool.max_connections == 0 {
+            problems.push(ConfigProblem::new("database.pool.max_connections", "must be greater than 0"));
+        }
+        if self.database.pool.min_connections > self.database.pool.max_connections {
+            problems.push(ConfigProblem::new("database.pool.min_connections", "cannot be greater than 'max_connections'"));
+        }
+        if self.database.pool.reconnect_base_delay == 0 {
+            problems.push(ConfigProblem::new("database.pool.reconnect_base_delay", "must be greater than 0"));
+        }
+        if self.database.pool.reconnect_max_delay < self.database.pool.reconnect_base_delay {
+            problems.push(ConfigProblem::new("database.pool.reconnect_max_delay", "cannot be lower than 'reconnect_base_delay'"));
+        }
+        if self.database.cleaner.batch_size == 0 {
+            problems.push(ConfigProblem::new("database.cleaner.batch_size", "must be greater than 0"));
+        }
+        if self.database.cleaner.user_lifetime <= 0 {
+            problems.push(ConfigProblem::new("database.cleaner.user_lifetime", "must be greater than 0"));
+        }
+        if self.database.cleaner.guild_lifetime <= 0 {
+            problems.push(ConfigProblem::new("database.cleaner.guild_lifetime", "must be greater than 0"));
+        }
+
+        problems
+    }
+}
+
+/// A Discord snowflake is a 64 bits integer, written in decimal
+fn is_snowflake(id: &str) -> bool {
+    !id.is_empty() && id.parse::<u64>().is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Config;
+
+    fn config() -> Config {
+        let mut config: Config = toml::from_str(include_str!("../../../config/config.toml")).unwrap();
+
+        let root = concat!(env!("CARGO_MANIFEST_DIR"), "/../..");
+        config.langs = format!("{root}/langs");
+        config.dynamic_requests = format!("{root}/requests.toml");
+        config.pid = format!("{root}/pid");
+        config.api.declared_files.clear();
+        config.security.discord_token = "token".into();
+        config.database.username = "kady".into();
+
+        config
+    }
+
+    #[test]
+    fn valid_config_has_no_problem() {
+        assert_eq!(config().problems(), vec![]);
+    }
+
+    #[test]
+    fn every_problem_is_reported() {
+        let mut config = config();
+        config.status.alternate.clear();
+        config.client.issue_channel = "not an id".into();
+        config.langs = "/this/path/does/not/exist".into();
+
+        let fields = config.problems().into_iter().map(|p| p.field).collect::<Vec<String>>();
+
+        assert_eq!(fields, vec!["langs", "status.alternate", "client.issue_channel"]);
+    }
+
+    #[test]
+    fn reconnect_delays_are_checked() {
+        let mut config = config();
+        config.database.pool.reconnect_base_delay = 0;
+        config.database.pool.reconnect_max_delay = 0;
+
+        let fields = config.problems().into_iter().map(|p| p.field).collect::<Vec<String>>();
+        assert_eq!(fields, vec!["database.pool.reconnect_base_delay"]);
+
+        config.database.pool.reconnect_base_delay = 10;
+        config.database.pool.reconnect_max_delay = 5;
+
+        let fields = config.problems().into_iter().map(|p| p.field).collect::<Vec<String>>();
+        assert_eq!(fields, vec!["database.pool.reconnect_max_delay"]);
+    }
+}
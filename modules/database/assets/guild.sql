@@ -17,7 +17,8 @@ CREATE OR REPLACE TABLE guilds
 (
     id                    VARCHAR(32) NOT NULL,
     tos_accepted          BOOLEAN NOT NULL DEFAULT FALSE,
-    lang                  VARCHAR(2) NOT NULL DEFAULT 'fr',
+    # The language chosen by the guild, NULL to use the locale of the guild given by Discord
+    lang                  VARCHAR(2) DEFAULT NULL,
     # If the bot will join every threads
     join_threads          BOOLEAN NOT NULL DEFAULT TRUE,
     # Sapphire, aka premium
@@ -0,0 +1,18 @@
+# Bring a database created before the transactional cookie operations up to date.
+# The migrations are applied by hand, in order, on the databases that weren't created from the current schema.
+
+# Used to remove exactly the cookies that were selected by a transaction
+ALTER TABLE user_cookies
+    ADD COLUMN IF NOT EXISTS id BIGINT UNSIGNED NOT NULL AUTO_INCREMENT FIRST,
+    ADD PRIMARY KEY (id);
+
+CREATE TABLE IF NOT EXISTS user_cookie_operations
+(
+    idempotency_key VARCHAR(128) NOT NULL,
+    operation       VARCHAR(32)  NOT NULL,
+    user            VARCHAR(32)  NOT NULL,
+    timestamp       DATETIME     NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (idempotency_key),
+
+    FOREIGN KEY (user) REFERENCES users (id) ON DELETE CASCADE
+);
@@ -0,0 +1,7 @@
+# Keep the cookies given by a user who erased their data, without their sender.
+# `user_cookies_ibfk_1` is the name generated for the first foreign key of the table, on `user_from`.
+ALTER TABLE user_cookies DROP FOREIGN KEY IF EXISTS user_cookies_ibfk_1;
+
+ALTER TABLE user_cookies
+    MODIFY user_from VARCHAR(32) DEFAULT NULL,
+    ADD CONSTRAINT user_cookies_ibfk_1 FOREIGN KEY (user_from) REFERENCES users (id) ON DELETE SET NULL;
@@ -0,0 +1,12 @@
+# Create the registry of the error broadcaster on a database created before it.
+CREATE TABLE IF NOT EXISTS system_errors (
+    fingerprint CHAR(64) PRIMARY KEY NOT NULL,
+    code CHAR(5),
+    code_path VARCHAR(255) NOT NULL,
+    occurrences INT UNSIGNED NOT NULL DEFAULT 1,
+    first_seen DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_seen DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    resolved BOOLEAN NOT NULL DEFAULT false,
+    # The JSON of the last report
+    last_report TEXT NOT NULL
+);
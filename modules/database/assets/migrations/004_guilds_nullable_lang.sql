@@ -0,0 +1,4 @@
+# The guilds that didn't choose a language use the locale given by Discord.
+# Every stored language was the old default, none was chosen by a guild, so they're all cleared.
+ALTER TABLE guilds MODIFY lang VARCHAR(2) DEFAULT NULL;
+UPDATE guilds SET lang = NULL;
@@ -1,28 +1,42 @@
-DROP TABLE IF EXISTS cookies_user_quiz;
-DROP TABLE IF EXISTS cookies_quiz_answers;
-DROP TABLE IF EXISTS cookies_quiz_questions;
-
-CREATE OR REPLACE TABLE cookies_quiz_questions (
-    id CHAR(36) PRIMARY KEY NOT NULL,
-    category VARCHAR(24) NOT NULL
-);
-
-CREATE OR REPLACE TABLE cookies_quiz_answers (
-    id CHAR(36) NOT NULL,
-    answer VARCHAR(254) NOT NULL,
-    
-    FOREIGN KEY (id) REFERENCES cookies_quiz_questions (id)
-);
-
-
-# Used to store what question the user was given
-CREATE OR REPLACE TABLE cookies_user_quiz (
-    id CHAR(36) NOT NULL,
-    user VARCHAR(32) NOT NULL,
-    date DATE NOT NULL DEFAULT CURDATE(),
-    completed BOOLEAN NOT NULL DEFAULT false,
-
-    PRIMARY KEY (id, user),
-    FOREIGN KEY (id) REFERENCES cookies_quiz_questions (id),
-    FOREIGN KEY (user) REFERENCES users(id)
-);
\ No newline at end of file
+DROP TABLE IF EXISTS cookies_user_quiz;
+DROP TABLE IF EXISTS cookies_quiz_answers;
+DROP TABLE IF EXISTS cookies_quiz_questions;
+
+CREATE OR REPLACE TABLE cookies_quiz_questions (
+    id CHAR(36) PRIMARY KEY NOT NULL,
+    category VARCHAR(24) NOT NULL
+);
+
+CREATE OR REPLACE TABLE cookies_quiz_answers (
+    id CHAR(36) NOT NULL,
+    answer VARCHAR(254) NOT NULL,
+    
+    FOREIGN KEY (id) REFERENCES cookies_quiz_questions (id)
+);
+
+
+# Used to store what question the user was given
+CREATE OR REPLACE TABLE cookies_user_quiz (
+    id CHAR(36) NOT NULL,
+    user VARCHAR(32) NOT NULL,
+    date DATE NOT NULL DEFAULT CURDATE(),
+    completed BOOLEAN NOT NULL DEFAULT false,
+
+    PRIMARY KEY (id, user),
+    FOREIGN KEY (id) REFERENCES cookies_quiz_questions (id),
+    FOREIGN KEY (user) REFERENCES users(id)
+);
+
+# The errors reported by the error broadcaster, grouped by fingerprint
+# The fingerprint is the SHA-256 of the code path without its line, the error code and the error kind
+CREATE OR REPLACE TABLE system_errors (
+    fingerprint CHAR(64) PRIMARY KEY NOT NULL,
+    code CHAR(5),
+    code_path VARCHAR(255) NOT NULL,
+    occurrences INT UNSIGNED NOT NULL DEFAULT 1,
+    first_seen DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_seen DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    resolved BOOLEAN NOT NULL DEFAULT false,
+    # The JSON of the last report
+    last_report TEXT NOT NULL
+);
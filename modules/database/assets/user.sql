@@ -1,7 +1,8 @@
 DROP TABLE IF EXISTS `user_badges`;
 DROP TABLE IF EXISTS `user_xp`;
-DROP TABLE IF EXISTS `users_marriage`;
+DROP TABLE IF EXISTS `user_marriages`;
 DROP TABLE IF EXISTS `user_biography`;
+DROP TABLE IF EXISTS user_cookie_operations;
 DROP TABLE IF EXISTS user_cookies;
 DROP TABLE IF EXISTS `users`;
 
@@ -39,7 +40,7 @@ CREATE OR REPLACE TABLE `user_badges`
 );
 
 # Contain each marriage between users.
-CREATE OR REPLACE TABLE `users_marriage`
+CREATE OR REPLACE TABLE `user_marriages`
 (
     user1      VARCHAR(32),
     user2      VARCHAR(32),
@@ -67,16 +68,20 @@ CREATE OR REPLACE TABLE `user_biography`
 # Contain each cookie given by a user to another.
 CREATE OR REPLACE TABLE user_cookies
 (
+    # Used to remove exactly the cookies that were selected by a transaction
+    id        BIGINT UNSIGNED NOT NULL AUTO_INCREMENT,
     # The user_from is the user that give the reputation.
-    user_from VARCHAR(32) NOT NULL,
+    # Set to NULL when the user erases their data, the cookie is kept for the receiver.
+    user_from VARCHAR(32),
     # The user_to is the user that receive the reputation.
     user_to   VARCHAR(32) NOT NULL,
     guild     VARCHAR(32),
     # The timestamp is used primarily for the reputation cooldown.
     timestamp DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
 
-    FOREIGN KEY (user_from) REFERENCES users (id) ON DELETE CASCADE,
+    FOREIGN KEY (user_from) REFERENCES users (id) ON DELETE SET NULL,
     FOREIGN KEY (user_to) REFERENCES users (id) ON DELETE CASCADE,
+    PRIMARY KEY (id),
     # The user_from and user_to must be different.
     CHECK (user_from != user_to)
 );
@@ -91,6 +96,19 @@ CREATE OR REPLACE TABLE user_cookie_nuggets
     FOREIGN KEY (user) REFERENCES users (id) ON DELETE CASCADE
 );
 
+# Contain the idempotency key of each transactional cookie or nugget operation.
+# An operation whose key is already registered is not applied twice.
+CREATE OR REPLACE TABLE user_cookie_operations
+(
+    idempotency_key VARCHAR(128) NOT NULL,
+    operation       VARCHAR(32)  NOT NULL,
+    user            VARCHAR(32)  NOT NULL,
+    timestamp       DATETIME     NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (idempotency_key),
+
+    FOREIGN KEY (user) REFERENCES users (id) ON DELETE CASCADE
+);
+
 
 
 
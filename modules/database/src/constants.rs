@@ -1,5 +1,12 @@
-/// Define the time a user can be stored in the database after leaving all guilds
+/// Define the time a user can be stored in the database after their last activity
 ///
-/// Is 14 days
+/// Is 14 days, the database cleaner uses `database.cleaner.user_lifetime` instead
 pub const USER_LIFETIME: i64 = 60 * 60 * 24 * 14;
+/// Define the time a guild can be stored in the database after its last activity
+///
+/// Is 14 days, the database cleaner uses `database.cleaner.guild_lifetime` instead
 pub const GUILD_LIFETIME: i64 = 60 * 60 * 24 * 14;
+/// Define the time an idempotency key of a cookie operation is kept, an operation can be retried during this time
+///
+/// Is 7 days
+pub const OPERATION_LIFETIME: i64 = 60 * 60 * 24 * 7;
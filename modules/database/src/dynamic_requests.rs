@@ -24,6 +24,7 @@ pub struct UserRequests {
     pub update_last_edited_timestamp: String,
     pub marriage: UserMarriage,
     pub cookies: UserCookies,
+    pub data: UserPersonalData,
 }
 
 /// Contain all requests for the marriage table
@@ -32,6 +33,17 @@ pub struct UserMarriage {
     pub get: String
 }
 
+/// Contain all requests used to export the personal data of a user
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UserPersonalData {
+    pub get_cookies_given: String,
+    pub get_nuggets: String,
+    pub get_xp: String,
+    pub get_quiz: String,
+    pub get_badges: String,
+    pub get_operations: String
+}
+
 /// Contain all requests for the reputation table
 #[derive(Serialize, Deserialize, Debug)]
 pub struct UserCookies {
@@ -52,7 +64,13 @@ pub struct UserCookies {
     pub decrease_nuggets: String,
     pub increase_nuggets: String,
 
-    pub remove_cookie: String
+    pub remove_cookie: String,
+
+    // transactional operations
+    pub register_operation: String,
+    pub lock_last_cookies: String,
+    pub remove_cookie_by_id: String,
+    pub lock_nuggets: String,
 }
 
 
@@ -69,6 +87,8 @@ pub struct GuildRequests {
     pub ensure: String,
     pub create: String,
     pub ensure_get: String,
+    /// Get the language chosen by the guild
+    pub get_lang: String,
     pub update_last_seen: String,
     pub update_last_edited_timestamp: String,
     pub delete: String,
@@ -116,7 +136,9 @@ pub struct GuildAutoRole {
 /// Contain all requests required by the app
 #[derive(Serialize, Deserialize, Debug)]
 pub struct SystemRequests {
-    pub quiz: SystemQuiz
+    pub quiz: SystemQuiz,
+    pub errors: SystemErrors,
+    pub cleaner: SystemCleaner
 }
 
 
@@ -134,6 +156,32 @@ pub struct SystemQuiz {
     pub clear_users: String,
 }
 
+/// Contain all requests used by the error registry
+///
+/// `get` and `resolve` accept a prefix of the fingerprint
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SystemErrors {
+    pub record: String,
+    pub get: String,
+    pub list_unresolved: String,
+    pub resolve: String,
+}
+
+/// Contain all requests used to purge the stale users and guilds
+///
+/// `delete_user` and `delete_guild` are executed in order, in a single transaction,
+/// with the ID as the only parameter
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SystemCleaner {
+    pub count_expired_users: String,
+    pub get_expired_users: String,
+    pub delete_user: Vec<String>,
+    pub prune_cookie_operations: String,
+    pub count_expired_guilds: String,
+    pub get_expired_guilds: String,
+    pub delete_guild: Vec<String>,
+}
+
 
 
 
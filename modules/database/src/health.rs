@@ -0,0 +1,65 @@
+use std::time::Duration;
+use log::{error, info, warn};
+use client::supervisor::{self, RestartPolicy};
+use crate::Database;
+
+/// Check the database periodically, and reconnect it with an exponential backoff while it's down
+pub fn spawn_health_monitor(database: Database) {
+    supervisor::spawn_loop("database_health", RestartPolicy::default(), move || {
+        let database = database.clone();
+        async move {
+            let config = database.pool_config().clone();
+            let interval = Duration::from_secs(config.health_check_interval.max(1));
+            let base = Duration::from_secs(config.reconnect_base_delay.max(1));
+            let max = Duration::from_secs(config.reconnect_max_delay);
+
+            loop {
+                if !database.is_available() || database.health_check().await.is_err() {
+                    error!(target: "DatabaseHealth", "The database is unavailable, trying to reconnect");
+
+                    let mut attempt = 0;
+                    loop {
+                        tokio::time::sleep(backoff_delay(attempt, base, max)).await;
+
+                        match database.reconnect().await {
+                            Ok(_) => {
+                                info!(target: "DatabaseHealth", "The database is available again after {} attempts", attempt + 1);
+                                break;
+                            }
+                            Err(e) => warn!(target: "DatabaseHealth", "Reconnection attempt {} failed: {e:?}", attempt + 1)
+                        }
+
+                        attempt = attempt.saturating_add(1);
+                    }
+                }
+
+                tokio::time::sleep(interval).await;
+            }
+        }
+    });
+}
+
+/// Get the delay before the given reconnection attempt: `base * 2^attempt`, capped to `max`
+pub fn backoff_delay(attempt: u32, base: Duration, max: Duration) -> Duration {
+    base.checked_mul(2u32.saturating_pow(attempt))
+        .unwrap_or(max)
+        .min(max)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use super::backoff_delay;
+
+    #[test]
+    fn backoff_doubles_until_max() {
+        let base = Duration::from_secs(1);
+        let max = Duration::from_secs(60);
+
+        assert_eq!(backoff_delay(0, base, max), Duration::from_secs(1));
+        assert_eq!(backoff_delay(1, base, max), Duration::from_secs(2));
+        assert_eq!(backoff_delay(5, base, max), Duration::from_secs(32));
+        assert_eq!(backoff_delay(6, base, max), max);
+        assert_eq!(backoff_delay(u32::MAX, base, max), max);
+    }
+}
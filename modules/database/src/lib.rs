@@ -1,22 +1,31 @@
 pub mod model;
 mod constants;
+mod transaction;
 pub mod dynamic_requests;
+pub mod health;
 
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 use serde_json::Value;
 use sqlx::{MySql, MySqlPool};
+use sqlx::mysql::{MySqlConnectOptions, MySqlPoolOptions};
 use sqlx::pool::PoolConnection;
 use client::typemap::Type;
 use error::{DatabaseError, Error, Result};
 use tokio::sync::{RwLock, RwLockReadGuard};
-use config::Config;
+use config::{Config, PoolConfig};
 
 #[derive(Clone, Debug)]
 pub struct Database {
     pool: Arc<RwLock<MySqlPool>>,
-    requests: Arc<RwLock<dynamic_requests::DynamicRequest>>
+    requests: Arc<RwLock<dynamic_requests::DynamicRequest>>,
+    options: MySqlConnectOptions,
+    pool_config: PoolConfig,
+    /// Set to false by the health monitor while the database is down
+    available: Arc<AtomicBool>
 }
 
 impl Type for Database {
@@ -24,9 +33,19 @@ impl Type for Database {
 }
 
 impl Database {
+    /// Create the pool and check that the database can be reached
+    ///
+    /// If the database is down, the client is still created but marked as unavailable,
+    /// the health monitor will reconnect it as soon as possible
     pub async fn connect(config: &Config) -> Result<Self> {
-        Ok(Self {
-            pool: Arc::new(RwLock::new(connect(config).await?)),
+        let options = connect_options(config);
+        let pool_config = config.database.pool.clone();
+
+        let database = Self {
+            pool: Arc::new(RwLock::new(pool_options(&pool_config).connect_lazy_with(options.clone()))),
+            options,
+            pool_config,
+            available: Arc::new(AtomicBool::new(false)),
             requests: Arc::new(
                 RwLock::new(
                     dynamic_requests::DynamicRequest::from_file(
@@ -34,7 +53,13 @@ impl Database {
                     )?
                 )
             )
-        })
+        };
+
+        if let Err(e) = database.health_check().await {
+            log::error!(target: "Database", "The database cannot be reached, the client will try to reconnect: {e:?}");
+        }
+
+        Ok(database)
     }
 
     pub async fn update_requests(&mut self, config: &Config) -> core::result::Result<(), String> {
@@ -55,12 +80,18 @@ impl Database {
     }
 
     /// Get the pool as a reference
-    pub async fn get_pool(&self) -> RwLockReadGuard<MySqlPool> {
-        self.pool.as_ref().read().await
+    ///
+    /// Return `DatabaseError::Unavailable` while the database is down
+    pub async fn get_pool(&self) -> Result<RwLockReadGuard<'_, MySqlPool>> {
+        self.ensure_available()?;
+
+        Ok(self.pool.as_ref().read().await)
     }
 
     /// Acquire a connection from the pool
     pub async fn get_connection(&self) -> Result<PoolConnection<MySql>> {
+        self.ensure_available()?;
+
         let conn = self.pool.read().await
             .acquire()
             .await;
@@ -71,49 +102,83 @@ impl Database {
         }
     }
 
+    /// Return false while the database is down
+    pub fn is_available(&self) -> bool {
+        self.available.load(Ordering::Relaxed)
+    }
+
+    /// Return `DatabaseError::Unavailable` while the database is down
+    pub fn ensure_available(&self) -> Result<()> {
+        if self.is_available() {
+            Ok(())
+        } else {
+            Err(Error::Database(DatabaseError::Unavailable))
+        }
+    }
+
+    /// Run a simple query to check that the database can be reached, and update the availability
+    pub async fn health_check(&self) -> Result<()> {
+        let result = ping(&*self.pool.read().await).await;
+        self.available.store(result.is_ok(), Ordering::Relaxed);
+
+        result
+    }
+
+    /// Replace the pool by a new one, only if the new pool can reach the database
+    pub async fn reconnect(&self) -> Result<()> {
+        let pool = match pool_options(&self.pool_config).connect_with(self.options.clone()).await {
+            Ok(pool) => pool,
+            Err(e) => return Err(Error::Database(DatabaseError::CannotConnect(e.to_string())))
+        };
+
+        ping(&pool).await?;
+
+        let old = std::mem::replace(&mut *self.pool.write().await, pool);
+        self.available.store(true, Ordering::Relaxed);
+
+        old.close().await;
+
+        Ok(())
+    }
+
+    /// Get the settings of the pool and of the health monitor
+    pub fn pool_config(&self) -> &PoolConfig {
+        &self.pool_config
+    }
+
     /// Get the dynamic requests as a reference
     pub async fn get_requests(&self) -> RwLockReadGuard<dynamic_requests::DynamicRequest> {
         self.requests.as_ref().read().await
     }
 }
 
-/// Create a pool with the archive given
-async fn connect(config: &Config) -> Result<MySqlPool> {
-    match MySqlPool::connect(prepare_connection(config)?.as_str()).await {
-        Ok(pool) => Ok(pool),
+async fn ping(pool: &MySqlPool) -> Result<()> {
+    match sqlx::query("SELECT 1").execute(pool).await {
+        Ok(_) => Ok(()),
         Err(e) => Err(Error::Database(DatabaseError::CannotConnect(e.to_string())))
     }
 }
 
-/// Get an archive and format the connection url for the database
-fn prepare_connection(config: &Config) -> Result<String> {
-    // // get credentials
-    // let db_credentials = if let Some(v) = archive.get::<Value>("database") { v }
-    //     else { return Err(Error::Database(DatabaseError::InvalidCredentials("Field 'database' is missing in the archive".into()))) };
-    //
-    // // get each field required
-    // let username = if let Some(uname) = db_credentials.get("username") { value_to_string(uname)? }
-    //     else { return Err(Error::Database(DatabaseError::InvalidCredentials("Field 'username' is missing in the archive".into()))) };
-    //
-    // let password = if let Some(pwd) = db_credentials.get("password") { value_to_string(pwd)? }
-    //     else { return Err(Error::Database(DatabaseError::InvalidCredentials("Field 'password' is missing in the archive".into()))) };
-    //
-    // let host = if let Some(h) = db_credentials.get("host") { value_to_string(h)? }
-    //     else { return Err(Error::Database(DatabaseError::InvalidCredentials("Field 'host' is missing in the archive".into()))) };
-    //
-    // let db_name = if let Some(n) = db_credentials.get("db_name") { value_to_string(n)? }
-    //     else { return Err(Error::Database(DatabaseError::InvalidCredentials("Field 'db_name' is missing in the archive".into()))) };
-
-    Ok(
-        format!(
-            "mysql://{username}:{password}@{host}:{port}/{db_name}",
-            username = config.database.username,
-            password = config.database.password,
-            host = config.database.host,
-            port = config.database.port,
-            db_name = config.database.database_name
-        )
-    )
+fn pool_options(config: &PoolConfig) -> MySqlPoolOptions {
+    MySqlPoolOptions::new()
+        .max_connections(config.max_connections)
+        .min_connections(config.min_connections)
+        .acquire_timeout(Duration::from_secs(config.acquire_timeout))
+        .idle_timeout(config.idle_timeout.map(Duration::from_secs))
+        .max_lifetime(config.max_lifetime.map(Duration::from_secs))
+}
+
+/// Get the connection options of the database from the config
+///
+/// The credentials may come from the secrets archive, they are merged into the config when it's loaded
+fn connect_options(config: &Config) -> MySqlConnectOptions {
+    MySqlConnectOptions::new()
+        .host(config.database.host.as_str())
+        .port(config.database.port)
+        .username(config.database.username.as_str())
+        .password(config.database.password.as_str())
+        .database(config.database.database_name.as_str())
+        .statement_cache_capacity(config.database.pool.statement_cache_capacity)
 }
 
 /// Transform a JSON value into string
@@ -0,0 +1,80 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::MySqlPool;
+use error::{DatabaseError, Error, Result};
+
+/// Represent an error reported by the error broadcaster, the identical errors share the same record
+#[derive(sqlx::FromRow, Serialize, Debug)]
+pub struct ErrorRecord {
+    /// The SHA-256 of the code path without its line, the error code and the error kind
+    pub fingerprint: String,
+    /// The error code, `DDNNN`
+    pub code: Option<String>,
+    pub code_path: String,
+    /// The number of times the error was reported
+    pub occurrences: u32,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    /// Set by an administrator, cleared when the error occurs again
+    pub resolved: bool,
+    /// The JSON of the last report
+    pub last_report: String
+}
+
+impl ErrorRecord {
+    /// Insert the error, or increment its occurrences if it was already reported
+    pub async fn record(
+        pool: &MySqlPool,
+        request: &str,
+        fingerprint: &str,
+        code: Option<String>,
+        code_path: &str,
+        report: &str
+    ) -> Result<()> {
+        let query = sqlx::query(request)
+            .bind(fingerprint)
+            .bind(code)
+            .bind(code_path)
+            .bind(report);
+
+        match query.execute(pool).await {
+            Ok(_) => Ok(()),
+            Err(e) => Err(Error::Database(DatabaseError::QueryError(e.to_string())))
+        }
+    }
+
+    /// Get the last seen error whose fingerprint starts with `fingerprint`
+    pub async fn get(pool: &MySqlPool, request: &str, fingerprint: &str) -> Result<Option<Self>> {
+        let query = sqlx::query_as::<_, Self>(request)
+            .bind(fingerprint);
+
+        match query.fetch_optional(pool).await {
+            Ok(record) => Ok(record),
+            Err(e) => Err(Error::Database(DatabaseError::QueryError(e.to_string())))
+        }
+    }
+
+    /// Get at most `limit` unresolved errors, the last seen first
+    pub async fn list_unresolved(pool: &MySqlPool, request: &str, limit: u64) -> Result<Vec<Self>> {
+        let query = sqlx::query_as::<_, Self>(request)
+            .bind(limit);
+
+        match query.fetch_all(pool).await {
+            Ok(records) => Ok(records),
+            Err(e) => Err(Error::Database(DatabaseError::QueryError(e.to_string())))
+        }
+    }
+
+    /// Mark the errors whose fingerprint starts with `fingerprint` as resolved
+    ///
+    /// Return the number of resolved errors
+    pub async fn resolve(pool: &MySqlPool, request: &str, fingerprint: &str) -> Result<u64> {
+        let query = sqlx::query(request)
+            .bind(fingerprint);
+
+        match query.execute(pool).await {
+            Ok(result) => Ok(result.rows_affected()),
+            Err(e) => Err(Error::Database(DatabaseError::QueryError(e.to_string())))
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::{Executor, FromRow, MySqlPool};
 use client::models::guild::GuildId;
@@ -6,6 +6,7 @@ use client::models::Snowflake;
 use client::models::user::UserId;
 use error::{DatabaseError, Error, Result};
 use crate::constants::GUILD_LIFETIME;
+use crate::transaction::execute_all;
 
 // const GUILD_QUERY: &str = r#"SELECT
 //     guilds.*,
@@ -48,7 +49,8 @@ use crate::constants::GUILD_LIFETIME;
 pub struct Guild {
     pub id: GuildId,
     pub tos_accepted: bool,
-    pub lang: String,
+    /// The language chosen by the guild, None to use the locale of the guild given by Discord
+    pub lang: Option<String>,
     pub join_threads: bool,
     pub sapphire: bool,
 
@@ -105,6 +107,11 @@ pub struct Guild {
 }
 
 impl Guild {
+    /// Get the language chosen by the guild, or `default` if it didn't choose one
+    pub fn lang_or(&self, default: &str) -> String {
+        self.lang.clone().unwrap_or_else(|| default.to_string())
+    }
+
     /// Get a guild from the database
     pub async fn from_pool(pool: &MySqlPool, request: &str, guild: &GuildId) -> Result<Self> {
         let query = sqlx::query_as::<_, Self>(request)
@@ -126,6 +133,17 @@ impl Guild {
         }
     }
 
+    /// Get the language chosen by the guild, None if the guild isn't in the database or didn't choose a language
+    pub async fn get_lang(pool: &MySqlPool, request: &str, guild: &GuildId) -> Result<Option<String>> {
+        let query = sqlx::query_scalar::<_, Option<String>>(request)
+            .bind(guild.to_string());
+
+        match query.fetch_optional(pool).await {
+            Ok(lang) => Ok(lang.flatten()),
+            Err(e) => Err(Error::Database(DatabaseError::QueryError(e.to_string())))
+        }
+    }
+
     /// Ensure that the guild exists
     pub async fn ensure<T: ToString>(pool: &MySqlPool, request: &str, guild: T) -> Result<()> {
         let query = sqlx::query(request)
@@ -188,6 +206,36 @@ impl Guild {
 
         Ok(now - last_seen >= GUILD_LIFETIME)
     }
+
+    /// Count the guilds that weren't seen for `lifetime` seconds
+    pub async fn count_expired(pool: &MySqlPool, request: &str, lifetime: i64) -> Result<i64> {
+        let query = sqlx::query_scalar::<_, i64>(request)
+            .bind(Utc::now() - Duration::seconds(lifetime));
+
+        match query.fetch_one(pool).await {
+            Ok(count) => Ok(count),
+            Err(e) => Err(Error::Database(DatabaseError::QueryError(e.to_string())))
+        }
+    }
+
+    /// Get the IDs of at most `limit` guilds that weren't seen for `lifetime` seconds
+    pub async fn get_expired(pool: &MySqlPool, request: &str, lifetime: i64, limit: u64) -> Result<Vec<String>> {
+        let query = sqlx::query_scalar::<_, String>(request)
+            .bind(Utc::now() - Duration::seconds(lifetime))
+            .bind(limit);
+
+        match query.fetch_all(pool).await {
+            Ok(ids) => Ok(ids),
+            Err(e) => Err(Error::Database(DatabaseError::QueryError(e.to_string())))
+        }
+    }
+
+    /// Delete a guild and all of its dependent rows in a single transaction
+    ///
+    /// Each request is executed with the guild ID as the only parameter
+    pub async fn delete<T: ToString>(pool: &MySqlPool, requests: &[String], id: T) -> Result<()> {
+        execute_all(pool, requests, id.to_string().as_str()).await
+    }
 }
 
 
@@ -425,4 +473,41 @@ impl GuildAutoRole {
             Err(e) => Err(Error::Database(DatabaseError::QueryError(e.to_string())))
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use crate::model::testing::setup;
+    use super::Guild;
+
+    #[tokio::test]
+    #[ignore = "needs a MySQL database in DATABASE_URL"]
+    async fn delete_guild_runs_against_the_schema() {
+        let (pool, requests) = setup().await;
+        Guild::ensure(&pool, requests.guilds.ensure.as_str(), "purge_guild").await.unwrap();
+        sqlx::query("INSERT IGNORE INTO `users` (`id`) VALUES ('purge_member');").execute(&pool).await.unwrap();
+
+        for request in [
+            "INSERT INTO `guild_config_ghostping` (`guild_id`) VALUES (?);",
+            "INSERT INTO `guild_config_join` (`guild_id`) VALUES (?);",
+            "INSERT INTO `guild_config_leave` (`guild_id`) VALUES (?);",
+            "INSERT INTO `guild_config_logs` (`guild_id`) VALUES (?);",
+            "INSERT INTO `guild_config_suggestions` (`guild_id`) VALUES (?);",
+            "INSERT INTO `guild_config_xp` (`guild_id`) VALUES (?);",
+            "INSERT INTO `guild_config_captcha` (`guild_id`) VALUES (?);",
+            "INSERT INTO `guild_config_auto_roles` (`guild_id`) VALUES (?);",
+            "INSERT INTO `guild_config_citation` (`guild_id`) VALUES (?);",
+            "INSERT INTO `guild_auto_roles` (`guild_id`, `role_id`) VALUES (?, 'role');",
+            "INSERT INTO `guild_users_xp` (`guild_id`, `user_id`) VALUES (?, 'purge_member');",
+            // not in `delete_guild`, removed by its foreign key
+            "INSERT INTO `guild_logs` (`guild_id`, `channel`, `log_type`) VALUES (?, 'channel', 'join');"
+        ] {
+            sqlx::query(request).bind("purge_guild").execute(&pool).await.unwrap();
+        }
+
+        Guild::delete(&pool, &requests.system.cleaner.delete_guild, "purge_guild").await.unwrap();
+
+        let remaining: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM `guilds` WHERE `id` = 'purge_guild';")
+            .fetch_one(&pool).await.unwrap();
+        assert_eq!(remaining, 0);
+    }
+}
@@ -1,2 +1,37 @@
 pub mod users;
-pub mod guild;
\ No newline at end of file
+pub mod guild;
+pub mod errors;
+
+#[cfg(test)]
+pub(crate) mod testing {
+    use std::path::PathBuf;
+    use sqlx::{Executor, MySqlPool};
+    use tokio::sync::OnceCell;
+    use crate::dynamic_requests::DynamicRequest;
+
+    static SCHEMA: OnceCell<()> = OnceCell::const_new();
+
+    /// Connect to the database in `DATABASE_URL`, create the real schema once and load the requests
+    pub(crate) async fn setup() -> (MySqlPool, DynamicRequest) {
+        let url = std::env::var("DATABASE_URL").expect("DATABASE_URL must point to a disposable database");
+        let pool = MySqlPool::connect(url.as_str()).await.unwrap();
+
+        SCHEMA.get_or_init(|| async {
+            let assets = PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/assets"));
+            let mut conn = pool.acquire().await.unwrap();
+
+            conn.execute("SET FOREIGN_KEY_CHECKS = 0;").await.unwrap();
+            for file in ["user.sql", "guild.sql", "system.sql"] {
+                let schema = std::fs::read_to_string(assets.join(file)).unwrap();
+                conn.execute(schema.as_str()).await.unwrap();
+            }
+            conn.execute("SET FOREIGN_KEY_CHECKS = 1;").await.unwrap();
+        }).await;
+
+        let requests = DynamicRequest::from_file(
+            PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/../../requests.toml"))
+        ).unwrap();
+
+        (pool, requests)
+    }
+}
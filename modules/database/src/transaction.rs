@@ -0,0 +1,55 @@
+//! Helpers to run several queries inside a single transaction
+
+use sqlx::{MySql, MySqlPool, Transaction};
+use error::{DatabaseError, Error, Result};
+
+/// Start a new transaction
+pub(crate) async fn begin(pool: &MySqlPool) -> Result<Transaction<'static, MySql>> {
+    match pool.begin().await {
+        Ok(tx) => Ok(tx),
+        Err(e) => Err(Error::Database(DatabaseError::TransactionError(e.to_string())))
+    }
+}
+
+/// Commit a transaction
+pub(crate) async fn commit(tx: Transaction<'static, MySql>) -> Result<()> {
+    match tx.commit().await {
+        Ok(_) => Ok(()),
+        Err(e) => Err(Error::Database(DatabaseError::TransactionError(e.to_string())))
+    }
+}
+
+/// Rollback a transaction
+pub(crate) async fn rollback(tx: Transaction<'static, MySql>) -> Result<()> {
+    match tx.rollback().await {
+        Ok(_) => Ok(()),
+        Err(e) => Err(Error::Database(DatabaseError::TransactionError(e.to_string())))
+    }
+}
+
+/// Execute a query inside a transaction
+pub(crate) async fn execute<'q>(
+    tx: &mut Transaction<'static, MySql>,
+    query: sqlx::query::Query<'q, MySql, sqlx::mysql::MySqlArguments>
+) -> Result<u64> {
+    match query.execute(&mut **tx).await {
+        Ok(r) => Ok(r.rows_affected()),
+        Err(e) => Err(Error::Database(DatabaseError::QueryError(e.to_string())))
+    }
+}
+
+/// Execute each request with the same single parameter inside one transaction
+///
+/// The transaction is rolled back if any request fails
+pub(crate) async fn execute_all(pool: &MySqlPool, requests: &[String], param: &str) -> Result<()> {
+    let mut tx = begin(pool).await?;
+
+    for request in requests {
+        if let Err(e) = execute(&mut tx, sqlx::query(request.as_str()).bind(param)).await {
+            rollback(tx).await?;
+            return Err(e);
+        }
+    }
+
+    commit(tx).await
+}
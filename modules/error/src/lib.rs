@@ -1,6 +1,10 @@
 use std::fmt::{Display, Formatter};
 use serde::{Deserialize, Serialize};
 
+mod report;
+
+pub use report::{ErrorCode, ErrorKind, ErrorReport};
+
 /// Will be used each time an error can occur
 pub type Result<T> = core::result::Result<T, Error>;
 
@@ -15,7 +19,46 @@ pub enum Error {
     Archive(ArchiveError),
     Database(DatabaseError),
     Model(ModelError),
-    Runtime(RuntimeError)
+    Runtime(RuntimeError),
+    /// An error with its code and its context, see [`ErrorReport`]
+    Report(ErrorReport)
+}
+
+impl Error {
+    /// The code of the error, the errors of the modules only have the code of their domain
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Error::Gateway(_) => ErrorCode::new(91, 0),
+            Error::Api(_) => ErrorCode::new(92, 0),
+            Error::Event(_) => ErrorCode::new(93, 0),
+            Error::Config(_) => ErrorCode::new(94, 0),
+            Error::Fs(_) => ErrorCode::new(95, 0),
+            Error::Archive(_) => ErrorCode::new(96, 0),
+            Error::Database(_) => ErrorCode::new(97, 0),
+            Error::Model(_) => ErrorCode::new(98, 0),
+            Error::Runtime(_) => ErrorCode::new(99, 0),
+            Error::Report(report) => report.code
+        }
+    }
+
+    /// What went wrong from the point of view of the user
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Error::Database(DatabaseError::Unavailable) => ErrorKind::DatabaseUnavailable,
+            Error::Report(report) => report.kind,
+            _ => ErrorKind::Internal
+        }
+    }
+
+    /// The key of the message shown to the user
+    pub fn translation_key(&self) -> &'static str {
+        self.kind().translation_key()
+    }
+
+    /// Wrap the error in a report with the given code
+    pub fn report(self, code: ErrorCode) -> ErrorReport {
+        ErrorReport::new(code).with_source(self)
+    }
 }
 
 impl From<serde_json::error::Error> for Error {
@@ -26,11 +69,29 @@ impl From<serde_json::error::Error> for Error {
 
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{self:?}")
+        match self {
+            Error::Gateway(e) => write!(f, "[{}] Gateway: {e:?}", self.code()),
+            Error::Api(e) => write!(f, "[{}] Api: {e:?}", self.code()),
+            Error::Event(e) => write!(f, "[{}] Event: {e:?}", self.code()),
+            Error::Config(e) => write!(f, "[{}] Config: {e:?}", self.code()),
+            Error::Fs(e) => write!(f, "[{}] Fs: {e:?}", self.code()),
+            Error::Archive(e) => write!(f, "[{}] Archive: {e:?}", self.code()),
+            Error::Database(e) => write!(f, "[{}] Database: {e:?}", self.code()),
+            Error::Model(e) => write!(f, "[{}] Model: {e:?}", self.code()),
+            Error::Runtime(e) => write!(f, "[{}] Runtime: {e:?}", self.code()),
+            Error::Report(report) => write!(f, "{report}")
+        }
     }
 }
 
-impl std::error::Error for Error {}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Report(report) => std::error::Error::source(report),
+            _ => None
+        }
+    }
+}
 
 /// Represent an error that can occur while the runtime is active
 #[derive(Debug, Serialize, Deserialize)]
@@ -84,6 +145,14 @@ pub enum ModelError {
     MissingField(String),
     InvalidPayload(String),
     InvalidTimestamp(String),
+    /// The custom_id is malformed or belongs to another component
+    InvalidCustomId(String),
+    /// The custom_id was encoded by an older version of the component
+    OutdatedCustomId(String),
+    /// The signature of the custom_id is missing or doesn't match
+    InvalidSignature(String),
+    /// Discord refuses the custom_ids longer than 100 characters
+    CustomIdTooLong(String),
 }
 
 /// Represent an error that can occur inside the config system
@@ -91,10 +160,31 @@ pub enum ModelError {
 pub enum ConfigError {
     InvalidFile(String),
     CannotReadFile(String),
-    InvalidConfig(String),
+    /// Returned with every problem found while validating the config
+    InvalidConfig(Vec<ConfigProblem>),
     CannotWriteFile(String),
 }
 
+/// Represent a value of the config that cannot be used
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigProblem {
+    /// The path of the field, e.g. `status.alternate`
+    pub field: String,
+    pub reason: String
+}
+
+impl ConfigProblem {
+    pub fn new(field: impl ToString, reason: impl ToString) -> Self {
+        Self { field: field.to_string(), reason: reason.to_string() }
+    }
+}
+
+impl Display for ConfigProblem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}`: {}", self.field, self.reason)
+    }
+}
+
 /// Represent an error that can occur inside the event system
 #[derive(Debug, Serialize, Deserialize)]
 pub enum EventError {
@@ -143,6 +233,12 @@ pub enum ArchiveError {
     InvalidBody(String),
     CannotSerializeBody(String),
     InvalidBodyValue(String),
+    /// Returned when an encrypted archive is opened without a key
+    MissingKey(String),
+    /// Returned when the encryption key cannot be derived
+    InvalidKey(String),
+    /// Returned when a path of the body doesn't exist
+    MissingEntry(String),
 }
 
 /// Represent an error that can occur with the file system
@@ -170,4 +266,52 @@ pub enum DatabaseError {
     /// Returned when the connection cannot be acquired from the pool
     CannotAcquireConnection(String),
     CannotParseDynamicRequestTable(String),
-}
\ No newline at end of file
+    /// Returned when a transaction cannot be started, committed or rolled back
+    TransactionError(String),
+    /// Returned while the database is down and the client is trying to reconnect
+    Unavailable,
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{ArchiveError, DatabaseError, Error, ErrorCode, ErrorKind, ErrorReport};
+
+    #[test]
+    fn code_round_trip() {
+        let code = ErrorCode::new(1, 4);
+        assert_eq!(code.to_string(), "01004");
+        assert_eq!("01004".parse::<ErrorCode>().unwrap(), code);
+        assert!("1004".parse::<ErrorCode>().is_err());
+
+        assert_eq!(serde_json::to_string(&code).unwrap(), "\"01004\"");
+    }
+
+    #[test]
+    #[should_panic]
+    fn code_number_fits_in_three_digits() {
+        ErrorCode::new(13, 1000);
+    }
+
+    #[test]
+    fn report_chain() {
+        let inner = Error::Archive(ArchiveError::MissingEntry("database".into()))
+            .report(ErrorCode::new(96, 1))
+            .with_context("Cannot load the secrets");
+        let report = ErrorReport::new(ErrorCode::new(13, 2))
+            .with_context("Cannot start")
+            .with_source(inner.into());
+
+        assert_eq!(report.kind, ErrorKind::Internal);
+        assert_eq!(report.chain().len(), 3);
+        assert!(report.to_string().starts_with("[13002] Cannot start: [96001] Cannot load the secrets: [96000] Archive"));
+
+        let source = std::error::Error::source(&report).unwrap();
+        assert!(std::error::Error::source(source).is_some());
+    }
+
+    #[test]
+    fn kind_is_taken_from_the_source() {
+        let report = Error::Database(DatabaseError::Unavailable).report(ErrorCode::new(13, 1));
+        assert_eq!(report.translation_key(), "errors::database_unavailable");
+    }
+}
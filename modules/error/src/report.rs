@@ -0,0 +1,201 @@
+//! The structured errors shown to the users and grouped by the error broadcaster
+//!
+//! An [`ErrorReport`] carries a stable [`ErrorCode`], the [`ErrorKind`] used to pick the
+//! translated message, the context added by the callers and the error at its source.
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use serde::{Deserialize, Serialize};
+use crate::Error;
+
+/// A stable code identifying where an error occurred, written `DDNNN`
+///
+/// `DD` is the domain (e.g. `13` for the xp commands) and `NNN` the number of the error in it.
+/// The domains `91` to `99` are used by the errors of the modules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub struct ErrorCode {
+    pub domain: u8,
+    pub number: u16
+}
+
+impl ErrorCode {
+    /// Panics if the domain is above 99 or the number above 999, they wouldn't fit in `DDNNN`
+    ///
+    /// The codes are constants, so a wrong one fails the build when used in a `const`
+    pub const fn new(domain: u8, number: u16) -> Self {
+        assert!(domain <= 99, "the domain of an error code must be at most 99");
+        assert!(number <= 999, "the number of an error code must be at most 999");
+        Self { domain, number }
+    }
+}
+
+impl Display for ErrorCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:02}{:03}", self.domain, self.number)
+    }
+}
+
+impl FromStr for ErrorCode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != 5 || !s.chars().all(|c| c.is_ascii_digit()) {
+            return Err(format!("'{s}' is not an error code"));
+        }
+
+        Ok(Self::new(s[..2].parse().unwrap_or_default(), s[2..].parse().unwrap_or_default()))
+    }
+}
+
+impl From<ErrorCode> for String {
+    fn from(value: ErrorCode) -> Self {
+        value.to_string()
+    }
+}
+
+impl TryFrom<String> for ErrorCode {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+/// What went wrong from the point of view of the user, used to pick the translated message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ErrorKind {
+    Internal,
+    DatabaseUnavailable,
+    GuildData,
+    UserNotFound,
+    UserId,
+    NotGuild,
+    NotForBot,
+    NotAMember,
+    AccessDenied,
+    InvalidCustomId
+}
+
+impl ErrorKind {
+    /// The key of the message in the `errors` translation file
+    pub fn translation_key(&self) -> &'static str {
+        match self {
+            ErrorKind::Internal => "errors::internal_error",
+            ErrorKind::DatabaseUnavailable => "errors::database_unavailable",
+            ErrorKind::GuildData => "errors::cannot_get_guild_data",
+            ErrorKind::UserNotFound => "errors::cannot_acquire_user",
+            ErrorKind::UserId => "errors::cannot_get_user_id",
+            ErrorKind::NotGuild => "errors::not_guild",
+            ErrorKind::NotForBot => "errors::not_for_bot",
+            ErrorKind::NotAMember => "errors::not_a_member",
+            ErrorKind::AccessDenied => "errors::access_denied",
+            ErrorKind::InvalidCustomId => "errors::invalid_custom_id"
+        }
+    }
+}
+
+/// An error with its code, its context and its source
+///
+/// # Example
+/// ```
+/// use error::{Error, DatabaseError, ErrorCode, ErrorReport};
+///
+/// let report = ErrorReport::new(ErrorCode::new(13, 1))
+///     .with_context("Cannot get the rank of the member")
+///     .with_source(Error::Database(DatabaseError::Unavailable));
+///
+/// assert_eq!(report.code.to_string(), "13001");
+/// assert_eq!(report.translation_key(), "errors::database_unavailable");
+/// ```
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ErrorReport {
+    pub code: ErrorCode,
+    pub kind: ErrorKind,
+    /// The context added by the callers, the outermost first
+    pub context: Vec<String>,
+    pub source: Option<Box<Error>>
+}
+
+impl ErrorReport {
+    pub fn new(code: ErrorCode) -> Self {
+        Self {
+            code,
+            kind: ErrorKind::Internal,
+            context: Vec::new(),
+            source: None
+        }
+    }
+
+    pub fn with_kind(mut self, kind: ErrorKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    pub fn with_context(mut self, context: impl ToString) -> Self {
+        self.context.push(context.to_string());
+        self
+    }
+
+    /// Set the source of the report, the kind is taken from it if it's still `Internal`
+    pub fn with_source(mut self, source: Error) -> Self {
+        if self.kind == ErrorKind::Internal {
+            self.kind = source.kind();
+        }
+        self.source = Some(Box::new(source));
+        self
+    }
+
+    pub fn translation_key(&self) -> &'static str {
+        self.kind.translation_key()
+    }
+
+    /// Describe the report then each of its sources
+    pub fn chain(&self) -> Vec<String> {
+        let mut chain = self.context.clone();
+        let mut source = self.source.as_deref();
+
+        while let Some(error) = source {
+            match error {
+                Error::Report(report) => {
+                    if report.context.is_empty() {
+                        chain.push(format!("[{}]", report.code));
+                    } else {
+                        chain.push(format!("[{}] {}", report.code, report.context.join(": ")));
+                    }
+                    source = report.source.as_deref();
+                }
+                error => {
+                    chain.push(error.to_string());
+                    source = None;
+                }
+            }
+        }
+
+        chain
+    }
+}
+
+impl Display for ErrorReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let chain = self.chain();
+
+        if chain.is_empty() {
+            write!(f, "[{}]", self.code)
+        } else {
+            write!(f, "[{}] {}", self.code, chain.join(": "))
+        }
+    }
+}
+
+impl std::error::Error for ErrorReport {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source.as_deref().map(|e| e as &(dyn std::error::Error + 'static))
+    }
+}
+
+impl From<ErrorReport> for Error {
+    fn from(value: ErrorReport) -> Self {
+        Error::Report(value)
+    }
+}
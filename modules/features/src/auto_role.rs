@@ -29,7 +29,7 @@ pub async fn trigger(
 }
 
 async fn get_all_roles(database: &Database, guild_id: &GuildId) -> Result<Vec<database::model::guild::GuildAutoRole>> {
-   let pool = database.get_pool().await;
+   let pool = database.get_pool().await?;
 
     database::model::guild::GuildAutoRole::get_all(
         &pool,
@@ -6,6 +6,7 @@ use tokio::sync::RwLock;
 use tokio::task::JoinHandle;
 use tokio::time::sleep;
 use uuid::Uuid;
+use client::supervisor::{self, RestartPolicy};
 use client::typemap::Type;
 use crate::captcha::generator::Difficulty;
 use crate::captcha::generator::fonts::Font;
@@ -52,18 +53,22 @@ impl CaptchaContainer {
         timeout: Arc<RwLock<Duration>>
     ) -> JoinHandle<()>
     {
-        tokio::spawn(async move {
-            loop {
-                // we update each second, so we don't need to wait for the timeout
-                sleep(Duration::from_secs(1)).await;
-
-                let mut instances = instances.write().await;
-                let timeout = *timeout.read().await;
-
-                // remove all expired instances
-                instances.retain(|_, instance| instance.creation.elapsed() < timeout);
-
-                drop(instances)
+        supervisor::spawn_loop("captcha_cleaner", RestartPolicy::default(), move || {
+            let instances = instances.clone();
+            let timeout = timeout.clone();
+            async move {
+                loop {
+                    // we update each second, so we don't need to wait for the timeout
+                    sleep(Duration::from_secs(1)).await;
+
+                    let mut instances = instances.write().await;
+                    let timeout = *timeout.read().await;
+
+                    // remove all expired instances
+                    instances.retain(|_, instance| instance.creation.elapsed() < timeout);
+
+                    drop(instances)
+                }
             }
         })
     }
@@ -0,0 +1,167 @@
+//! Generic cooldowns for the interactions
+//!
+//! A [`Cooldown`] is a duration and a [`Scope`]: the scope tells who shares the cooldown,
+//! e.g. a cooldown per channel starts for everyone in the channel when someone uses the command.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+use client::models::channel::ChannelId;
+use client::models::guild::GuildId;
+use client::models::interaction::Interaction;
+use client::models::user::UserId;
+use client::supervisor::{self, RestartPolicy};
+use client::typemap::Type;
+
+/// The delay between two cleanings of the expired cooldowns
+const CLEANER_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Who shares a cooldown
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Scope {
+    User,
+    /// The users of a guild, or the user alone in DMs
+    Guild,
+    Channel
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Cooldown {
+    pub duration: Duration,
+    pub scope: Scope
+}
+
+impl Cooldown {
+    pub const fn per_user(seconds: u64) -> Self {
+        Self { duration: Duration::from_secs(seconds), scope: Scope::User }
+    }
+
+    pub const fn per_guild(seconds: u64) -> Self {
+        Self { duration: Duration::from_secs(seconds), scope: Scope::Guild }
+    }
+
+    pub const fn per_channel(seconds: u64) -> Self {
+        Self { duration: Duration::from_secs(seconds), scope: Scope::Channel }
+    }
+}
+
+/// The user, guild or channel a cooldown is applied to
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Subject {
+    User(UserId),
+    Guild(GuildId),
+    Channel(ChannelId)
+}
+
+impl Subject {
+    /// Get the subject of an interaction for a scope, None if the user cannot be identified
+    pub fn of(scope: Scope, interaction: &Interaction) -> Option<Self> {
+        let user = interaction.user.as_ref()
+            .or_else(|| interaction.member.as_ref().and_then(|m| m.user.as_ref()))
+            .map(|u| Subject::User(u.id.clone()));
+
+        match scope {
+            Scope::User => user,
+            Scope::Guild => interaction.guild_id.clone().map(Subject::Guild).or(user),
+            Scope::Channel => interaction.channel_id.clone().map(Subject::Channel).or(user)
+        }
+    }
+}
+
+/// The running cooldowns, mapped by the name of the interaction and the subject to the end of the cooldown
+#[derive(Debug, Default)]
+struct Cooldowns(HashMap<(String, Subject), Instant>);
+
+impl Cooldowns {
+    fn hit(&mut self, name: &str, subject: Subject, duration: Duration, now: Instant) -> Result<(), Duration> {
+        let key = (name.to_string(), subject);
+
+        match self.0.get(&key) {
+            Some(end) if *end > now => Err(*end - now),
+            _ => {
+                self.0.insert(key, now + duration);
+                Ok(())
+            }
+        }
+    }
+
+    fn clean(&mut self, now: Instant) {
+        self.0.retain(|_, end| *end > now);
+    }
+}
+
+/// This structure will contain the cooldowns of every interaction
+#[derive(Clone)]
+pub struct CooldownContainer {
+    cooldowns: Arc<RwLock<Cooldowns>>,
+    /// The task that will clean up the expired cooldowns
+    #[allow(unused)]
+    cleaner: Arc<JoinHandle<()>>
+}
+
+impl Type for CooldownContainer {
+    type Value = Self;
+}
+
+impl CooldownContainer {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        let cooldowns = Arc::new(RwLock::new(Cooldowns::default()));
+
+        Self {
+            cleaner: Arc::new(Self::cleaner_task(cooldowns.clone())),
+            cooldowns
+        }
+    }
+
+    fn cleaner_task(cooldowns: Arc<RwLock<Cooldowns>>) -> JoinHandle<()> {
+        supervisor::spawn_loop("cooldown_cleaner", RestartPolicy::default(), move || {
+            let cooldowns = cooldowns.clone();
+            async move {
+                loop {
+                    sleep(CLEANER_INTERVAL).await;
+
+                    cooldowns.write().await.clean(Instant::now());
+                }
+            }
+        })
+    }
+
+    /// Start the cooldown of an interaction for a subject, or get the time left if it is still running
+    pub async fn hit(&self, name: &str, subject: Subject, duration: Duration) -> Result<(), Duration> {
+        self.cooldowns.write().await.hit(name, subject, duration, Instant::now())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+    use client::models::Snowflake;
+    use client::models::user::UserId;
+    use crate::cooldown::{Cooldowns, Subject};
+
+    fn user(id: &str) -> Subject {
+        Subject::User(UserId(Snowflake(id.to_string())))
+    }
+
+    #[test]
+    fn cooldowns_are_per_name_and_subject() {
+        let mut cooldowns = Cooldowns::default();
+        let now = Instant::now();
+        let duration = Duration::from_secs(10);
+
+        assert_eq!(cooldowns.hit("ping", user("1"), duration, now), Ok(()));
+        assert_eq!(cooldowns.hit("ping", user("1"), duration, now + Duration::from_secs(4)), Err(Duration::from_secs(6)));
+        assert_eq!(cooldowns.hit("ping", user("2"), duration, now), Ok(()));
+        assert_eq!(cooldowns.hit("help", user("1"), duration, now), Ok(()));
+
+        // the cooldown is over
+        assert_eq!(cooldowns.hit("ping", user("1"), duration, now + duration), Ok(()));
+
+        cooldowns.clean(now + Duration::from_secs(30));
+        assert!(cooldowns.0.is_empty());
+    }
+}
@@ -3,5 +3,6 @@ pub mod captcha;
 pub mod auto_role;
 pub mod xp;
 pub mod utils;
+pub mod cooldown;
 
 pub mod coolors;
\ No newline at end of file
@@ -20,6 +20,7 @@ use client::models::channel::ChannelId;
 use client::models::guild::GuildId;
 use client::models::message::MessageBuilder;
 use client::models::user::{User, UserId};
+use client::supervisor::{self, RestartPolicy};
 use client::typemap::Type;
 use database::dynamic_requests::DynamicRequest;
 use database::model::guild::GuildUserXp;
@@ -55,14 +56,17 @@ impl XpCooldownContainer {
 
 	fn cleaner(map: Arc<RwLock<HashMap<(GuildId, UserId), Instant>>>) -> JoinHandle<()> {
 		let map_clone = map.clone();
-		tokio::spawn(async move {
-			loop {
-				// every hours
-				sleep(Duration::from_secs(60 * 60)).await;
-
-				{
-					let mut users = map_clone.write().await;
-					users.retain(|_, instant| Instant::now().duration_since(*instant) < Duration::from_secs(0));
+		supervisor::spawn_loop("xp_cooldown_cleaner", RestartPolicy::default(), move || {
+			let map_clone = map_clone.clone();
+			async move {
+				loop {
+					// every hours
+					sleep(Duration::from_secs(60 * 60)).await;
+
+					{
+						let mut users = map_clone.write().await;
+						users.retain(|_, instant| Instant::now().duration_since(*instant) < Duration::from_secs(0));
+					}
 				}
 			}
 		})
@@ -256,7 +260,7 @@ async fn send_lvl_up_message(
 ) {
 	let msg = match guild_data.xp_message.clone() {
 		Some(m) => m,
-		None => message!(guild_data.lang.clone(), "features::xp::lvl_up").to_string()
+		None => message!(guild_data.lang_or(&translation::default_language().0), "features::xp::lvl_up").to_string()
 	};
 
 	let msg_builder = MessageBuilder::new()
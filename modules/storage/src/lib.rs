@@ -0,0 +1,234 @@
+//! Write the files of Kady safely, used by the config and the archives
+//!
+//! A file is never written in place:
+//! - an advisory lock is taken on `<file>.lock`, so two processes cannot write the file together
+//! - the previous content is copied to `<file>.bak`, only readable by the owner if the file holds secrets (see [`write_private`])
+//! - the new content is written to a temporary file which is renamed over the file
+//! - the directory is synced, so the rename itself survives a crash
+//!
+//! A crash in the middle of a write leaves the old file untouched.
+
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use error::{Error, FileError, Result};
+
+/// An advisory lock on a file, released when dropped
+///
+/// The lock is taken on `<file>.lock` instead of the file itself, since the file is replaced by a rename
+pub struct FileLock {
+    file: File
+}
+
+impl FileLock {
+    /// Wait for an exclusive lock, used to write the file
+    pub fn exclusive(path: &Path) -> Result<Self> {
+        let file = open_lock_file(path)?;
+
+        match file.lock() {
+            Ok(_) => Ok(Self { file }),
+            Err(e) => Err(Error::Fs(FileError::IOError(format!("Cannot lock {path:?}: {e}"))))
+        }
+    }
+
+    /// Wait for a shared lock, used to read the file while nobody is writing it
+    pub fn shared(path: &Path) -> Result<Self> {
+        let file = open_lock_file(path)?;
+
+        match file.lock_shared() {
+            Ok(_) => Ok(Self { file }),
+            Err(e) => Err(Error::Fs(FileError::IOError(format!("Cannot lock {path:?}: {e}"))))
+        }
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+    }
+}
+
+/// Get the path of the lock file of a file
+pub fn lock_path(path: &Path) -> PathBuf {
+    with_suffix(path, "lock")
+}
+
+/// Get the path of the backup of a file
+pub fn backup_path(path: &Path) -> PathBuf {
+    with_suffix(path, "bak")
+}
+
+/// Write a file atomically, keeping the previous content in `<file>.bak`
+pub fn write(path: &Path, content: impl AsRef<[u8]>) -> Result<()> {
+    write_with(path, content.as_ref(), false)
+}
+
+/// Write a file atomically like [`write`], used for the files holding secrets
+///
+/// The file and its backup are only readable by their owner
+pub fn write_private(path: &Path, content: impl AsRef<[u8]>) -> Result<()> {
+    write_with(path, content.as_ref(), true)
+}
+
+fn write_with(path: &Path, content: &[u8], private: bool) -> Result<()> {
+    let _lock = FileLock::exclusive(path)?;
+
+    if path.exists() {
+        let backup = backup_path(path);
+        let copied = std::fs::copy(path, &backup)
+            .and_then(|_| if private { restrict_permissions(&backup) } else { Ok(()) });
+
+        if let Err(e) = copied {
+            return Err(Error::Fs(FileError::CannotWriteFile(format!("Cannot backup {path:?}: {e}"))));
+        }
+    }
+
+    let temporary = with_suffix(path, &format!("tmp.{}", std::process::id()));
+    let written = write_temporary(&temporary, content, private)
+        .and_then(|_| std::fs::rename(&temporary, path))
+        .and_then(|_| sync_parent(path));
+
+    if let Err(e) = written {
+        let _ = std::fs::remove_file(&temporary);
+        return Err(Error::Fs(FileError::CannotWriteFile(format!("Cannot write {path:?}: {e}"))));
+    }
+
+    Ok(())
+}
+
+/// Read a file, waiting for the writes in progress
+///
+/// The file is read without lock if the lock file cannot be created, e.g. on a read-only mount
+pub fn read(path: &Path) -> Result<Vec<u8>> {
+    if !path.exists() {
+        return Err(Error::Fs(FileError::NoFile(format!("{path:?} doesn't exist"))));
+    }
+
+    let _lock = FileLock::shared(path).ok();
+
+    match std::fs::read(path) {
+        Ok(content) => Ok(content),
+        Err(e) => Err(Error::Fs(FileError::CannotReadFile(format!("Cannot read {path:?}: {e}"))))
+    }
+}
+
+fn write_temporary(path: &Path, content: &[u8], private: bool) -> std::io::Result<()> {
+    let mut file = File::create(path)?;
+    if private {
+        restrict_permissions(path)?;
+    }
+    file.write_all(content)?;
+    file.sync_all()
+}
+
+/// Make a file readable and writable by its owner only
+#[cfg(unix)]
+fn restrict_permissions(path: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+}
+
+/// The permissions cannot be restricted the same way on the other platforms
+#[cfg(not(unix))]
+fn restrict_permissions(_path: &Path) -> std::io::Result<()> {
+    Ok(())
+}
+
+/// Sync the directory of the file, so its new entry is on the disk
+#[cfg(unix)]
+fn sync_parent(path: &Path) -> std::io::Result<()> {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => File::open(parent)?.sync_all(),
+        _ => File::open(".")?.sync_all()
+    }
+}
+
+/// A directory cannot be opened as a file on the other platforms
+#[cfg(not(unix))]
+fn sync_parent(_path: &Path) -> std::io::Result<()> {
+    Ok(())
+}
+
+fn open_lock_file(path: &Path) -> Result<File> {
+    let lock = lock_path(path);
+
+    match OpenOptions::new().create(true).truncate(false).read(true).write(true).open(&lock) {
+        Ok(file) => Ok(file),
+        Err(e) => Err(Error::Fs(FileError::IOError(format!("Cannot open {lock:?}: {e}"))))
+    }
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+
+#[cfg(test)]
+mod test {
+    use std::fs::TryLockError;
+    use std::path::{Path, PathBuf};
+    use crate::{backup_path, lock_path, read, write, write_private, FileLock};
+
+    fn temp(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("kady_storage_{}_{name}", std::process::id()))
+    }
+
+    fn clean(path: &Path) {
+        for file in [path.to_path_buf(), backup_path(path), lock_path(path)] {
+            let _ = std::fs::remove_file(file);
+        }
+    }
+
+    #[test]
+    fn write_keeps_a_backup() {
+        let path = temp("backup");
+
+        write(&path, "first").unwrap();
+        assert!(!backup_path(&path).exists());
+
+        write(&path, "second").unwrap();
+        assert_eq!(read(&path).unwrap(), b"second");
+        assert_eq!(std::fs::read(backup_path(&path)).unwrap(), b"first");
+
+        clean(&path);
+    }
+
+    #[test]
+    fn secrets_keep_a_private_backup() {
+        let path = temp("secret");
+
+        write(&path, "first").unwrap();
+        write_private(&path, "second").unwrap();
+        write_private(&path, "third").unwrap();
+        assert_eq!(read(&path).unwrap(), b"third");
+        assert_eq!(std::fs::read(backup_path(&path)).unwrap(), b"second");
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            for file in [path.clone(), backup_path(&path)] {
+                assert_eq!(std::fs::metadata(file).unwrap().permissions().mode() & 0o777, 0o600);
+            }
+        }
+
+        clean(&path);
+    }
+
+    #[test]
+    fn lock_is_exclusive() {
+        let path = temp("lock");
+
+        let lock = FileLock::exclusive(&path).unwrap();
+        let other = std::fs::File::open(lock_path(&path)).unwrap();
+        assert!(matches!(other.try_lock_shared(), Err(TryLockError::WouldBlock)));
+
+        drop(lock);
+        assert!(other.try_lock_shared().is_ok());
+
+        clean(&path);
+    }
+}
@@ -0,0 +1,198 @@
+//! Generate the typed keys of the reference language (see `src/keys.rs`)
+//!
+//! The languages are read in `KADY_LANGS_DIR` (`../../langs` by default),
+//! the reference language is `KADY_REFERENCE_LANG` (`fr` by default).
+
+#[path = "src/icu/syntax.rs"]
+mod syntax;
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use serde_json::Value;
+
+const LANGS_DIR_ENV: &str = "KADY_LANGS_DIR";
+const REFERENCE_ENV: &str = "KADY_REFERENCE_LANG";
+const SEPARATOR: &str = "::";
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "extern", "false", "fn", "for",
+    "gen", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "static",
+    "struct", "trait", "true", "try", "type", "unsafe", "use", "where", "while", "abstract", "become", "box", "do",
+    "final", "macro", "override", "priv", "typeof", "unsized", "virtual", "yield"
+];
+
+#[derive(Default)]
+struct Directory {
+    directories: BTreeMap<String, Directory>,
+    keys: BTreeMap<String, Value>
+}
+
+fn main() {
+    println!("cargo:rerun-if-env-changed={LANGS_DIR_ENV}");
+    println!("cargo:rerun-if-env-changed={REFERENCE_ENV}");
+    println!("cargo:rerun-if-changed=src/icu/syntax.rs");
+
+    let langs = match std::env::var(LANGS_DIR_ENV) {
+        Ok(dir) => PathBuf::from(dir),
+        Err(_) => Path::new(env!("CARGO_MANIFEST_DIR")).join("../../langs")
+    };
+    let reference = std::env::var(REFERENCE_ENV).unwrap_or_else(|_| "fr".to_string());
+    let dir = langs.join(&reference);
+
+    let mut root = Directory::default();
+    if dir.is_dir() {
+        println!("cargo:rerun-if-changed={}", dir.display());
+        read_dir(&dir, &mut root);
+    } else {
+        println!("cargo:warning=The reference language {dir:?} doesn't exist, no key is generated");
+    }
+
+    let mut code = String::new();
+    write_directory(&mut code, &root, &[]);
+
+    let out = PathBuf::from(std::env::var("OUT_DIR").expect("OUT_DIR is set by cargo")).join("keys.rs");
+    std::fs::write(out, code).expect("Cannot write the generated keys");
+}
+
+fn read_dir(dir: &Path, root: &mut Directory) {
+    let mut entries: Vec<PathBuf> = std::fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("Cannot read {dir:?}: {e}"))
+        .flatten()
+        .map(|e| e.path())
+        .collect();
+    // the same keys in the same order on every build
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            read_dir(&path, root);
+            continue;
+        }
+        let content = || std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("Cannot read {path:?}: {e}"));
+        let file: Value = match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => serde_json::from_str(&content()).unwrap_or_else(|e| panic!("Invalid JSON in {path:?}: {e}")),
+            Some("toml") => toml::from_str(&content()).unwrap_or_else(|e| panic!("Invalid TOML in {path:?}: {e}")),
+            Some("yaml" | "yml") => serde_yaml::from_str(&content()).unwrap_or_else(|e| panic!("Invalid YAML in {path:?}: {e}")),
+            _ => continue
+        };
+
+        // the declaration file has no path
+        let (Some(file_path), Some(data)) = (file.get("path").and_then(Value::as_str), file.get("data")) else { continue };
+        if file_path.trim() == "#" { continue; }
+
+        let mut directory = &mut *root;
+        for segment in file_path.replace('#', "").split(SEPARATOR) {
+            directory = directory.directories.entry(segment.to_string()).or_default();
+        }
+        insert(directory, data);
+    }
+}
+
+fn insert(directory: &mut Directory, data: &Value) {
+    let Some(entries) = data.as_object() else { return };
+
+    for (key, value) in entries {
+        if value.is_object() {
+            insert(directory.directories.entry(key.clone()).or_default(), value);
+        } else {
+            directory.keys.insert(key.clone(), value.clone());
+        }
+    }
+}
+
+/// Write the modules of the directories and a struct per key
+fn write_directory(code: &mut String, directory: &Directory, path: &[&str]) {
+    let mut names = BTreeMap::new();
+
+    for (name, child) in &directory.directories {
+        let Some(ident) = unique_ident(&mut names, name, path) else { continue };
+        let mut child_path = path.to_vec();
+        child_path.push(name);
+
+        writeln!(code, "#[doc = {:?}]", format!("`{}`", child_path.join(SEPARATOR))).unwrap();
+        writeln!(code, "pub mod {ident} {{").unwrap();
+        write_directory(code, child, &child_path);
+        writeln!(code, "}}").unwrap();
+    }
+
+    for (name, value) in &directory.keys {
+        let Some(ident) = unique_ident(&mut names, name, path) else { continue };
+        let mut key_path = path.to_vec();
+        key_path.push(name);
+        write_key(code, &ident, &key_path.join(SEPARATOR), value);
+    }
+}
+
+fn write_key(code: &mut String, ident: &str, path: &str, value: &Value) {
+    let text = match value {
+        Value::String(s) => s.clone(),
+        value => value.to_string()
+    };
+    let arguments: Vec<(String, String)> = syntax::arguments(&text).into_iter()
+        .map(|name| (ident_of(&name), name))
+        .collect();
+    let generics: Vec<String> = (0..arguments.len()).map(|i| format!("A{i}")).collect();
+
+    // the code blocks of the translations would be run as doc tests
+    writeln!(code, "#[doc = {:?}]", format!("`{path}`: {}", text.replace('`', "\\`"))).unwrap();
+    writeln!(code, "#[allow(non_camel_case_types)]").unwrap();
+
+    if arguments.is_empty() {
+        writeln!(code, "pub struct {ident};").unwrap();
+        writeln!(code, "impl crate::keys::Key for {ident} {{").unwrap();
+        writeln!(code, "    const PATH: &'static str = {path:?};").unwrap();
+        writeln!(code, "    fn formatter(&self) -> crate::fmt::formatter::Formatter {{ crate::fmt::formatter::Formatter::new() }}").unwrap();
+        writeln!(code, "}}").unwrap();
+        return;
+    }
+
+    writeln!(code, "pub struct {ident}<{}> {{", generics.join(", ")).unwrap();
+    for ((field, _), generic) in arguments.iter().zip(&generics) {
+        writeln!(code, "    pub {field}: {generic},").unwrap();
+    }
+    writeln!(code, "}}").unwrap();
+
+    let bounds: Vec<String> = generics.iter().map(|g| format!("{g}: std::fmt::Display")).collect();
+    writeln!(code, "impl<{}> crate::keys::Key for {ident}<{}> {{", bounds.join(", "), generics.join(", ")).unwrap();
+    writeln!(code, "    const PATH: &'static str = {path:?};").unwrap();
+    writeln!(code, "    fn formatter(&self) -> crate::fmt::formatter::Formatter {{").unwrap();
+    writeln!(code, "        let mut formatter = crate::fmt::formatter::Formatter::new();").unwrap();
+    for (field, name) in &arguments {
+        writeln!(code, "        formatter.add({name:?}, &self.{field});").unwrap();
+    }
+    writeln!(code, "        formatter").unwrap();
+    writeln!(code, "    }}").unwrap();
+    writeln!(code, "}}").unwrap();
+}
+
+/// Get the identifier of a key, None if another key of the directory has the same one
+fn unique_ident(names: &mut BTreeMap<String, String>, name: &str, path: &[&str]) -> Option<String> {
+    if name.starts_with("__") { return None; }
+
+    let ident = ident_of(name);
+    if let Some(other) = names.get(&ident) {
+        println!("cargo:warning=The keys '{other}' and '{name}' of '{}' have the same identifier, '{name}' is ignored", path.join(SEPARATOR));
+        return None;
+    }
+    names.insert(ident.clone(), name.to_string());
+
+    Some(ident)
+}
+
+/// Turn a key into an identifier: `8ball` becomes `_8ball` and `const` becomes `r#const`
+fn ident_of(name: &str) -> String {
+    let mut ident: String = name.chars().map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' }).collect();
+
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+    if KEYWORDS.contains(&ident.as_str()) {
+        ident.insert_str(0, "r#");
+    }
+    if matches!(ident.as_str(), "self" | "super" | "crate" | "Self" | "_") {
+        ident.push('_');
+    }
+
+    ident
+}
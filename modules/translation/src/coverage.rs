@@ -0,0 +1,267 @@
+//! Check that the languages are complete and consistent, and that the keys used in the code exist
+//!
+//! Each language is compared to a reference language, the last one of the fallback chain:
+//! - the missing keys are in the reference but not in the language
+//! - the orphan keys are in the language but not in the reference
+//! - the placeholders of a key must be the same in both languages
+//!
+//! The keys given to `message!` in the code must exist in the reference language.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use error::{Error, FileError, Result};
+use crate::{icu, parser, SEPARATOR, TranslationNode};
+
+/// The result of a check
+#[derive(Debug, Default)]
+pub struct CoverageReport {
+    pub reference: String,
+    /// The problems of each language, the languages without problem are omitted
+    pub locales: BTreeMap<String, LocaleCoverage>,
+    /// The keys used in the code that don't exist in the reference language
+    pub unknown_keys: Vec<KeyUsage>
+}
+
+/// The problems of a language, compared to the reference
+#[derive(Debug, Default)]
+pub struct LocaleCoverage {
+    pub missing: Vec<String>,
+    pub orphans: Vec<String>,
+    pub placeholder_mismatches: Vec<PlaceholderMismatch>
+}
+
+#[derive(Debug)]
+pub struct PlaceholderMismatch {
+    pub key: String,
+    /// The placeholders in the reference
+    pub expected: BTreeSet<String>,
+    pub found: BTreeSet<String>
+}
+
+/// A translation key written in the code
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyUsage {
+    pub key: String,
+    pub file: PathBuf,
+    pub line: usize
+}
+
+impl LocaleCoverage {
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.orphans.is_empty() && self.placeholder_mismatches.is_empty()
+    }
+}
+
+impl CoverageReport {
+    /// True if a key used in the code doesn't exist
+    pub fn has_unknown_keys(&self) -> bool {
+        !self.unknown_keys.is_empty()
+    }
+}
+
+/// Check every language of `dir` against `reference`, and the keys used in the Rust files of `sources`
+pub fn check(dir: &Path, reference: &str, sources: &[&Path]) -> Result<CoverageReport> {
+    let languages = load_languages(dir)?;
+    let reference_keys = match languages.get(reference) {
+        Some(keys) => keys,
+        None => return Err(Error::Fs(FileError::InvalidPath(format!("The reference language '{reference}' isn't in {dir:?}"))))
+    };
+
+    let mut report = CoverageReport {
+        reference: reference.to_string(),
+        ..Default::default()
+    };
+
+    for (lang, keys) in languages.iter().filter(|(lang, _)| *lang != reference) {
+        let coverage = compare(reference_keys, keys);
+        if !coverage.is_empty() {
+            report.locales.insert(lang.clone(), coverage);
+        }
+    }
+
+    for source in sources {
+        for usage in scan_usages(source)? {
+            let known = reference_keys.contains_key(&usage.key)
+                || reference_keys.keys().any(|k| k.starts_with(&format!("{}{SEPARATOR}", usage.key)));
+
+            if !known {
+                report.unknown_keys.push(usage);
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+/// Get the keys of each language with their placeholders
+fn load_languages(dir: &Path) -> Result<BTreeMap<String, BTreeMap<String, BTreeSet<String>>>> {
+    let mut languages = BTreeMap::new();
+
+    for lang in parser::get_langs_dir(dir)? {
+        let files = parser::get_files(dir.join(&lang).as_path())?;
+        let root = parser::parse_lang_files(files)?;
+
+        let mut keys = BTreeMap::new();
+        collect_keys(&root, String::new(), &mut keys);
+        languages.insert(lang, keys);
+    }
+
+    Ok(languages)
+}
+
+fn collect_keys(node: &TranslationNode, path: String, keys: &mut BTreeMap<String, BTreeSet<String>>) {
+    if let Some(value) = &node.value {
+        let placeholders = match value {
+            serde_json::Value::String(s) => icu::arguments(s),
+            value => icu::arguments(&value.to_string())
+        };
+        keys.insert(path, placeholders);
+        return;
+    }
+
+    for (key, child) in node.children.iter().flatten() {
+        // the declaration of the language
+        if key.0.starts_with("__") { continue; }
+
+        let child_path = if path.is_empty() { key.0.clone() } else { format!("{path}{SEPARATOR}{}", key.0) };
+        collect_keys(child, child_path, keys);
+    }
+}
+
+fn compare(reference: &BTreeMap<String, BTreeSet<String>>, keys: &BTreeMap<String, BTreeSet<String>>) -> LocaleCoverage {
+    let mut coverage = LocaleCoverage::default();
+
+    for (key, expected) in reference {
+        match keys.get(key) {
+            None => coverage.missing.push(key.clone()),
+            Some(found) if found != expected => coverage.placeholder_mismatches.push(PlaceholderMismatch {
+                key: key.clone(),
+                expected: expected.clone(),
+                found: found.clone()
+            }),
+            Some(_) => {}
+        }
+    }
+
+    coverage.orphans = keys.keys().filter(|k| !reference.contains_key(*k)).cloned().collect();
+    coverage
+}
+
+/// Find the keys written as string literals in the `message!` calls of the Rust files of `dir`
+pub fn scan_usages(dir: &Path) -> Result<Vec<KeyUsage>> {
+    let mut usages = Vec::new();
+
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => return Err(Error::Fs(FileError::CannotReadDir(format!("{dir:?}: {e}"))))
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+
+        if path.is_dir() {
+            if path.file_name().is_some_and(|n| n == "target" || n.to_string_lossy().starts_with('.')) { continue; }
+            usages.extend(scan_usages(&path)?);
+        } else if path.extension().is_some_and(|e| e == "rs") {
+            let content = match std::fs::read_to_string(&path) {
+                Ok(content) => content,
+                Err(e) => return Err(Error::Fs(FileError::CannotReadFile(format!("{path:?}: {e}"))))
+            };
+
+            usages.extend(
+                message_keys(&content).into_iter().map(|(key, line)| KeyUsage { key, file: path.clone(), line })
+            );
+        }
+    }
+
+    Ok(usages)
+}
+
+/// Get the key and the line of each `message!(lang, "key"...)` of a file
+fn message_keys(content: &str) -> Vec<(String, usize)> {
+    const MACRO: &str = "message!(";
+    let mut keys = Vec::new();
+
+    for (start, _) in content.match_indices(MACRO) {
+        // skip the language, which can be an expression
+        let rest = &content[start + MACRO.len()..];
+        let mut depth = 0usize;
+        let comma = rest.char_indices().find(|(_, c)| match c {
+            '(' | '[' | '{' => { depth += 1; false }
+            ')' | ']' | '}' => { depth = depth.saturating_sub(1); false }
+            ',' => depth == 0,
+            _ => false
+        });
+
+        let Some((comma, _)) = comma else { continue };
+        let Some(literal) = rest[comma + 1..].trim_start().strip_prefix('"') else { continue };
+        let Some(end) = literal.find('"') else { continue };
+
+        // skip the comments and the documentation
+        let line_start = content[..start].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        if content[line_start..start].trim_start().starts_with("//") { continue; }
+
+        let line = content[..start].matches('\n').count() + 1;
+        keys.push((literal[..end].to_string(), line));
+    }
+
+    keys
+}
+
+impl Display for CoverageReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Reference language: {}", self.reference)?;
+
+        for (lang, coverage) in &self.locales {
+            writeln!(f, "\n[{lang}] {} missing, {} orphans, {} placeholder mismatches", coverage.missing.len(), coverage.orphans.len(), coverage.placeholder_mismatches.len())?;
+            for key in &coverage.missing {
+                writeln!(f, "  missing      {key}")?;
+            }
+            for key in &coverage.orphans {
+                writeln!(f, "  orphan       {key}")?;
+            }
+            for mismatch in &coverage.placeholder_mismatches {
+                writeln!(f, "  placeholders {}: expected {:?}, found {:?}", mismatch.key, mismatch.expected, mismatch.found)?;
+            }
+        }
+
+        if !self.unknown_keys.is_empty() {
+            writeln!(f, "\n{} unknown keys used in the code", self.unknown_keys.len())?;
+            for usage in &self.unknown_keys {
+                writeln!(f, "  {}:{} {}", usage.file.display(), usage.line, usage.key)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+    use crate::coverage::{check, message_keys};
+
+    #[test]
+    fn keys_are_found_in_the_macros() {
+        let content = "let a = message!(get_guild_locale(&x, y), \"errors::not_guild\");\n/// message!(lang, \"ignored\")\nmessage!(\n    \"fr\",\n    \"features::xp::lvl_up\",\n    f\n)";
+
+        assert_eq!(message_keys(content), vec![
+            ("errors::not_guild".to_string(), 1),
+            ("features::xp::lvl_up".to_string(), 3)
+        ]);
+    }
+
+    #[test]
+    fn the_code_uses_existing_keys() {
+        let root = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/../.."));
+        let report = check(
+            &root.join("langs"),
+            crate::DEFAULT_LANGUAGE,
+            &[&root.join("app"), &root.join("modules"), &root.join("client")]
+        ).unwrap();
+
+        assert!(!report.has_unknown_keys(), "{report}");
+    }
+}
@@ -1,68 +1,136 @@
 use logs::warn;
-use crate::{Language, SEPARATOR, TranslationKey, TranslationNode, TRANSLATIONS};
-use crate::fmt::constants::{NO_CHILDREN, NO_LANG, NO_NODE};
+use crate::{Language, SEPARATOR, TranslationKey, TranslationNode, default_language, metrics, store};
 use crate::fmt::formatter::Formatter;
+use crate::keys::Key;
 
 /// Get the lang and path to locate the node at the given path
 ///
-/// Ensure a TranslationNode but can be a node with an error in the value
+/// The node is searched in the fallback chain of the lang (see [`fallback_chain`]),
+/// the missing children of a directory are taken from the next languages of the chain.
+/// If no language has the node, its value is the key marker `[[path]]`
 pub fn translate(
     lang: impl Into<Language>,
     path: &str,
     formatter: &Formatter
 ) -> TranslationNode {
-    let translations = TRANSLATIONS.read().expect("Translation object is poisoned");
-    let final_node = match translations.get(&lang.into()) {
-        Some(node) => {
-            if path == "#" {
-                node.clone()
-            } else {
-                let path_vec: Vec<&str> = path.split(SEPARATOR).collect();
-                get_node(
-                    path_vec,
-                    node
-                )
+    let lang = lang.into();
+    let translations = store::current();
+
+    let mut found: Option<TranslationNode> = None;
+    let mut fell_back = false;
+    for (i, candidate) in fallback_chain(&lang).iter().enumerate() {
+        let node = match translations.get(candidate).and_then(|root| get_node(path, root)) {
+            Some(node) => node,
+            None => continue
+        };
+
+        match found.as_mut() {
+            // the fallback only completes the directories
+            Some(found) if found.children.is_some() => fell_back |= fill_missing(path, found, node, formatter, candidate),
+            Some(_) => break,
+            None => {
+                fell_back = i > 0;
+                // each language is formatted with its own plural rules and separators
+                found = Some(formatter::format(path, node, formatter, candidate))
             }
-        },
+        }
+    }
+
+    // a directory completed by another language counts as one fallback, like a missing value
+    if fell_back {
+        metrics::record_fallback(&lang);
+    }
+
+    match found {
+        Some(node) => node,
         None => {
-            return TranslationNode {
+            warn!(target: "Translation", "Translation not found for '{path}' in {:?}", lang.0);
+            metrics::record_missing(&lang);
+
+            TranslationNode {
                 children: None,
-                value: Some(NO_LANG.into())
+                value: Some(key_marker(path).into())
             }
         }
-    };
+    }
+}
+
+/// Get a node of a language as it is written: without fallback nor formatting
+pub fn get_raw(lang: &Language, path: &str) -> Option<TranslationNode> {
+    let translations = store::current();
+    translations.get(lang).and_then(|root| get_node(path, root)).cloned()
+}
 
-    formatter::format(&path.to_string(), final_node, formatter)
+/// Translate a key checked at compile time, see [`crate::keys`]
+pub fn translate_key<K: Key>(lang: impl Into<Language>, key: &K) -> TranslationNode {
+    translate(lang, K::PATH, &key.formatter())
+}
+
+/// Get the languages searched for a translation, in order:
+/// the lang itself, its base language (`en-US` to `en`), then the default language
+pub fn fallback_chain(lang: &Language) -> Vec<Language> {
+    let mut chain = vec![lang.clone()];
+
+    if let Some((base, _)) = lang.0.split_once('-') {
+        chain.push(Language::from(base));
+    }
+
+    let default = default_language();
+    if !chain.contains(&default) {
+        chain.push(default);
+    }
+
+    chain
+}
+
+/// The value shown when a key is missing in every language
+pub fn key_marker(path: &str) -> String {
+    format!("[[{path}]]")
 }
 
 /// Retrieve a node (if available) from the path
 ///
 /// Will return None if no node was found at the given path
-fn get_node(path: Vec<&str>, node: &TranslationNode) -> TranslationNode {
-    if path.is_empty() {
-        node.clone()
-    } else {
-        let key = path.first().unwrap_or(&"");
-        if node.children.is_none() { return TranslationNode { children: None, value: Some(NO_CHILDREN.into()) } }
-        // get the node
-        if let Some(node) = node.children.as_ref().unwrap().get(&TranslationKey(key.to_string())) {
-            get_node(
-                path[1..].to_vec(),
-                node
-            )
-        } else {
-            warn!(target: "Translation", "Translation not found for {path:?}");
-            TranslationNode { children: None, value: Some(NO_NODE.into()) }
+fn get_node<'a>(path: &str, root: &'a TranslationNode) -> Option<&'a TranslationNode> {
+    if path == "#" {
+        return Some(root);
+    }
+
+    let mut node = root;
+    for key in path.split(SEPARATOR) {
+        node = node.children.as_ref()?.get(&TranslationKey(key.to_string()))?;
+    }
+
+    Some(node)
+}
+
+/// Add the children of `fallback` that are missing in `node`, recursively, formatted in the language of the fallback
+///
+/// Return true if a child was added
+fn fill_missing(path: &str, node: &mut TranslationNode, fallback: &TranslationNode, formatter: &Formatter, lang: &Language) -> bool {
+    let (Some(children), Some(fallback_children)) = (node.children.as_mut(), fallback.children.as_ref()) else {
+        return false;
+    };
+
+    let mut added = false;
+    for (key, fallback_child) in fallback_children {
+        match children.get_mut(key) {
+            Some(child) => added |= fill_missing(path, child, fallback_child, formatter, lang),
+            None => {
+                children.insert(key.clone(), formatter::format(path, fallback_child, formatter, lang));
+                added = true;
+            }
         }
     }
+
+    added
 }
 
 pub mod formatter {
     use std::collections::HashMap;
     use std::fmt::Display;
-    use std::ops::Deref;
     use serde_json::Value;
-    use crate::{FORMATTING_RG, TranslationNode};
+    use crate::{icu, Language, TranslationNode};
 
     /// Builder & Abstraction structure to simplify the formatting of translations
     #[derive(Debug, Clone, Default)]
@@ -87,6 +155,11 @@ pub mod formatter {
             self
         }
 
+        /// Get the value of an argument
+        pub fn get(&self, key: &str) -> Option<&str> {
+            self.arguments.get(key).map(String::as_str)
+        }
+
         /// Builder method to add a value with a key
         ///
         /// Will update the value at the key emplacement is the value is already set,
@@ -98,49 +171,91 @@ pub mod formatter {
     }
 
 
-    pub(super) fn format(path: &String, mut source: TranslationNode, formatter: &Formatter) -> TranslationNode {
-        if let Some(v) = source.value.as_mut() {
-            source.value = Some(format_value(path, v.deref().to_owned(), formatter));
+    /// Format a copy of the node, the stored node is left untouched
+    pub(super) fn format(path: &str, source: &TranslationNode, formatter: &Formatter, lang: &Language) -> TranslationNode {
+        TranslationNode {
+            value: source.value.as_ref().map(|v| format_value(path, v, formatter, lang)),
+            children: source.children.as_ref().map(|children| {
+                children.iter().map(|(k, v)| (k.clone(), format(path, v, formatter, lang))).collect()
+            })
         }
-        if let Some(childrens) = source.children.as_mut() {
-            for (_, v) in childrens.iter_mut() {
-                *v = format(path, v.deref().to_owned(), formatter);
-            }
-        };
-        source
     }
 
     #[allow(clippy::only_used_in_recursion)]
-    fn format_value(path: &String, mut source: Value, formatter: &Formatter) -> Value {
-        if source.is_object() {
-            let entries = source.as_object_mut().unwrap();
-            for (_, v) in entries.iter_mut() {
-                *v = format_value(path, v.clone(), formatter);
-            };
-            return source
-        } else if source.is_array() {
-            let array = source.as_array_mut().unwrap();
-            for v in array {
-                *v = format_value(path, v.clone(), formatter)
-            };
-            return source
-        } else if source.is_string() {
-            return Value::String(format_string(source.as_str().unwrap(), formatter))
+    fn format_value(path: &str, source: &Value, formatter: &Formatter, lang: &Language) -> Value {
+        match source {
+            Value::Object(entries) => Value::Object(
+                entries.iter().map(|(k, v)| (k.clone(), format_value(path, v, formatter, lang))).collect()
+            ),
+            Value::Array(array) => Value::Array(array.iter().map(|v| format_value(path, v, formatter, lang)).collect()),
+            Value::String(string) => Value::String(format_string(string, formatter, lang)),
+            source => source.clone()
         }
-        source
     }
 
-    fn format_string(from: &str, formatter: &Formatter) -> String {
-        FORMATTING_RG.replace_all(from, |caps: &regex::Captures| -> String {
-            let k: &str = caps[1].as_ref();
-            if let Some(v) = formatter.arguments.get(k) { v.clone() }
-            else { format!("{{{t}}}", t = k) }
-        }).to_string()
+    /// Replace the placeholders, and format the plural, select, number and date arguments (see [`icu`])
+    fn format_string(from: &str, formatter: &Formatter, lang: &Language) -> String {
+        icu::format(from, &formatter.arguments, &lang.0)
     }
 }
 
-mod constants {
-    pub(super) const NO_LANG: &str = "INVALID_LANG";
-    pub(super) const NO_NODE: &str = "NO_NODE_FOUND";
-    pub(super) const NO_CHILDREN: &str = "NO_CHILDREN_FOUND";
-}
\ No newline at end of file
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+    use crate::{metrics, store, Language, TranslationKey};
+    use crate::fmt::{fallback_chain, key_marker, translate};
+    use crate::fmt::formatter::Formatter;
+    use crate::parser::add_to_node;
+
+    /// Add the data under `key` to the root of the language, keeping what the other tests added
+    fn insert(lang: &str, key: &str, data: serde_json::Value) {
+        let lang = Language::from(lang);
+
+        store::update(|translations| {
+            let mut root = translations.get(&lang).cloned().unwrap_or_default();
+            add_to_node(&mut root, &TranslationKey::from(key), &data);
+            translations.insert(lang.clone(), root);
+        });
+    }
+
+    #[test]
+    fn chain_goes_to_the_base_then_the_default() {
+        assert_eq!(
+            fallback_chain(&Language::from("en-US")),
+            vec![Language::from("en-US"), Language::from("en"), Language::from("fr")]
+        );
+        assert_eq!(fallback_chain(&Language::from("fr")), vec![Language::from("fr")]);
+    }
+
+    #[test]
+    fn missing_keys_fall_back() {
+        insert("fr", "fallback", json!({ "hello": "bonjour", "bye": "au revoir", "nested": { "a": "fr a", "b": "fr b" } }));
+        insert("xx", "fallback", json!({ "hello": "xx hello", "nested": { "a": "xx a" } }));
+        let formatter = Formatter::new();
+
+        assert_eq!(translate("xx-YY", "fallback::hello", &formatter).to_string(), "xx hello");
+        assert_eq!(translate("xx", "fallback::bye", &formatter).to_string(), "au revoir");
+
+        let nested = translate("xx", "fallback::nested", &formatter);
+        assert_eq!(nested.get_children("a").unwrap().to_string(), "xx a");
+        assert_eq!(nested.get_children("b").unwrap().to_string(), "fr b");
+
+        assert_eq!(translate("xx", "fallback::unknown", &formatter).to_string(), key_marker("fallback::unknown"));
+    }
+
+    #[test]
+    fn completed_directories_are_counted_as_fallbacks() {
+        insert("fr", "completed", json!({ "dir": { "a": "fr a", "b": "fr b" }, "full": { "a": "fr a" } }));
+        insert("zz", "completed", json!({ "dir": { "a": "zz a" }, "full": { "a": "zz a" } }));
+        let formatter = Formatter::new();
+        let fallbacks = || metrics::get().fallbacks.get(&Language::from("zz")).copied().unwrap_or_default();
+
+        let before = fallbacks();
+        translate("zz", "completed::full", &formatter);
+        assert_eq!(fallbacks(), before);
+
+        translate("zz", "completed::dir", &formatter);
+        assert_eq!(fallbacks(), before + 1);
+    }
+}
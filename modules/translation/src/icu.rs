@@ -0,0 +1,275 @@
+//! Format the translation strings with a subset of the ICU message syntax
+//!
+//! - `{name}` is replaced by the argument, and kept as is if the argument isn't given
+//! - `{count, plural, =0 {no cookie} one {# cookie} other {# cookies}}` picks a message from the plural
+//!   category of the number in the language, `#` is replaced by the formatted number
+//! - `{gender, select, female {elle} male {il} other {iel}}` picks a message from the value
+//! - `{count, number}`, `{ratio, number, percent}` and `{count, number, integer}` format a number
+//! - `{joined, date}` (`short`, `medium` or `long`) and `{joined, time}` format a unix timestamp or an RFC 3339 date
+//!
+//! Unlike ICU, the apostrophes are not used to escape the braces.
+
+mod syntax;
+
+use std::collections::HashMap;
+use chrono::{DateTime, Datelike, TimeZone, Timelike, Utc};
+pub(crate) use syntax::arguments;
+use syntax::{matching_brace, parse_options, split_top_level};
+
+/// Format a translation string in the given language
+pub(crate) fn format(message: &str, arguments: &HashMap<String, String>, lang: &str) -> String {
+    let lang = lang.split('-').next().unwrap_or(lang).to_lowercase();
+    format_message(message, arguments, &lang, None)
+}
+
+fn format_message(message: &str, arguments: &HashMap<String, String>, lang: &str, plural_value: Option<&str>) -> String {
+    let mut output = String::with_capacity(message.len());
+    let mut rest = message;
+
+    while let Some(c) = rest.chars().next() {
+        match c {
+            '{' => match matching_brace(rest) {
+                Some(end) => {
+                    let content = &rest[1..end];
+                    match format_argument(content, arguments, lang, plural_value) {
+                        Some(formatted) => output.push_str(&formatted),
+                        None => output.push_str(&rest[..=end])
+                    }
+                    rest = &rest[end + 1..];
+                }
+                None => {
+                    output.push_str(rest);
+                    break;
+                }
+            },
+            '#' if plural_value.is_some() => {
+                output.push_str(&format_number(plural_value.unwrap_or_default(), lang, None));
+                rest = &rest[1..];
+            }
+            c => {
+                output.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+
+    output
+}
+
+/// Format the content of a `{...}`, return None to keep it as is
+fn format_argument(content: &str, arguments: &HashMap<String, String>, lang: &str, plural_value: Option<&str>) -> Option<String> {
+    let mut parts = split_top_level(content, 3).into_iter();
+    let name = parts.next()?.trim();
+    let kind = parts.next().map(str::trim);
+    let style = parts.next();
+
+    let value = arguments.get(name)?;
+
+    match kind {
+        None => Some(value.clone()),
+        Some("plural") => {
+            let options = parse_options(style?)?;
+            let message = options.iter().find(|(selector, _)| selector.strip_prefix('=') == Some(value.trim()))
+                .or_else(|| {
+                    let category = plural_category(value, lang);
+                    options.iter().find(|(selector, _)| *selector == category)
+                })
+                .or_else(|| options.iter().find(|(selector, _)| *selector == "other"))?;
+
+            Some(format_message(message.1, arguments, lang, Some(value)))
+        }
+        Some("select") => {
+            let options = parse_options(style?)?;
+            let message = options.iter().find(|(selector, _)| *selector == value.trim())
+                .or_else(|| options.iter().find(|(selector, _)| *selector == "other"))?;
+
+            Some(format_message(message.1, arguments, lang, plural_value))
+        }
+        Some("number") => Some(format_number(value, lang, style.map(str::trim))),
+        Some("date") => format_date(value, lang, style.map(str::trim).unwrap_or("medium")),
+        Some("time") => format_time(value, lang),
+        Some(_) => None
+    }
+}
+
+/// Get the CLDR plural category of a number: `zero`, `one`, `two`, `few`, `many` or `other`
+pub(crate) fn plural_category(value: &str, lang: &str) -> &'static str {
+    let value = value.trim();
+    let (integer, fraction) = value.split_once(['.', ',']).unwrap_or((value, ""));
+    let i: u64 = match integer.trim_start_matches('-').parse() {
+        Ok(i) => i,
+        Err(_) => return "other"
+    };
+    let v = fraction.len();
+
+    match lang {
+        // no plural
+        "ja" | "zh" | "ko" | "vi" | "th" | "id" | "ms" | "tr" => "other",
+        // 0 and 1 are singular
+        "fr" | "pt" => if i < 2 { "one" } else if v == 0 && i != 0 && i.is_multiple_of(1_000_000) { "many" } else { "other" },
+        "ru" | "uk" => {
+            if v != 0 { "other" }
+            else if i % 10 == 1 && i % 100 != 11 { "one" }
+            else if (2..=4).contains(&(i % 10)) && !(12..=14).contains(&(i % 100)) { "few" }
+            else { "many" }
+        }
+        "pl" => {
+            if v != 0 { "other" }
+            else if i == 1 { "one" }
+            else if (2..=4).contains(&(i % 10)) && !(12..=14).contains(&(i % 100)) { "few" }
+            else { "many" }
+        }
+        // english and most of the european languages
+        _ => if i == 1 && v == 0 { "one" } else { "other" }
+    }
+}
+
+/// Format a number with the separators of the language, `style` can be `integer` or `percent`
+///
+/// The value is kept as is if it isn't a number
+pub(crate) fn format_number(value: &str, lang: &str, style: Option<&str>) -> String {
+    let number: f64 = match value.trim().parse() {
+        Ok(n) => n,
+        Err(_) => return value.to_string()
+    };
+
+    let (group, decimal) = match lang {
+        "fr" => ("\u{202f}", ","),
+        "de" | "es" | "it" | "nl" | "pt" | "tr" | "id" => (".", ","),
+        "ru" | "uk" | "pl" => ("\u{a0}", ","),
+        _ => (",", ".")
+    };
+
+    let (digits, suffix) = match style {
+        Some("integer") => (format!("{}", number.round() as i64), String::new()),
+        Some("percent") => (
+            format!("{}", (number * 100.0).round() as i64),
+            if lang == "fr" { "\u{202f}%".to_string() } else { "%".to_string() }
+        ),
+        _ => (value.trim().to_string(), String::new())
+    };
+
+    let (sign, digits) = match digits.strip_prefix('-') {
+        Some(digits) => ("-", digits.to_string()),
+        None => ("", digits)
+    };
+    let (integer, fraction) = match digits.split_once('.') {
+        Some((integer, fraction)) => (integer.to_string(), Some(fraction.to_string())),
+        None => (digits, None)
+    };
+
+    let mut grouped = String::new();
+    for (i, c) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            grouped.push_str(group);
+        }
+        grouped.push(c);
+    }
+
+    match fraction {
+        Some(fraction) => format!("{sign}{grouped}{decimal}{fraction}{suffix}"),
+        None => format!("{sign}{grouped}{suffix}")
+    }
+}
+
+/// Read a unix timestamp, in seconds, or an RFC 3339 date
+fn parse_date(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+
+    match value.parse::<i64>() {
+        Ok(timestamp) => Utc.timestamp_opt(timestamp, 0).single(),
+        Err(_) => DateTime::parse_from_rfc3339(value).ok().map(|d| d.with_timezone(&Utc))
+    }
+}
+
+const FR_MONTHS: [&str; 12] = ["janvier", "février", "mars", "avril", "mai", "juin", "juillet", "août", "septembre", "octobre", "novembre", "décembre"];
+const FR_SHORT_MONTHS: [&str; 12] = ["janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.", "nov.", "déc."];
+const EN_MONTHS: [&str; 12] = ["January", "February", "March", "April", "May", "June", "July", "August", "September", "October", "November", "December"];
+
+/// Format a date, `style` is `short`, `medium` or `long`
+fn format_date(value: &str, lang: &str, style: &str) -> Option<String> {
+    let date = parse_date(value)?;
+    let (day, month, year) = (date.day(), date.month0() as usize, date.year());
+
+    Some(match (lang, style) {
+        ("fr", "short") => format!("{day:02}/{:02}/{year}", month + 1),
+        ("fr", "long") => format!("{day} {} {year}", FR_MONTHS[month]),
+        ("fr", _) => format!("{day} {} {year}", FR_SHORT_MONTHS[month]),
+        ("en", "short") => format!("{}/{day}/{year}", month + 1),
+        ("en", "long") => format!("{} {day}, {year}", EN_MONTHS[month]),
+        ("en", _) => format!("{} {day}, {year}", &EN_MONTHS[month][..3]),
+        ("de", _) => format!("{day:02}.{:02}.{year}", month + 1),
+        _ => date.format("%Y-%m-%d").to_string()
+    })
+}
+
+/// Format the hour and the minutes of a date, in UTC
+fn format_time(value: &str, lang: &str) -> Option<String> {
+    let date = parse_date(value)?;
+
+    Some(match lang {
+        "en" => {
+            let hour = match date.hour() % 12 { 0 => 12, h => h };
+            format!("{hour}:{:02} {}", date.minute(), if date.hour() < 12 { "AM" } else { "PM" })
+        }
+        _ => format!("{:02}:{:02}", date.hour(), date.minute())
+    })
+}
+
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+    use crate::icu::{arguments, format, format_number, plural_category};
+
+    fn args(values: &[(&str, &str)]) -> HashMap<String, String> {
+        values.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn placeholders_are_backward_compatible() {
+        let args = args(&[("name", "Kady")]);
+        assert_eq!(format("> **#{rank}** - {name}", &args, "fr"), "> **#{rank}** - Kady");
+    }
+
+    #[test]
+    fn plural() {
+        let message = "{n, plural, =0 {aucun cookie} one {# cookie} other {# cookies}}";
+
+        assert_eq!(format(message, &args(&[("n", "0")]), "fr"), "aucun cookie");
+        assert_eq!(format(message, &args(&[("n", "1")]), "fr"), "1 cookie");
+        assert_eq!(format(message, &args(&[("n", "1500")]), "fr"), "1\u{202f}500 cookies");
+        assert_eq!(format(message, &args(&[("n", "1500")]), "en-US"), "1,500 cookies");
+
+        assert_eq!(plural_category("1.5", "fr"), "one");
+        assert_eq!(plural_category("1.5", "en"), "other");
+        assert_eq!(plural_category("22", "ru"), "few");
+        assert_eq!(plural_category("25", "pl"), "many");
+    }
+
+    #[test]
+    fn select_and_nesting() {
+        let message = "{gender, select, female {elle a {n, plural, one {# cookie} other {# cookies}}} other {iel a {n} cookies}}";
+
+        assert_eq!(format(message, &args(&[("gender", "female"), ("n", "2")]), "fr"), "elle a 2 cookies");
+        assert_eq!(format(message, &args(&[("gender", "x"), ("n", "2")]), "fr"), "iel a 2 cookies");
+    }
+
+    #[test]
+    fn nested_arguments() {
+        let names = arguments("> **#{rank}** {user} {n, plural, one {# de {who}} other {#}} {x, number}");
+        assert_eq!(names.into_iter().collect::<Vec<String>>(), vec!["n", "rank", "user", "who", "x"]);
+    }
+
+    #[test]
+    fn numbers_and_dates() {
+        assert_eq!(format_number("-1234567.25", "de", None), "-1.234.567,25");
+        assert_eq!(format_number("0.256", "fr", Some("percent")), "26\u{202f}%");
+        assert_eq!(format_number("not a number", "fr", None), "not a number");
+
+        let args = args(&[("d", "1700000000")]);
+        assert_eq!(format("{d, date, long}", &args, "fr"), "14 novembre 2023");
+        assert_eq!(format("{d, date}", &args, "en"), "Nov 14, 2023");
+        assert_eq!(format("{d, time}", &args, "en"), "10:13 PM");
+    }
+}
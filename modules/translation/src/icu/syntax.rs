@@ -0,0 +1,93 @@
+//! The parsing of the message syntax, without dependency so that it is shared with `build.rs`
+
+use std::collections::BTreeSet;
+
+/// Get the names of the arguments used by a translation string, including the ones nested in plural and select
+pub(crate) fn arguments(message: &str) -> BTreeSet<String> {
+    let mut names = BTreeSet::new();
+    let mut rest = message;
+
+    while let Some(start) = rest.find('{') {
+        rest = &rest[start..];
+        let end = match matching_brace(rest) {
+            Some(end) => end,
+            None => break
+        };
+
+        let parts = split_top_level(&rest[1..end], 3);
+        let name = parts[0].trim();
+        if !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            names.insert(name.to_string());
+        }
+
+        if let (Some("plural" | "select"), Some(style)) = (parts.get(1).map(|k| k.trim()), parts.get(2)) {
+            for (_, option) in parse_options(style).unwrap_or_default() {
+                names.extend(arguments(option));
+            }
+        }
+
+        rest = &rest[end + 1..];
+    }
+
+    names
+}
+
+/// Get the index of the brace closing the one at the start of `s`
+pub(crate) fn matching_brace(s: &str) -> Option<usize> {
+    let mut depth = 0usize;
+
+    for (i, c) in s.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 { return Some(i); }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Split at the commas outside of the braces, in at most `max` parts
+pub(crate) fn split_top_level(s: &str, max: usize) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+
+    for (i, c) in s.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 && parts.len() + 1 < max => {
+                parts.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+
+    parts.push(&s[start..]);
+    parts
+}
+
+/// Parse `selector {message} selector {message}...`
+pub(crate) fn parse_options(s: &str) -> Option<Vec<(&str, &str)>> {
+    let mut options = Vec::new();
+    let mut rest = s.trim_start();
+
+    while !rest.is_empty() {
+        let selector_end = rest.find(|c: char| c.is_whitespace() || c == '{')?;
+        let selector = &rest[..selector_end];
+
+        rest = rest[selector_end..].trim_start();
+        if !rest.starts_with('{') { return None; }
+
+        let end = matching_brace(rest)?;
+        options.push((selector, &rest[1..end]));
+        rest = rest[end + 1..].trim_start();
+    }
+
+    Some(options)
+}
@@ -0,0 +1,36 @@
+//! The keys of the reference language, checked at compile time
+//!
+//! `build.rs` generates a module per directory and a struct per key, with a field per placeholder:
+//! `features::xp::lvl_up { username, lvl }` for ``"> `{username}` a atteint le niveau **{lvl}** !"``.
+//! A key that doesn't exist, or a missing placeholder, doesn't compile.
+//!
+//! The keys that aren't valid identifiers are renamed: `8ball` becomes `_8ball` and `const` becomes `r#const`.
+//! See [`checked_message!`](crate::checked_message).
+
+use crate::fmt::formatter::Formatter;
+
+/// A key of the reference language with its arguments
+pub trait Key {
+    const PATH: &'static str;
+
+    /// The formatter holding the arguments of the key
+    fn formatter(&self) -> Formatter;
+}
+
+include!(concat!(env!("OUT_DIR"), "/keys.rs"));
+
+#[cfg(test)]
+mod test {
+    use crate::keys::{features, slash_commands, Key};
+
+    #[test]
+    fn keys_carry_their_path_and_arguments() {
+        assert_eq!(features::ghostping::author::PATH, "features::ghostping::author");
+        // written in TOML
+        assert_eq!(slash_commands::ping::description::PATH, "slash_commands::ping::description");
+
+        let formatter = features::ghostping::single { mention: 1, author: "2" }.formatter();
+        assert_eq!(formatter.get("mention"), Some("1"));
+        assert_eq!(formatter.get("author"), Some("2"));
+    }
+}
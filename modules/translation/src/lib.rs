@@ -18,27 +18,56 @@
 //! }
 //! ```
 //!
+//! The same files can be written in TOML or YAML:
+//! ```toml
+//! path = "..."
+//!
+//! [data]
+//! ...
+//! ```
+//!
+//! The declaration can list the Discord locales of the language with `"discord_locales": ["en-US", "en-GB"]`,
+//! the language itself is used otherwise.
+//!
 //! Please note that the path is each branch names who are separated with `#`
 //! The root is always #
 //! Example:
 //! `#command#help`
+//!
+//! The loaded languages are an immutable snapshot swapped at once on reload, see the `store` module.
+//!
+//! A key missing in the requested language is searched in its base language (`en-US` to `en`),
+//! then in the default language, and is shown as `[[key]]` if no language has it.
+//!
+//! The strings can use plural, select, number and date arguments, e.g.
+//! `{count, plural, one {# cookie} other {# cookies}}`, see the `icu` module.
+//!
+//! The keys of the reference language are also generated at build time, `checked_message!`
+//! refuses the unknown keys and the missing placeholders, see the `keys` module.
 
 mod parser;
+mod icu;
+pub mod coverage;
+pub mod keys;
 pub mod macros;
 pub mod fmt;
+pub mod metrics;
+pub mod store;
 
 use std::collections::HashMap;
 use std::ops::Deref;
 use std::path::Path;
-use std::sync::{Arc, RwLock};
+use std::sync::RwLock;
 use lazy_static::lazy_static;
 use logs::error;
 use serde_json::Value;
 use error::Result;
-use regex::Regex;
 use crate::parser::parse_lang_files;
+use crate::store::Translations;
 
 const SEPARATOR: &str = "::";
+/// The language used when the default one isn't configured
+const DEFAULT_LANGUAGE: &str = "fr";
 
 /// Represents a translation key.
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
@@ -101,6 +130,11 @@ impl TranslationNode {
         self.value.is_none()
     }
 
+    /// The value of a sheet
+    pub fn value(&self) -> Option<&Value> {
+        self.value.as_ref()
+    }
+
     pub fn get_children(&self, k: impl Into<TranslationKey>) -> Option<&Self> {
         if let Some(childrens) = self.children.as_ref() {
             childrens.get(&k.into())
@@ -147,32 +181,51 @@ impl From<TranslationNode> for Value {
 }
 
 lazy_static! {
-    pub static ref TRANSLATIONS: Arc<RwLock<HashMap<Language, TranslationNode>>> = Arc::new(RwLock::new(HashMap::new()));
+    /// The last language of the fallback chain
+    static ref DEFAULT: RwLock<Language> = RwLock::new(Language::from(DEFAULT_LANGUAGE));
+}
 
-    static ref FORMATTING_RG: Regex = Regex::new(r"(?:\{([^{}]+)\})").unwrap();
+/// Get the language used when a translation is missing in the requested one
+pub fn default_language() -> Language {
+    DEFAULT.read().map(|l| l.clone()).unwrap_or_else(|_| Language::from(DEFAULT_LANGUAGE))
 }
 
+/// Set the language used when a translation is missing in the requested one
+pub fn set_default_language(lang: impl Into<Language>) {
+    if let Ok(mut default) = DEFAULT.write() {
+        *default = lang.into();
+    }
+}
+
+/// Load every language of `dir` and swap them with the current translations at once
+///
+/// A language that cannot be parsed keeps its previous translations
 pub fn load_translations(dir: &Path) -> Result<()> {
     let langs = parser::get_langs_dir(dir)?;
+    let previous = store::snapshot();
+    let mut translations = Translations::default();
 
     for lang in langs {
         // for each lang, we parse it
         let files = parser::get_files(Path::new(dir).join(lang.clone()).as_path())?;
+        let lang = Language(lang);
 
         match parse_lang_files(files) {
-            Ok(node) => {
-                let mut translations = TRANSLATIONS.write().expect("Failed to get write lock on TRANSLATIONS");
-                if let Some(lang) = translations.get_mut(&Language(lang.clone())) {
-                    *lang = node;
-                } else {
-                    translations.insert(Language(lang), node);
-                }
-            },
+            Ok(node) => translations.insert(lang, node),
             Err(e) => {
-                error!(target: "Translation", "{:?}", e)
+                error!(target: "Translation", "{:?}", e);
+                if let Some(node) = previous.get(&lang) {
+                    translations.insert(lang, node.clone());
+                }
             }
         }
     }
 
+    store::replace(translations);
     Ok(())
-}
\ No newline at end of file
+}
+
+/// True if the language is loaded
+pub fn is_available(lang: impl Into<Language>) -> bool {
+    store::current().contains(&lang.into())
+}
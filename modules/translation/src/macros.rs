@@ -5,6 +5,11 @@ macro_rules! init {
             std::path::Path::new($dir)
         ).expect("Cannot load translations")
     };
+
+    ($dir:expr, $default:expr) => {
+        $crate::set_default_language($default);
+        $crate::init!($dir)
+    };
 }
 
 #[macro_export]
@@ -16,4 +21,17 @@ macro_rules! message {
     ($lang:expr,$path:expr,$formatter:expr) => {
         $crate::fmt::translate($lang, $path, &$formatter)
     };
-}
\ No newline at end of file
+}
+
+/// Same as `message!`, but the key and its placeholders are checked at compile time
+///
+/// ```ignore
+/// checked_message!(lang, errors::not_guild);
+/// checked_message!(lang, features::xp::lvl_up { username: name, lvl: 4 });
+/// ```
+#[macro_export]
+macro_rules! checked_message {
+    ($lang:expr, $($key:tt)+) => {
+        $crate::fmt::translate_key($lang, &$crate::keys::$($key)+)
+    };
+}
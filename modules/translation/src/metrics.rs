@@ -0,0 +1,48 @@
+//! Count the translations that weren't found in the requested language
+//!
+//! A high count for a language means that its files are incomplete.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use lazy_static::lazy_static;
+use crate::Language;
+
+lazy_static! {
+    static ref METRICS: Mutex<TranslationMetrics> = Mutex::new(TranslationMetrics::default());
+}
+
+/// The fallbacks since the start, by requested language
+#[derive(Debug, Clone, Default)]
+pub struct TranslationMetrics {
+    /// The translations taken from another language of the fallback chain
+    pub fallbacks: HashMap<Language, u64>,
+    /// The translations found in no language, shown as a key marker
+    pub missing: HashMap<Language, u64>
+}
+
+impl TranslationMetrics {
+    pub fn total_fallbacks(&self) -> u64 {
+        self.fallbacks.values().sum()
+    }
+
+    pub fn total_missing(&self) -> u64 {
+        self.missing.values().sum()
+    }
+}
+
+/// Get a copy of the metrics
+pub fn get() -> TranslationMetrics {
+    METRICS.lock().map(|m| m.clone()).unwrap_or_default()
+}
+
+pub(crate) fn record_fallback(requested: &Language) {
+    if let Ok(mut metrics) = METRICS.lock() {
+        *metrics.fallbacks.entry(requested.clone()).or_default() += 1;
+    }
+}
+
+pub(crate) fn record_missing(requested: &Language) {
+    if let Ok(mut metrics) = METRICS.lock() {
+        *metrics.missing.entry(requested.clone()).or_default() += 1;
+    }
+}
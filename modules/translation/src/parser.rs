@@ -27,7 +27,10 @@ pub(crate) fn get_langs_dir(dir: &Path) -> Result<Vec<String>> {
     Ok(langs)
 }
 
-/// Return all files with the extension "json" inside a directory
+/// The extensions of the translation files
+const EXTENSIONS: &[&str] = &["json", "toml", "yaml", "yml"];
+
+/// Return all translation files (JSON, TOML or YAML) inside a directory
 pub(crate) fn get_files(dir: &Path) -> Result<Vec<String>> {
     // Check if the path is a directory
     if !dir.is_dir() { return Err(Error::Fs(FileError::InvalidPath("Not a directory".into()))) }
@@ -50,7 +53,7 @@ pub(crate) fn get_files(dir: &Path) -> Result<Vec<String>> {
             for entry in get_files(file.path().as_path())? {
                 files.push(entry)
             }
-        } else if file.path().extension() == Some(OsStr::new("json")) {
+        } else if file.path().extension().and_then(OsStr::to_str).is_some_and(|e| EXTENSIONS.contains(&e)) {
             if let Some(path) = file.path().to_str() {
                 files.push(path.to_string())
             }
@@ -88,7 +91,7 @@ pub(crate) fn get_files_content(files: &Vec<String>) -> Result<Vec<Value>> {
     for dir in files {
         match std::fs::read_to_string(dir.clone()) {
             Ok(cnt) => {
-                match serde_json::from_str::<Value>(cnt.as_str()) {
+                match parse_content(dir, cnt.as_str()) {
                     Ok(v) => {
                         if check_file_integrity(&v) || is_declaration_file(&v) {
                             contents.push(v)
@@ -96,7 +99,7 @@ pub(crate) fn get_files_content(files: &Vec<String>) -> Result<Vec<Value>> {
                             error!(target: "Translation", "Invalid content at {:?}", dir)
                         }
                     },
-                    Err(e) => return Err(Error::Fs(FileError::CannotReadFile(format!("{:?}", (dir, e.to_string())))))
+                    Err(e) => return Err(Error::Fs(FileError::CannotReadFile(format!("{:?}", (dir, e)))))
                 }
             },
             Err(e) => return Err(Error::Fs(FileError::IOError(format!("{:?}", (dir, e.to_string())))))
@@ -105,6 +108,15 @@ pub(crate) fn get_files_content(files: &Vec<String>) -> Result<Vec<Value>> {
     Ok(contents)
 }
 
+/// Parse a file according to its extension, the TOML and YAML files have the same structure as the JSON ones
+fn parse_content(file: &str, content: &str) -> std::result::Result<Value, String> {
+    match Path::new(file).extension().and_then(OsStr::to_str) {
+        Some("toml") => toml::from_str::<Value>(content).map_err(|e| e.to_string()),
+        Some("yaml" | "yml") => serde_yaml::from_str::<Value>(content).map_err(|e| e.to_string()),
+        _ => serde_json::from_str::<Value>(content).map_err(|e| e.to_string())
+    }
+}
+
 fn manage_declaration(node: &mut TranslationNode, data: Value) {
     if node.children.is_none() {
         node.children = Some(HashMap::new())
@@ -119,6 +131,13 @@ fn manage_declaration(node: &mut TranslationNode, data: Value) {
             TranslationKey("__locale".to_string()),
             TranslationNode { children: None, value: Some(data["locale"].clone()) }
         );
+        // the locales of Discord using this language, e.g. `en-US` and `en-GB` for `en`
+        if let Some(locales) = data.get("discord_locales") {
+            children.insert(
+                TranslationKey("__discord_locales".to_string()),
+                TranslationNode { children: None, value: Some(locales.clone()) }
+            );
+        }
     }
 }
 
@@ -215,4 +234,21 @@ pub(crate) fn add_to_node(node: &mut TranslationNode, key: &TranslationKey, data
             }
         );
     };
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+    use crate::parser::parse_content;
+
+    #[test]
+    fn toml_and_yaml_files_are_parsed_like_json() {
+        let expected = json!({ "path": "features::xp", "data": { "lvl_up": "Niveau {lvl}", "rank": { "title": "Rang" } } });
+
+        let toml = "path = \"features::xp\"\n\n[data]\nlvl_up = \"Niveau {lvl}\"\n\n[data.rank]\ntitle = \"Rang\"\n";
+        let yaml = "path: features::xp\ndata:\n  lvl_up: Niveau {lvl}\n  rank:\n    title: Rang\n";
+
+        assert_eq!(parse_content("xp.toml", toml).unwrap(), expected);
+        assert_eq!(parse_content("xp.yaml", yaml).unwrap(), expected);
+    }
+}
@@ -0,0 +1,81 @@
+//! The loaded translations
+//!
+//! The translations are an immutable snapshot: a reload builds a new snapshot and swaps it atomically,
+//! the lookups never wait for a lock and never see a half-loaded language.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use arc_swap::{ArcSwap, Guard};
+use lazy_static::lazy_static;
+use crate::{Language, TranslationNode};
+
+/// A snapshot of the translations of every language
+#[derive(Debug, Clone, Default)]
+pub struct Translations {
+    languages: HashMap<Language, TranslationNode>
+}
+
+impl Translations {
+    /// Get the root node of a language
+    pub fn get(&self, lang: &Language) -> Option<&TranslationNode> {
+        self.languages.get(lang)
+    }
+
+    pub fn contains(&self, lang: &Language) -> bool {
+        self.languages.contains_key(lang)
+    }
+
+    pub fn languages(&self) -> impl Iterator<Item = &Language> {
+        self.languages.keys()
+    }
+
+    pub(crate) fn insert(&mut self, lang: Language, root: TranslationNode) {
+        self.languages.insert(lang, root);
+    }
+}
+
+lazy_static! {
+    static ref STORE: ArcSwap<Translations> = ArcSwap::from_pointee(Translations::default());
+}
+
+/// Get the current snapshot, it stays the same even if the translations are reloaded meanwhile
+pub fn snapshot() -> Arc<Translations> {
+    STORE.load_full()
+}
+
+/// Borrow the current snapshot without touching its reference count, for the short lookups
+pub(crate) fn current() -> Guard<Arc<Translations>> {
+    STORE.load()
+}
+
+/// Replace every translation at once
+pub(crate) fn replace(translations: Translations) {
+    STORE.store(Arc::new(translations));
+}
+
+/// Update a copy of the current snapshot and swap it
+#[cfg(test)]
+pub(crate) fn update(f: impl Fn(&mut Translations)) {
+    STORE.rcu(|current| {
+        let mut translations = Translations::clone(current);
+        f(&mut translations);
+        translations
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Language, TranslationNode};
+    use crate::store::{snapshot, update};
+
+    #[test]
+    fn snapshots_are_immutable() {
+        let lang = Language::from("zz-snapshot");
+        let before = snapshot();
+
+        update(|translations| translations.insert(lang.clone(), TranslationNode::default()));
+
+        assert!(!before.contains(&lang));
+        assert!(snapshot().contains(&lang));
+    }
+}